@@ -21,30 +21,87 @@ Register-ArgumentCompleter -Native -CommandName 'roco' -ScriptBlock {
 
     $completions = @(switch ($command) {
         'roco' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
             [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('-V', 'V ', [CompletionResultType]::ParameterName, 'Print version')
             [CompletionResult]::new('--version', 'version', [CompletionResultType]::ParameterName, 'Print version')
             [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list local installed packages')
+            [CompletionResult]::new('info', 'info', [CompletionResultType]::ParameterValue, 'show the nuspec metadata of a local installed package, or of a package on a source')
+            [CompletionResult]::new('verify', 'verify', [CompletionResultType]::ParameterValue, 'verify installed files against the checksums recorded by choco (.chocolatey/<id>.<version>/.files)')
+            [CompletionResult]::new('doctor', 'doctor', [CompletionResultType]::ParameterValue, 'scan the installation for broken lib folders, duplicates and interrupted installs')
             [CompletionResult]::new('bad', 'bad', [CompletionResultType]::ParameterValue, 'list packages in lib-bad/')
             [CompletionResult]::new('outdated', 'outdated', [CompletionResultType]::ParameterValue, 'Returns a list of outdated packages.')
-            [CompletionResult]::new('source', 'source', [CompletionResultType]::ParameterValue, 'list choco sources')
+            [CompletionResult]::new('search', 'search', [CompletionResultType]::ParameterValue, 'search packages on all enabled sources')
+            [CompletionResult]::new('versions', 'versions', [CompletionResultType]::ParameterValue, 'list all available versions of a package on each enabled source')
+            [CompletionResult]::new('source', 'source', [CompletionResultType]::ParameterValue, 'list and manage choco sources')
+            [CompletionResult]::new('deps', 'deps', [CompletionResultType]::ParameterValue, 'analyze dependencies of local installed packages')
+            [CompletionResult]::new('pin', 'pin', [CompletionResultType]::ParameterValue, 'list, add or remove package pins (pinned packages are excluded from upgrades)')
+            [CompletionResult]::new('export', 'export', [CompletionResultType]::ParameterValue, 'export local installed packages (e.g. to packages.config, for `choco install packages.config`)')
+            [CompletionResult]::new('diff', 'diff', [CompletionResultType]::ParameterValue, 'compare two package sets (added, removed, upgraded, downgraded and pin changed packages)')
+            [CompletionResult]::new('drift', 'drift', [CompletionResultType]::ParameterValue, 'check the local installation against a baseline manifest (required/absent packages, versions, pins)')
+            [CompletionResult]::new('feature', 'feature', [CompletionResultType]::ParameterValue, 'list choco features')
+            [CompletionResult]::new('config', 'config', [CompletionResultType]::ParameterValue, 'retrieve choco config settings')
             [CompletionResult]::new('license', 'license', [CompletionResultType]::ParameterValue, 'display license information')
             [CompletionResult]::new('upgrade', 'upgrade', [CompletionResultType]::ParameterValue, 'upgrade outdated choco packages (using choco.exe)')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
         }
         'roco;list' {
+            [CompletionResult]::new('--dependents', 'dependents', [CompletionResultType]::ParameterName, 'list packages depending on the given package')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
             [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
             [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
             [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
             [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
             [CompletionResult]::new('--dependency-tree', 'dependency-tree', [CompletionResultType]::ParameterName, 'list dependencies')
+            [CompletionResult]::new('--transitive', 'transitive', [CompletionResultType]::ParameterName, 'include indirect dependents')
+            [CompletionResult]::new('--details', 'details', [CompletionResultType]::ParameterName, 'include disk usage, install time and pin status')
+            [CompletionResult]::new('--json', 'json', [CompletionResultType]::ParameterName, 'print details as JSON')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;info' {
+            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 'name, url or path of the source to fetch the metadata from')
+            [CompletionResult]::new('--source', 'source', [CompletionResultType]::ParameterName, 'name, url or path of the source to fetch the metadata from')
+            [CompletionResult]::new('--version', 'version', [CompletionResultType]::ParameterName, 'version of the package on the source (default: the latest one)')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-p', 'p', [CompletionResultType]::ParameterName, 'include prerelease versions')
+            [CompletionResult]::new('--pre', 'pre', [CompletionResultType]::ParameterName, 'include prerelease versions')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--sslcheck', 'sslcheck', [CompletionResultType]::ParameterName, 'require https/ssl-validation')
+            [CompletionResult]::new('--semver2', 'semver2', [CompletionResultType]::ParameterName, 'compare prerelease versions using SemVer 2.0 rules 
+(default: same ordering as choco)')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;verify' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;doctor' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
             [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
         'roco;bad' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
             [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
             [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
             [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
@@ -54,6 +111,7 @@ Register-ArgumentCompleter -Native -CommandName 'roco' -ScriptBlock {
             break
         }
         'roco;outdated' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
             [CompletionResult]::new('--choco-mode', 'choco-mode', [CompletionResultType]::ParameterName, 'enables ''ignore-pinned'' and ''ignore-unfound'' 
 (otherwise they are true by default, even if not set)')
             [CompletionResult]::new('--ignore-pinned', 'ignore-pinned', [CompletionResultType]::ParameterName, 'ignore any pinned packages 
@@ -63,6 +121,44 @@ Register-ArgumentCompleter -Native -CommandName 'roco' -ScriptBlock {
             [CompletionResult]::new('-l', 'l', [CompletionResultType]::ParameterName, 'output a whitespace-separated list of results')
             [CompletionResult]::new('-p', 'p', [CompletionResultType]::ParameterName, 'include prerelease versions')
             [CompletionResult]::new('--pre', 'pre', [CompletionResultType]::ParameterName, 'include prerelease versions')
+            [CompletionResult]::new('--semver2', 'semver2', [CompletionResultType]::ParameterName, 'compare prerelease versions using SemVer 2.0 rules 
+(default: same ordering as choco)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--sslcheck', 'sslcheck', [CompletionResultType]::ParameterName, 'require https/ssl-validation')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;search' {
+            [CompletionResult]::new('--page', 'page', [CompletionResultType]::ParameterName, 'page of results to show (0-based)')
+            [CompletionResult]::new('--page-size', 'page-size', [CompletionResultType]::ParameterName, 'number of results per page')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-e', 'e', [CompletionResultType]::ParameterName, 'only the package with exactly this id')
+            [CompletionResult]::new('--exact', 'exact', [CompletionResultType]::ParameterName, 'only the package with exactly this id')
+            [CompletionResult]::new('-a', 'a', [CompletionResultType]::ParameterName, 'list all versions instead of the latest one')
+            [CompletionResult]::new('--all-versions', 'all-versions', [CompletionResultType]::ParameterName, 'list all versions instead of the latest one')
+            [CompletionResult]::new('-p', 'p', [CompletionResultType]::ParameterName, 'include prerelease versions')
+            [CompletionResult]::new('--pre', 'pre', [CompletionResultType]::ParameterName, 'include prerelease versions')
+            [CompletionResult]::new('--semver2', 'semver2', [CompletionResultType]::ParameterName, 'compare prerelease versions using SemVer 2.0 rules 
+(default: same ordering as choco)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--sslcheck', 'sslcheck', [CompletionResultType]::ParameterName, 'require https/ssl-validation')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;versions' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-p', 'p', [CompletionResultType]::ParameterName, 'include prerelease versions')
+            [CompletionResult]::new('--pre', 'pre', [CompletionResultType]::ParameterName, 'include prerelease versions')
+            [CompletionResult]::new('--semver2', 'semver2', [CompletionResultType]::ParameterName, 'compare prerelease versions using SemVer 2.0 rules 
+(default: same ordering as choco)')
             [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
             [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
             [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
@@ -73,6 +169,137 @@ Register-ArgumentCompleter -Native -CommandName 'roco' -ScriptBlock {
             break
         }
         'roco;source' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list choco sources (default)')
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'add a source, or update an existing one')
+            [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'remove a source')
+            [CompletionResult]::new('enable', 'enable', [CompletionResultType]::ParameterValue, 'enable a source')
+            [CompletionResult]::new('disable', 'disable', [CompletionResultType]::ParameterValue, 'disable a source')
+            [CompletionResult]::new('priority', 'priority', [CompletionResultType]::ParameterValue, 'change the priority of a source')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'roco;source;list' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;source;add' {
+            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'name of the source')
+            [CompletionResult]::new('--name', 'name', [CompletionResultType]::ParameterName, 'name of the source')
+            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 'url or path of the source')
+            [CompletionResult]::new('--source', 'source', [CompletionResultType]::ParameterName, 'url or path of the source')
+            [CompletionResult]::new('-u', 'u', [CompletionResultType]::ParameterName, 'user name for authenticated sources')
+            [CompletionResult]::new('--user', 'user', [CompletionResultType]::ParameterName, 'user name for authenticated sources')
+            [CompletionResult]::new('-p', 'p', [CompletionResultType]::ParameterName, 'password for authenticated sources (stored encrypted)')
+            [CompletionResult]::new('--password', 'password', [CompletionResultType]::ParameterName, 'password for authenticated sources (stored encrypted)')
+            [CompletionResult]::new('--cert', 'cert', [CompletionResultType]::ParameterName, 'path to a client certificate')
+            [CompletionResult]::new('--certpassword', 'certpassword', [CompletionResultType]::ParameterName, 'password for the client certificate (stored encrypted)')
+            [CompletionResult]::new('--priority', 'priority', [CompletionResultType]::ParameterName, 'source priority, lower values take precedence (0 = no priority)')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('--bypassproxy', 'bypassproxy', [CompletionResultType]::ParameterName, 'do not use the configured proxy for this source')
+            [CompletionResult]::new('--allowselfservice', 'allowselfservice', [CompletionResultType]::ParameterName, 'allow self-service for this source')
+            [CompletionResult]::new('--adminonly', 'adminonly', [CompletionResultType]::ParameterName, 'make this source visible to administrators only')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;source;remove' {
+            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'name of the source')
+            [CompletionResult]::new('--name', 'name', [CompletionResultType]::ParameterName, 'name of the source')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;source;enable' {
+            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'name of the source')
+            [CompletionResult]::new('--name', 'name', [CompletionResultType]::ParameterName, 'name of the source')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;source;disable' {
+            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'name of the source')
+            [CompletionResult]::new('--name', 'name', [CompletionResultType]::ParameterName, 'name of the source')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;source;priority' {
+            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'name of the source')
+            [CompletionResult]::new('--name', 'name', [CompletionResultType]::ParameterName, 'name of the source')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;source;help' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list choco sources (default)')
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'add a source, or update an existing one')
+            [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'remove a source')
+            [CompletionResult]::new('enable', 'enable', [CompletionResultType]::ParameterValue, 'enable a source')
+            [CompletionResult]::new('disable', 'disable', [CompletionResultType]::ParameterValue, 'disable a source')
+            [CompletionResult]::new('priority', 'priority', [CompletionResultType]::ParameterValue, 'change the priority of a source')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'roco;source;help;list' {
+            break
+        }
+        'roco;source;help;add' {
+            break
+        }
+        'roco;source;help;remove' {
+            break
+        }
+        'roco;source;help;enable' {
+            break
+        }
+        'roco;source;help;disable' {
+            break
+        }
+        'roco;source;help;priority' {
+            break
+        }
+        'roco;source;help;help' {
+            break
+        }
+        'roco;deps' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'verify the dependency constraints of all local packages are satisfied')
+            [CompletionResult]::new('orphans', 'orphans', [CompletionResultType]::ParameterValue, 'list dependency-only packages (.install, .portable, .extension) no local package depends on')
+            [CompletionResult]::new('graph', 'graph', [CompletionResultType]::ParameterValue, 'export the dependency graph of all local packages')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'roco;deps;check' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
             [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
             [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
             [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
@@ -81,7 +308,219 @@ Register-ArgumentCompleter -Native -CommandName 'roco' -ScriptBlock {
             [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
+        'roco;deps;orphans' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;deps;graph' {
+            [CompletionResult]::new('-f', 'f', [CompletionResultType]::ParameterName, 'output format')
+            [CompletionResult]::new('--format', 'format', [CompletionResultType]::ParameterName, 'output format')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;deps;help' {
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'verify the dependency constraints of all local packages are satisfied')
+            [CompletionResult]::new('orphans', 'orphans', [CompletionResultType]::ParameterValue, 'list dependency-only packages (.install, .portable, .extension) no local package depends on')
+            [CompletionResult]::new('graph', 'graph', [CompletionResultType]::ParameterValue, 'export the dependency graph of all local packages')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'roco;deps;help;check' {
+            break
+        }
+        'roco;deps;help;orphans' {
+            break
+        }
+        'roco;deps;help;graph' {
+            break
+        }
+        'roco;deps;help;help' {
+            break
+        }
+        'roco;pin' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list pinned packages')
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'pin a local installed package')
+            [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'remove the pin of a local installed package')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'roco;pin;list' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;pin;add' {
+            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'id of the package')
+            [CompletionResult]::new('--name', 'name', [CompletionResultType]::ParameterName, 'id of the package')
+            [CompletionResult]::new('--version', 'version', [CompletionResultType]::ParameterName, 'version of the package (default: the installed version)')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;pin;remove' {
+            [CompletionResult]::new('-n', 'n', [CompletionResultType]::ParameterName, 'id of the package')
+            [CompletionResult]::new('--name', 'name', [CompletionResultType]::ParameterName, 'id of the package')
+            [CompletionResult]::new('--version', 'version', [CompletionResultType]::ParameterName, 'version of the package (default: the installed version)')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;pin;help' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list pinned packages')
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'pin a local installed package')
+            [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'remove the pin of a local installed package')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'roco;pin;help;list' {
+            break
+        }
+        'roco;pin;help;add' {
+            break
+        }
+        'roco;pin;help;remove' {
+            break
+        }
+        'roco;pin;help;help' {
+            break
+        }
+        'roco;export' {
+            [CompletionResult]::new('-f', 'f', [CompletionResultType]::ParameterName, 'output format')
+            [CompletionResult]::new('--format', 'format', [CompletionResultType]::ParameterName, 'output format')
+            [CompletionResult]::new('-s', 's', [CompletionResultType]::ParameterName, 'source to install the packages from')
+            [CompletionResult]::new('--source', 'source', [CompletionResultType]::ParameterName, 'source to install the packages from')
+            [CompletionResult]::new('-o', 'o', [CompletionResultType]::ParameterName, 'file to write to (default: stdout)')
+            [CompletionResult]::new('--output', 'output', [CompletionResultType]::ParameterName, 'file to write to (default: stdout)')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('--include-versions', 'include-versions', [CompletionResultType]::ParameterName, 'record the installed versions 
+(default: latest versions are installed on import)')
+            [CompletionResult]::new('--exclude-dependencies', 'exclude-dependencies', [CompletionResultType]::ParameterName, 'only record top-level packages (no other installed package depends on)')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;diff' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--json', 'json', [CompletionResultType]::ParameterName, 'print differences as JSON')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;drift' {
+            [CompletionResult]::new('-b', 'b', [CompletionResultType]::ParameterName, 'JSON baseline manifest')
+            [CompletionResult]::new('--baseline', 'baseline', [CompletionResultType]::ParameterName, 'JSON baseline manifest')
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;feature' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list choco features')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'roco;feature;list' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;feature;help' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list choco features')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'roco;feature;help;list' {
+            break
+        }
+        'roco;feature;help;help' {
+            break
+        }
+        'roco;config' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list choco config settings')
+            [CompletionResult]::new('get', 'get', [CompletionResultType]::ParameterValue, 'get the value of a choco config setting')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'roco;config;list' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;config;get' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
+            [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('--verbose', 'verbose', [CompletionResultType]::ParameterName, 'be verbose')
+            [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', 'help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'roco;config;help' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list choco config settings')
+            [CompletionResult]::new('get', 'get', [CompletionResultType]::ParameterValue, 'get the value of a choco config setting')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'roco;config;help;list' {
+            break
+        }
+        'roco;config;help;get' {
+            break
+        }
+        'roco;config;help;help' {
+            break
+        }
         'roco;license' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
             [CompletionResult]::new('-f', 'f', [CompletionResultType]::ParameterName, 'display full license information')
             [CompletionResult]::new('--full', 'full', [CompletionResultType]::ParameterName, 'display full license information')
             [CompletionResult]::new('-h', 'h', [CompletionResultType]::ParameterName, 'Print help')
@@ -89,8 +528,11 @@ Register-ArgumentCompleter -Native -CommandName 'roco' -ScriptBlock {
             break
         }
         'roco;upgrade' {
+            [CompletionResult]::new('--choco-root', 'choco-root', [CompletionResultType]::ParameterName, 'Chocolatey installation to inspect (default: env:ChocolateyInstall)')
             [CompletionResult]::new('-p', 'p', [CompletionResultType]::ParameterName, 'include prerelease versions')
             [CompletionResult]::new('--pre', 'pre', [CompletionResultType]::ParameterName, 'include prerelease versions')
+            [CompletionResult]::new('--semver2', 'semver2', [CompletionResultType]::ParameterName, 'compare prerelease versions using SemVer 2.0 rules 
+(default: same ordering as choco)')
             [CompletionResult]::new('-r', 'r', [CompletionResultType]::ParameterName, 'limit the output to essential information')
             [CompletionResult]::new('--limitoutput', 'limitoutput', [CompletionResultType]::ParameterName, 'limit the output to essential information')
             [CompletionResult]::new('-v', 'v', [CompletionResultType]::ParameterName, 'be verbose')
@@ -102,9 +544,21 @@ Register-ArgumentCompleter -Native -CommandName 'roco' -ScriptBlock {
         }
         'roco;help' {
             [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list local installed packages')
+            [CompletionResult]::new('info', 'info', [CompletionResultType]::ParameterValue, 'show the nuspec metadata of a local installed package, or of a package on a source')
+            [CompletionResult]::new('verify', 'verify', [CompletionResultType]::ParameterValue, 'verify installed files against the checksums recorded by choco (.chocolatey/<id>.<version>/.files)')
+            [CompletionResult]::new('doctor', 'doctor', [CompletionResultType]::ParameterValue, 'scan the installation for broken lib folders, duplicates and interrupted installs')
             [CompletionResult]::new('bad', 'bad', [CompletionResultType]::ParameterValue, 'list packages in lib-bad/')
             [CompletionResult]::new('outdated', 'outdated', [CompletionResultType]::ParameterValue, 'Returns a list of outdated packages.')
-            [CompletionResult]::new('source', 'source', [CompletionResultType]::ParameterValue, 'list choco sources')
+            [CompletionResult]::new('search', 'search', [CompletionResultType]::ParameterValue, 'search packages on all enabled sources')
+            [CompletionResult]::new('versions', 'versions', [CompletionResultType]::ParameterValue, 'list all available versions of a package on each enabled source')
+            [CompletionResult]::new('source', 'source', [CompletionResultType]::ParameterValue, 'list and manage choco sources')
+            [CompletionResult]::new('deps', 'deps', [CompletionResultType]::ParameterValue, 'analyze dependencies of local installed packages')
+            [CompletionResult]::new('pin', 'pin', [CompletionResultType]::ParameterValue, 'list, add or remove package pins (pinned packages are excluded from upgrades)')
+            [CompletionResult]::new('export', 'export', [CompletionResultType]::ParameterValue, 'export local installed packages (e.g. to packages.config, for `choco install packages.config`)')
+            [CompletionResult]::new('diff', 'diff', [CompletionResultType]::ParameterValue, 'compare two package sets (added, removed, upgraded, downgraded and pin changed packages)')
+            [CompletionResult]::new('drift', 'drift', [CompletionResultType]::ParameterValue, 'check the local installation against a baseline manifest (required/absent packages, versions, pins)')
+            [CompletionResult]::new('feature', 'feature', [CompletionResultType]::ParameterValue, 'list choco features')
+            [CompletionResult]::new('config', 'config', [CompletionResultType]::ParameterValue, 'retrieve choco config settings')
             [CompletionResult]::new('license', 'license', [CompletionResultType]::ParameterValue, 'display license information')
             [CompletionResult]::new('upgrade', 'upgrade', [CompletionResultType]::ParameterValue, 'upgrade outdated choco packages (using choco.exe)')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
@@ -113,13 +567,109 @@ Register-ArgumentCompleter -Native -CommandName 'roco' -ScriptBlock {
         'roco;help;list' {
             break
         }
+        'roco;help;info' {
+            break
+        }
+        'roco;help;verify' {
+            break
+        }
+        'roco;help;doctor' {
+            break
+        }
         'roco;help;bad' {
             break
         }
         'roco;help;outdated' {
             break
         }
+        'roco;help;search' {
+            break
+        }
+        'roco;help;versions' {
+            break
+        }
         'roco;help;source' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list choco sources (default)')
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'add a source, or update an existing one')
+            [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'remove a source')
+            [CompletionResult]::new('enable', 'enable', [CompletionResultType]::ParameterValue, 'enable a source')
+            [CompletionResult]::new('disable', 'disable', [CompletionResultType]::ParameterValue, 'disable a source')
+            [CompletionResult]::new('priority', 'priority', [CompletionResultType]::ParameterValue, 'change the priority of a source')
+            break
+        }
+        'roco;help;source;list' {
+            break
+        }
+        'roco;help;source;add' {
+            break
+        }
+        'roco;help;source;remove' {
+            break
+        }
+        'roco;help;source;enable' {
+            break
+        }
+        'roco;help;source;disable' {
+            break
+        }
+        'roco;help;source;priority' {
+            break
+        }
+        'roco;help;deps' {
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'verify the dependency constraints of all local packages are satisfied')
+            [CompletionResult]::new('orphans', 'orphans', [CompletionResultType]::ParameterValue, 'list dependency-only packages (.install, .portable, .extension) no local package depends on')
+            [CompletionResult]::new('graph', 'graph', [CompletionResultType]::ParameterValue, 'export the dependency graph of all local packages')
+            break
+        }
+        'roco;help;deps;check' {
+            break
+        }
+        'roco;help;deps;orphans' {
+            break
+        }
+        'roco;help;deps;graph' {
+            break
+        }
+        'roco;help;pin' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list pinned packages')
+            [CompletionResult]::new('add', 'add', [CompletionResultType]::ParameterValue, 'pin a local installed package')
+            [CompletionResult]::new('remove', 'remove', [CompletionResultType]::ParameterValue, 'remove the pin of a local installed package')
+            break
+        }
+        'roco;help;pin;list' {
+            break
+        }
+        'roco;help;pin;add' {
+            break
+        }
+        'roco;help;pin;remove' {
+            break
+        }
+        'roco;help;export' {
+            break
+        }
+        'roco;help;diff' {
+            break
+        }
+        'roco;help;drift' {
+            break
+        }
+        'roco;help;feature' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list choco features')
+            break
+        }
+        'roco;help;feature;list' {
+            break
+        }
+        'roco;help;config' {
+            [CompletionResult]::new('list', 'list', [CompletionResultType]::ParameterValue, 'list choco config settings')
+            [CompletionResult]::new('get', 'get', [CompletionResultType]::ParameterValue, 'get the value of a choco config setting')
+            break
+        }
+        'roco;help;config;list' {
+            break
+        }
+        'roco;help;config;get' {
             break
         }
         'roco;help;license' {
@@ -1,19 +1,57 @@
+complete -c roco -n "__fish_use_subcommand" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
 complete -c roco -n "__fish_use_subcommand" -s h -l help -d 'Print help'
 complete -c roco -n "__fish_use_subcommand" -s V -l version -d 'Print version'
 complete -c roco -n "__fish_use_subcommand" -f -a "list" -d 'list local installed packages'
+complete -c roco -n "__fish_use_subcommand" -f -a "info" -d 'show the nuspec metadata of a local installed package, or of a package on a source'
+complete -c roco -n "__fish_use_subcommand" -f -a "verify" -d 'verify installed files against the checksums recorded by choco (.chocolatey/<id>.<version>/.files)'
+complete -c roco -n "__fish_use_subcommand" -f -a "doctor" -d 'scan the installation for broken lib folders, duplicates and interrupted installs'
 complete -c roco -n "__fish_use_subcommand" -f -a "bad" -d 'list packages in lib-bad/'
 complete -c roco -n "__fish_use_subcommand" -f -a "outdated" -d 'Returns a list of outdated packages.'
-complete -c roco -n "__fish_use_subcommand" -f -a "source" -d 'list choco sources'
+complete -c roco -n "__fish_use_subcommand" -f -a "search" -d 'search packages on all enabled sources'
+complete -c roco -n "__fish_use_subcommand" -f -a "versions" -d 'list all available versions of a package on each enabled source'
+complete -c roco -n "__fish_use_subcommand" -f -a "source" -d 'list and manage choco sources'
+complete -c roco -n "__fish_use_subcommand" -f -a "deps" -d 'analyze dependencies of local installed packages'
+complete -c roco -n "__fish_use_subcommand" -f -a "pin" -d 'list, add or remove package pins (pinned packages are excluded from upgrades)'
+complete -c roco -n "__fish_use_subcommand" -f -a "export" -d 'export local installed packages (e.g. to packages.config, for `choco install packages.config`)'
+complete -c roco -n "__fish_use_subcommand" -f -a "diff" -d 'compare two package sets (added, removed, upgraded, downgraded and pin changed packages)'
+complete -c roco -n "__fish_use_subcommand" -f -a "drift" -d 'check the local installation against a baseline manifest (required/absent packages, versions, pins)'
+complete -c roco -n "__fish_use_subcommand" -f -a "feature" -d 'list choco features'
+complete -c roco -n "__fish_use_subcommand" -f -a "config" -d 'retrieve choco config settings'
 complete -c roco -n "__fish_use_subcommand" -f -a "license" -d 'display license information'
 complete -c roco -n "__fish_use_subcommand" -f -a "upgrade" -d 'upgrade outdated choco packages (using choco.exe)'
 complete -c roco -n "__fish_use_subcommand" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from list" -l dependents -d 'list packages depending on the given package' -r
+complete -c roco -n "__fish_seen_subcommand_from list" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
 complete -c roco -n "__fish_seen_subcommand_from list" -s r -l limitoutput -d 'limit the output to essential information'
 complete -c roco -n "__fish_seen_subcommand_from list" -s v -l verbose -d 'be verbose'
 complete -c roco -n "__fish_seen_subcommand_from list" -l dependency-tree -d 'list dependencies'
+complete -c roco -n "__fish_seen_subcommand_from list" -l transitive -d 'include indirect dependents'
+complete -c roco -n "__fish_seen_subcommand_from list" -l details -d 'include disk usage, install time and pin status'
+complete -c roco -n "__fish_seen_subcommand_from list" -l json -d 'print details as JSON'
 complete -c roco -n "__fish_seen_subcommand_from list" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from info" -s s -l source -d 'name, url or path of the source to fetch the metadata from' -r
+complete -c roco -n "__fish_seen_subcommand_from info" -l version -d 'version of the package on the source (default: the latest one)' -r
+complete -c roco -n "__fish_seen_subcommand_from info" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from info" -s p -l pre -d 'include prerelease versions'
+complete -c roco -n "__fish_seen_subcommand_from info" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from info" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from info" -l sslcheck -d 'require https/ssl-validation'
+complete -c roco -n "__fish_seen_subcommand_from info" -l semver2 -d 'compare prerelease versions using SemVer 2.0 rules 
+(default: same ordering as choco)'
+complete -c roco -n "__fish_seen_subcommand_from info" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from verify" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from verify" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from verify" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from verify" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from doctor" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from doctor" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from doctor" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from doctor" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from bad" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
 complete -c roco -n "__fish_seen_subcommand_from bad" -s r -l limitoutput -d 'limit the output to essential information'
 complete -c roco -n "__fish_seen_subcommand_from bad" -s v -l verbose -d 'be verbose'
 complete -c roco -n "__fish_seen_subcommand_from bad" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from outdated" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
 complete -c roco -n "__fish_seen_subcommand_from outdated" -l choco-mode -d 'enables \'ignore-pinned\' and \'ignore-unfound\' 
 (otherwise they are true by default, even if not set)'
 complete -c roco -n "__fish_seen_subcommand_from outdated" -l ignore-pinned -d 'ignore any pinned packages 
@@ -22,24 +60,215 @@ complete -c roco -n "__fish_seen_subcommand_from outdated" -l ignore-unfound -d
 (default, unless \'choco-mode\' is set)'
 complete -c roco -n "__fish_seen_subcommand_from outdated" -s l -d 'output a whitespace-separated list of results'
 complete -c roco -n "__fish_seen_subcommand_from outdated" -s p -l pre -d 'include prerelease versions'
+complete -c roco -n "__fish_seen_subcommand_from outdated" -l semver2 -d 'compare prerelease versions using SemVer 2.0 rules 
+(default: same ordering as choco)'
 complete -c roco -n "__fish_seen_subcommand_from outdated" -s r -l limitoutput -d 'limit the output to essential information'
 complete -c roco -n "__fish_seen_subcommand_from outdated" -s v -l verbose -d 'be verbose'
 complete -c roco -n "__fish_seen_subcommand_from outdated" -l sslcheck -d 'require https/ssl-validation'
 complete -c roco -n "__fish_seen_subcommand_from outdated" -s h -l help -d 'Print help'
-complete -c roco -n "__fish_seen_subcommand_from source" -s r -l limitoutput -d 'limit the output to essential information'
-complete -c roco -n "__fish_seen_subcommand_from source" -s v -l verbose -d 'be verbose'
-complete -c roco -n "__fish_seen_subcommand_from source" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from search" -l page -d 'page of results to show (0-based)' -r
+complete -c roco -n "__fish_seen_subcommand_from search" -l page-size -d 'number of results per page' -r
+complete -c roco -n "__fish_seen_subcommand_from search" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from search" -s e -l exact -d 'only the package with exactly this id'
+complete -c roco -n "__fish_seen_subcommand_from search" -s a -l all-versions -d 'list all versions instead of the latest one'
+complete -c roco -n "__fish_seen_subcommand_from search" -s p -l pre -d 'include prerelease versions'
+complete -c roco -n "__fish_seen_subcommand_from search" -l semver2 -d 'compare prerelease versions using SemVer 2.0 rules 
+(default: same ordering as choco)'
+complete -c roco -n "__fish_seen_subcommand_from search" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from search" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from search" -l sslcheck -d 'require https/ssl-validation'
+complete -c roco -n "__fish_seen_subcommand_from search" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from versions" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from versions" -s p -l pre -d 'include prerelease versions'
+complete -c roco -n "__fish_seen_subcommand_from versions" -l semver2 -d 'compare prerelease versions using SemVer 2.0 rules 
+(default: same ordering as choco)'
+complete -c roco -n "__fish_seen_subcommand_from versions" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from versions" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from versions" -l sslcheck -d 'require https/ssl-validation'
+complete -c roco -n "__fish_seen_subcommand_from versions" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "list" -d 'list choco sources (default)'
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "add" -d 'add a source, or update an existing one'
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "remove" -d 'remove a source'
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "enable" -d 'enable a source'
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "disable" -d 'disable a source'
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "priority" -d 'change the priority of a source'
+complete -c roco -n "__fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from list" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from list" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from list" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -s n -l name -d 'name of the source' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -s s -l source -d 'url or path of the source' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -s u -l user -d 'user name for authenticated sources' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -s p -l password -d 'password for authenticated sources (stored encrypted)' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -l cert -d 'path to a client certificate' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -l certpassword -d 'password for the client certificate (stored encrypted)' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -l priority -d 'source priority, lower values take precedence (0 = no priority)' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -l bypassproxy -d 'do not use the configured proxy for this source'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -l allowselfservice -d 'allow self-service for this source'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -l adminonly -d 'make this source visible to administrators only'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from add" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from remove" -s n -l name -d 'name of the source' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from remove" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from remove" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from remove" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from enable" -s n -l name -d 'name of the source' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from enable" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from enable" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from enable" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from disable" -s n -l name -d 'name of the source' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from disable" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from disable" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from disable" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from priority" -s n -l name -d 'name of the source' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from priority" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from priority" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from priority" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "list" -d 'list choco sources (default)'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "add" -d 'add a source, or update an existing one'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "remove" -d 'remove a source'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "enable" -d 'enable a source'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "disable" -d 'disable a source'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "priority" -d 'change the priority of a source'
+complete -c roco -n "__fish_seen_subcommand_from source; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph; and not __fish_seen_subcommand_from help" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph; and not __fish_seen_subcommand_from help" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph; and not __fish_seen_subcommand_from help" -f -a "check" -d 'verify the dependency constraints of all local packages are satisfied'
+complete -c roco -n "__fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph; and not __fish_seen_subcommand_from help" -f -a "orphans" -d 'list dependency-only packages (.install, .portable, .extension) no local package depends on'
+complete -c roco -n "__fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph; and not __fish_seen_subcommand_from help" -f -a "graph" -d 'export the dependency graph of all local packages'
+complete -c roco -n "__fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from check" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from check" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from check" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from check" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from orphans" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from orphans" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from orphans" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from orphans" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from graph" -s f -l format -d 'output format' -r -f -a "{dot	'',mermaid	'',json	''}"
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from graph" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from graph" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from graph" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph; and not __fish_seen_subcommand_from help" -f -a "check" -d 'verify the dependency constraints of all local packages are satisfied'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph; and not __fish_seen_subcommand_from help" -f -a "orphans" -d 'list dependency-only packages (.install, .portable, .extension) no local package depends on'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph; and not __fish_seen_subcommand_from help" -f -a "graph" -d 'export the dependency graph of all local packages'
+complete -c roco -n "__fish_seen_subcommand_from deps; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from help" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from help" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from help" -f -a "list" -d 'list pinned packages'
+complete -c roco -n "__fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from help" -f -a "add" -d 'pin a local installed package'
+complete -c roco -n "__fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from help" -f -a "remove" -d 'remove the pin of a local installed package'
+complete -c roco -n "__fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from list" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from list" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from list" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from add" -s n -l name -d 'id of the package' -r
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from add" -l version -d 'version of the package (default: the installed version)' -r
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from add" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from add" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from add" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from remove" -s n -l name -d 'id of the package' -r
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from remove" -l version -d 'version of the package (default: the installed version)' -r
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from remove" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from remove" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from remove" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from help" -f -a "list" -d 'list pinned packages'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from help" -f -a "add" -d 'pin a local installed package'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from help" -f -a "remove" -d 'remove the pin of a local installed package'
+complete -c roco -n "__fish_seen_subcommand_from pin; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from export" -s f -l format -d 'output format' -r -f -a "{config	'',json	'',toml	''}"
+complete -c roco -n "__fish_seen_subcommand_from export" -s s -l source -d 'source to install the packages from' -r
+complete -c roco -n "__fish_seen_subcommand_from export" -s o -l output -d 'file to write to (default: stdout)' -r
+complete -c roco -n "__fish_seen_subcommand_from export" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from export" -l include-versions -d 'record the installed versions 
+(default: latest versions are installed on import)'
+complete -c roco -n "__fish_seen_subcommand_from export" -l exclude-dependencies -d 'only record top-level packages (no other installed package depends on)'
+complete -c roco -n "__fish_seen_subcommand_from export" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from export" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from diff" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from diff" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from diff" -l json -d 'print differences as JSON'
+complete -c roco -n "__fish_seen_subcommand_from diff" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from diff" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from drift" -s b -l baseline -d 'JSON baseline manifest' -r
+complete -c roco -n "__fish_seen_subcommand_from drift" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from drift" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from drift" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from drift" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from help" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from help" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from help" -f -a "list" -d 'list choco features'
+complete -c roco -n "__fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from feature; and __fish_seen_subcommand_from list" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from feature; and __fish_seen_subcommand_from list" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from feature; and __fish_seen_subcommand_from list" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from feature; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from feature; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from help" -f -a "list" -d 'list choco features'
+complete -c roco -n "__fish_seen_subcommand_from feature; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from help" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from help" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from help" -f -a "list" -d 'list choco config settings'
+complete -c roco -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from help" -f -a "get" -d 'get the value of a choco config setting'
+complete -c roco -n "__fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from list" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from list" -s r -l limitoutput -d 'limit the output to essential information'
+complete -c roco -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from list" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from list" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from get" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
+complete -c roco -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from get" -s v -l verbose -d 'be verbose'
+complete -c roco -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from get" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from help" -f -a "list" -d 'list choco config settings'
+complete -c roco -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from help" -f -a "get" -d 'get the value of a choco config setting'
+complete -c roco -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from license" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
 complete -c roco -n "__fish_seen_subcommand_from license" -s f -l full -d 'display full license information'
 complete -c roco -n "__fish_seen_subcommand_from license" -s h -l help -d 'Print help'
+complete -c roco -n "__fish_seen_subcommand_from upgrade" -l choco-root -d 'Chocolatey installation to inspect (default: env:ChocolateyInstall)' -r
 complete -c roco -n "__fish_seen_subcommand_from upgrade" -s p -l pre -d 'include prerelease versions'
+complete -c roco -n "__fish_seen_subcommand_from upgrade" -l semver2 -d 'compare prerelease versions using SemVer 2.0 rules 
+(default: same ordering as choco)'
 complete -c roco -n "__fish_seen_subcommand_from upgrade" -s r -l limitoutput -d 'limit the output to essential information'
 complete -c roco -n "__fish_seen_subcommand_from upgrade" -s v -l verbose -d 'be verbose'
 complete -c roco -n "__fish_seen_subcommand_from upgrade" -l sslcheck -d 'require https/ssl-validation'
 complete -c roco -n "__fish_seen_subcommand_from upgrade" -s h -l help -d 'Print help'
-complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "list" -d 'list local installed packages'
-complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "bad" -d 'list packages in lib-bad/'
-complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "outdated" -d 'Returns a list of outdated packages.'
-complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "source" -d 'list choco sources'
-complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "license" -d 'display license information'
-complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "upgrade" -d 'upgrade outdated choco packages (using choco.exe)'
-complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "list" -d 'list local installed packages'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "info" -d 'show the nuspec metadata of a local installed package, or of a package on a source'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "verify" -d 'verify installed files against the checksums recorded by choco (.chocolatey/<id>.<version>/.files)'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "doctor" -d 'scan the installation for broken lib folders, duplicates and interrupted installs'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "bad" -d 'list packages in lib-bad/'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "outdated" -d 'Returns a list of outdated packages.'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "search" -d 'search packages on all enabled sources'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "versions" -d 'list all available versions of a package on each enabled source'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "source" -d 'list and manage choco sources'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "deps" -d 'analyze dependencies of local installed packages'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "pin" -d 'list, add or remove package pins (pinned packages are excluded from upgrades)'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "export" -d 'export local installed packages (e.g. to packages.config, for `choco install packages.config`)'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "diff" -d 'compare two package sets (added, removed, upgraded, downgraded and pin changed packages)'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "drift" -d 'check the local installation against a baseline manifest (required/absent packages, versions, pins)'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "feature" -d 'list choco features'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "config" -d 'retrieve choco config settings'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "license" -d 'display license information'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "upgrade" -d 'upgrade outdated choco packages (using choco.exe)'
+complete -c roco -n "__fish_seen_subcommand_from help; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from info; and not __fish_seen_subcommand_from verify; and not __fish_seen_subcommand_from doctor; and not __fish_seen_subcommand_from bad; and not __fish_seen_subcommand_from outdated; and not __fish_seen_subcommand_from search; and not __fish_seen_subcommand_from versions; and not __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from export; and not __fish_seen_subcommand_from diff; and not __fish_seen_subcommand_from drift; and not __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from license; and not __fish_seen_subcommand_from upgrade; and not __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority" -f -a "list" -d 'list choco sources (default)'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority" -f -a "add" -d 'add a source, or update an existing one'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority" -f -a "remove" -d 'remove a source'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority" -f -a "enable" -d 'enable a source'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority" -f -a "disable" -d 'disable a source'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from source; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove; and not __fish_seen_subcommand_from enable; and not __fish_seen_subcommand_from disable; and not __fish_seen_subcommand_from priority" -f -a "priority" -d 'change the priority of a source'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph" -f -a "check" -d 'verify the dependency constraints of all local packages are satisfied'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph" -f -a "orphans" -d 'list dependency-only packages (.install, .portable, .extension) no local package depends on'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from deps; and not __fish_seen_subcommand_from check; and not __fish_seen_subcommand_from orphans; and not __fish_seen_subcommand_from graph" -f -a "graph" -d 'export the dependency graph of all local packages'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove" -f -a "list" -d 'list pinned packages'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove" -f -a "add" -d 'pin a local installed package'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from pin; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from add; and not __fish_seen_subcommand_from remove" -f -a "remove" -d 'remove the pin of a local installed package'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from feature; and not __fish_seen_subcommand_from list" -f -a "list" -d 'list choco features'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get" -f -a "list" -d 'list choco config settings'
+complete -c roco -n "__fish_seen_subcommand_from help; and __fish_seen_subcommand_from config; and not __fish_seen_subcommand_from list; and not __fish_seen_subcommand_from get" -f -a "get" -d 'get the value of a choco config setting'
@@ -21,18 +21,105 @@ pub fn build_cli() -> Command {
         .long("sslcheck")
         .action(ArgAction::SetTrue)
         .help("require https/ssl-validation");
+    let common_arg_semver2 = Arg::new("semver2")
+        .long("semver2")
+        .action(ArgAction::SetTrue)
+        .help("compare prerelease versions using SemVer 2.0 rules \n(default: same ordering as choco)");
+    let common_arg_source_name = Arg::new("name")
+        .short('n')
+        .long("name")
+        .required(true)
+        .help("name of the source");
+    let common_arg_pin_name = Arg::new("name")
+        .short('n')
+        .long("name")
+        .required(true)
+        .help("id of the package");
+    let common_arg_pin_version = Arg::new("version")
+        .long("version")
+        .help("version of the package (default: the installed version)");
 
     Command::new("Rocolatey")
     .version("0.9.3")
     .author("Manfred Wallner <schusterfredl@mwallner.net>")
     .about("provides a basic interface for rocolatey-lib")
+    .arg(
+      Arg::new("choco-root")
+        .long("choco-root")
+        .global(true)
+        .value_name("DIR")
+        .help("Chocolatey installation to inspect (default: env:ChocolateyInstall)"),
+    )
     .subcommand(
       Command::new("list")
         .about("list local installed packages")
         .arg(Arg::new("filter").default_value("all"))
         .arg(&common_arg_limitoutput)
         .arg(&common_arg_verbose)
-        .arg(Arg::new("deptree").long("dependency-tree").action(ArgAction::SetTrue).help("list dependencies")),
+        .arg(Arg::new("deptree").long("dependency-tree").action(ArgAction::SetTrue).help("list dependencies"))
+        .arg(
+          Arg::new("dependents")
+            .long("dependents")
+            .value_name("ID")
+            .conflicts_with("deptree")
+            .help("list packages depending on the given package"),
+        )
+        .arg(
+          Arg::new("transitive")
+            .long("transitive")
+            .action(ArgAction::SetTrue)
+            .requires("dependents")
+            .help("include indirect dependents"),
+        )
+        .arg(
+          Arg::new("details")
+            .long("details")
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all(["deptree", "dependents"])
+            .help("include disk usage, install time and pin status"),
+        )
+        .arg(
+          Arg::new("json")
+            .long("json")
+            .action(ArgAction::SetTrue)
+            .requires("details")
+            .help("print details as JSON"),
+        ),
+    )
+    .subcommand(
+      Command::new("info")
+        .about("show the nuspec metadata of a local installed package, or of a package on a source")
+        .arg(Arg::new("pkg").required(true).help("id of the package"))
+        .arg(
+          Arg::new("source")
+            .short('s')
+            .long("source")
+            .help("name, url or path of the source to fetch the metadata from"),
+        )
+        .arg(
+          Arg::new("version")
+            .long("version")
+            .requires("source")
+            .help("version of the package on the source (default: the latest one)"),
+        )
+        .arg(common_arg_prerelease.clone().requires("source"))
+        .arg(&common_arg_limitoutput)
+        .arg(&common_arg_verbose)
+        .arg(&common_arg_enable_cert_validation)
+        .arg(&common_arg_semver2),
+    )
+    .subcommand(
+      Command::new("verify")
+        .about("verify installed files against the checksums recorded by choco (.chocolatey/<id>.<version>/.files)")
+        .arg(Arg::new("pkg").help("id of the package to verify (default: all local packages)"))
+        .arg(&common_arg_limitoutput)
+        .arg(&common_arg_verbose),
+    )
+    .subcommand(
+      Command::new("doctor")
+        .about("scan the installation for broken lib folders, duplicates and interrupted installs")
+        .arg(&common_arg_limitoutput)
+        .arg(&common_arg_verbose),
     )
     .subcommand(
       Command::new("bad")
@@ -72,16 +159,277 @@ pub fn build_cli() -> Command {
             .help("output a whitespace-separated list of results"),
         )
         .arg(&common_arg_prerelease)
+        .arg(&common_arg_semver2)
+        .arg(&common_arg_limitoutput)
+        .arg(&common_arg_verbose)
+        .arg(&common_arg_enable_cert_validation),
+    )
+    .subcommand(
+      Command::new("search")
+        .about("search packages on all enabled sources")
+        .arg(Arg::new("term").default_value("").help("search term (default: all packages)"))
+        .arg(
+          Arg::new("exact")
+            .short('e')
+            .long("exact")
+            .action(ArgAction::SetTrue)
+            .help("only the package with exactly this id"),
+        )
+        .arg(
+          Arg::new("all-versions")
+            .short('a')
+            .long("all-versions")
+            .action(ArgAction::SetTrue)
+            .help("list all versions instead of the latest one"),
+        )
+        .arg(
+          Arg::new("page")
+            .long("page")
+            .value_parser(clap::value_parser!(u32))
+            .default_value("0")
+            .help("page of results to show (0-based)"),
+        )
+        .arg(
+          Arg::new("page-size")
+            .long("page-size")
+            .value_parser(clap::value_parser!(u32).range(1..))
+            .default_value("30")
+            .help("number of results per page"),
+        )
+        .arg(&common_arg_prerelease)
+        .arg(&common_arg_semver2)
+        .arg(&common_arg_limitoutput)
+        .arg(&common_arg_verbose)
+        .arg(&common_arg_enable_cert_validation),
+    )
+    .subcommand(
+      Command::new("versions")
+        .about("list all available versions of a package on each enabled source")
+        .arg(Arg::new("pkg").required(true).help("id of the package"))
+        .arg(&common_arg_prerelease)
+        .arg(&common_arg_semver2)
         .arg(&common_arg_limitoutput)
         .arg(&common_arg_verbose)
         .arg(&common_arg_enable_cert_validation),
     )
     .subcommand(
       Command::new("source")
-        .about("list choco sources")
+        .about("list and manage choco sources")
+        .arg(&common_arg_limitoutput)
+        .arg(&common_arg_verbose)
+        .subcommand(
+          Command::new("list")
+            .about("list choco sources (default)")
+            .arg(&common_arg_limitoutput)
+            .arg(&common_arg_verbose),
+        )
+        .subcommand(
+          Command::new("add")
+            .about("add a source, or update an existing one")
+            .arg(&common_arg_source_name)
+            .arg(
+              Arg::new("source")
+                .short('s')
+                .long("source")
+                .required(true)
+                .help("url or path of the source"),
+            )
+            .arg(Arg::new("user").short('u').long("user").help("user name for authenticated sources"))
+            .arg(Arg::new("password").short('p').long("password").help("password for authenticated sources (stored encrypted)"))
+            .arg(Arg::new("cert").long("cert").help("path to a client certificate"))
+            .arg(Arg::new("certpassword").long("certpassword").help("password for the client certificate (stored encrypted)"))
+            .arg(
+              Arg::new("priority")
+                .long("priority")
+                .default_value("0")
+                .value_parser(clap::value_parser!(i64))
+                .help("source priority, lower values take precedence (0 = no priority)"),
+            )
+            .arg(Arg::new("bypassproxy").long("bypassproxy").action(ArgAction::SetTrue).help("do not use the configured proxy for this source"))
+            .arg(Arg::new("allowselfservice").long("allowselfservice").action(ArgAction::SetTrue).help("allow self-service for this source"))
+            .arg(Arg::new("adminonly").long("adminonly").action(ArgAction::SetTrue).help("make this source visible to administrators only"))
+            .arg(&common_arg_verbose),
+        )
+        .subcommand(
+          Command::new("remove")
+            .about("remove a source")
+            .arg(&common_arg_source_name)
+            .arg(&common_arg_verbose),
+        )
+        .subcommand(
+          Command::new("enable")
+            .about("enable a source")
+            .arg(&common_arg_source_name)
+            .arg(&common_arg_verbose),
+        )
+        .subcommand(
+          Command::new("disable")
+            .about("disable a source")
+            .arg(&common_arg_source_name)
+            .arg(&common_arg_verbose),
+        )
+        .subcommand(
+          Command::new("priority")
+            .about("change the priority of a source")
+            .arg(&common_arg_source_name)
+            .arg(
+              Arg::new("value")
+                .required(true)
+                .value_parser(clap::value_parser!(i64))
+                .help("new priority (0 = no priority)"),
+            )
+            .arg(&common_arg_verbose),
+        ),
+    )
+    .subcommand(
+      Command::new("deps")
+        .about("analyze dependencies of local installed packages")
+        .subcommand_required(true)
+        .subcommand(
+          Command::new("check")
+            .about("verify the dependency constraints of all local packages are satisfied")
+            .arg(&common_arg_limitoutput)
+            .arg(&common_arg_verbose),
+        )
+        .subcommand(
+          Command::new("orphans")
+            .about("list dependency-only packages (.install, .portable, .extension) no local package depends on")
+            .arg(&common_arg_limitoutput)
+            .arg(&common_arg_verbose),
+        )
+        .subcommand(
+          Command::new("graph")
+            .about("export the dependency graph of all local packages")
+            .arg(
+              Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_parser(["dot", "mermaid", "json"])
+                .default_value("dot")
+                .help("output format"),
+            )
+            .arg(&common_arg_verbose),
+        ),
+    )
+    .subcommand(
+      Command::new("pin")
+        .about("list, add or remove package pins (pinned packages are excluded from upgrades)")
+        .subcommand_required(true)
+        .subcommand(
+          Command::new("list")
+            .about("list pinned packages")
+            .arg(&common_arg_limitoutput)
+            .arg(&common_arg_verbose),
+        )
+        .subcommand(
+          Command::new("add")
+            .about("pin a local installed package")
+            .arg(&common_arg_pin_name)
+            .arg(&common_arg_pin_version)
+            .arg(&common_arg_verbose),
+        )
+        .subcommand(
+          Command::new("remove")
+            .about("remove the pin of a local installed package")
+            .arg(&common_arg_pin_name)
+            .arg(&common_arg_pin_version)
+            .arg(&common_arg_verbose),
+        ),
+    )
+    .subcommand(
+      Command::new("export")
+        .about("export local installed packages (e.g. to packages.config, for `choco install packages.config`)")
+        .arg(
+          Arg::new("format")
+            .short('f')
+            .long("format")
+            .value_parser(["config", "json", "toml"])
+            .default_value("config")
+            .help("output format"),
+        )
+        .arg(
+          Arg::new("include-versions")
+            .long("include-versions")
+            .action(ArgAction::SetTrue)
+            .help("record the installed versions \n(default: latest versions are installed on import)"),
+        )
+        .arg(
+          Arg::new("exclude-dependencies")
+            .long("exclude-dependencies")
+            .action(ArgAction::SetTrue)
+            .help("only record top-level packages (no other installed package depends on)"),
+        )
+        .arg(Arg::new("source").short('s').long("source").value_name("SOURCE").help("source to install the packages from"))
+        .arg(
+          Arg::new("output")
+            .short('o')
+            .long("output")
+            .value_name("FILE")
+            .help("file to write to (default: stdout)"),
+        )
+        .arg(&common_arg_verbose),
+    )
+    .subcommand(
+      Command::new("diff")
+        .about("compare two package sets (added, removed, upgraded, downgraded and pin changed packages)")
+        .arg(
+          Arg::new("a")
+            .required(true)
+            .help("'local', a Chocolatey root directory or a snapshot file (packages.config, JSON, TOML)"),
+        )
+        .arg(Arg::new("b").required(true).help("package set to compare with (same as <a>)"))
+        .arg(&common_arg_limitoutput)
+        .arg(
+          Arg::new("json")
+            .long("json")
+            .action(ArgAction::SetTrue)
+            .conflicts_with("limitoutput")
+            .help("print differences as JSON"),
+        )
+        .arg(&common_arg_verbose),
+    )
+    .subcommand(
+      Command::new("drift")
+        .about("check the local installation against a baseline manifest (required/absent packages, versions, pins)")
+        .arg(
+          Arg::new("baseline")
+            .short('b')
+            .long("baseline")
+            .value_name("FILE")
+            .required(true)
+            .help("JSON baseline manifest"),
+        )
         .arg(&common_arg_limitoutput)
         .arg(&common_arg_verbose),
     )
+    .subcommand(
+      Command::new("feature")
+        .about("list choco features")
+        .subcommand_required(true)
+        .subcommand(
+          Command::new("list")
+            .about("list choco features")
+            .arg(&common_arg_limitoutput)
+            .arg(&common_arg_verbose),
+        ),
+    )
+    .subcommand(
+      Command::new("config")
+        .about("retrieve choco config settings")
+        .subcommand_required(true)
+        .subcommand(
+          Command::new("list")
+            .about("list choco config settings")
+            .arg(&common_arg_limitoutput)
+            .arg(&common_arg_verbose),
+        )
+        .subcommand(
+          Command::new("get")
+            .about("get the value of a choco config setting")
+            .arg(Arg::new("name").required(true).help("name of the config setting"))
+            .arg(&common_arg_verbose),
+        ),
+    )
     .subcommand(
       Command::new("license").about("display license information").arg(
         Arg::new("full")
@@ -98,6 +446,7 @@ pub fn build_cli() -> Command {
           .default_value("all")
         )
         .arg(&common_arg_prerelease)
+        .arg(&common_arg_semver2)
         .arg(&common_arg_limitoutput)
         .arg(&common_arg_verbose)
         .arg(&common_arg_enable_cert_validation),
@@ -132,6 +481,26 @@ mod tests {
         assert!(sub_matches.contains_id("verbose"));
     }
 
+    #[test]
+    fn test_list_dependents() {
+        let matches = build_cli()
+            .try_get_matches_from(vec![
+                "rocolatey",
+                "list",
+                "--dependents",
+                "jre8",
+                "--transitive",
+            ])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("list").unwrap();
+        assert_eq!(sub_matches.get_one::<String>("dependents").unwrap(), "jre8");
+        assert!(sub_matches.get_flag("transitive"));
+
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "list", "--transitive"])
+            .is_err());
+    }
+
     #[test]
     fn test_source_command() {
         let matches = build_cli()
@@ -144,6 +513,339 @@ mod tests {
         assert!(sub_matches.contains_id("verbose"));
     }
 
+    #[test]
+    fn test_source_add_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec![
+                "rocolatey",
+                "source",
+                "add",
+                "-n",
+                "internal",
+                "-s",
+                "https://repo/nuget",
+                "--priority",
+                "10",
+                "--bypassproxy",
+            ])
+            .unwrap();
+
+        let (name, sub_matches) = matches
+            .subcommand_matches("source")
+            .unwrap()
+            .subcommand()
+            .unwrap();
+        assert_eq!(name, "add");
+        assert_eq!(sub_matches.get_one::<String>("name").unwrap(), "internal");
+        assert_eq!(*sub_matches.get_one::<i64>("priority").unwrap(), 10);
+        assert!(sub_matches.get_flag("bypassproxy"));
+        assert!(!sub_matches.get_flag("adminonly"));
+
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "source", "remove"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_feature_config_commands() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "feature", "list", "-r"])
+            .unwrap();
+        let sub_matches = matches
+            .subcommand_matches("feature")
+            .unwrap()
+            .subcommand_matches("list")
+            .unwrap();
+        assert!(sub_matches.get_flag("limitoutput"));
+
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "config", "get", "proxy"])
+            .unwrap();
+        let sub_matches = matches
+            .subcommand_matches("config")
+            .unwrap()
+            .subcommand_matches("get")
+            .unwrap();
+        assert_eq!(sub_matches.get_one::<String>("name").unwrap(), "proxy");
+
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "config"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_choco_root_arg() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "list", "--choco-root", "d:\\choco"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<String>("choco-root").unwrap(),
+            "d:\\choco"
+        );
+    }
+
+    #[test]
+    fn test_deps_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "deps", "check", "-r"])
+            .unwrap();
+        let sub_matches = matches
+            .subcommand_matches("deps")
+            .unwrap()
+            .subcommand_matches("check")
+            .unwrap();
+        assert!(sub_matches.get_flag("limitoutput"));
+    }
+
+    #[test]
+    fn test_deps_graph_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "deps", "graph", "--format", "mermaid"])
+            .unwrap();
+        let sub_matches = matches
+            .subcommand_matches("deps")
+            .unwrap()
+            .subcommand_matches("graph")
+            .unwrap();
+        assert_eq!(sub_matches.get_one::<String>("format").unwrap(), "mermaid");
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "deps", "graph", "-f", "svg"])
+            .is_err());
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "deps", "orphans", "-r"])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_info_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "info", "vlc", "-r"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("info").unwrap();
+        assert_eq!(sub_matches.get_one::<String>("pkg").unwrap(), "vlc");
+        assert!(sub_matches.get_flag("limitoutput"));
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "info"])
+            .is_err());
+
+        let matches = build_cli()
+            .try_get_matches_from(vec![
+                "rocolatey",
+                "info",
+                "git",
+                "-s",
+                "chocolatey",
+                "--version",
+                "2.40.0",
+                "--pre",
+            ])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("info").unwrap();
+        assert_eq!(
+            sub_matches.get_one::<String>("source").unwrap(),
+            "chocolatey"
+        );
+        assert_eq!(sub_matches.get_one::<String>("version").unwrap(), "2.40.0");
+        assert!(sub_matches.get_flag("prerelease"));
+        // --version and --pre only apply to a source
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "info", "git", "--version", "2.40.0"])
+            .is_err());
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "info", "git", "--pre"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "verify"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("verify").unwrap();
+        assert_eq!(sub_matches.get_one::<String>("pkg"), None);
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "verify", "vlc", "-r"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("verify").unwrap();
+        assert_eq!(sub_matches.get_one::<String>("pkg").unwrap(), "vlc");
+    }
+
+    #[test]
+    fn test_list_details() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "list", "--details", "--json"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("list").unwrap();
+        assert!(sub_matches.get_flag("details"));
+        assert!(sub_matches.get_flag("json"));
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "list", "--json"])
+            .is_err());
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "list", "--details", "--dependency-tree"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_doctor_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "doctor", "-r"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("doctor").unwrap();
+        assert!(sub_matches.get_flag("limitoutput"));
+    }
+
+    #[test]
+    fn test_pin_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec![
+                "rocolatey",
+                "pin",
+                "add",
+                "-n",
+                "vlc",
+                "--version",
+                "3.0.18",
+            ])
+            .unwrap();
+        let sub_matches = matches
+            .subcommand_matches("pin")
+            .unwrap()
+            .subcommand_matches("add")
+            .unwrap();
+        assert_eq!(sub_matches.get_one::<String>("name").unwrap(), "vlc");
+        assert_eq!(sub_matches.get_one::<String>("version").unwrap(), "3.0.18");
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "pin", "remove"])
+            .is_err());
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "pin", "list", "-r"])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_export_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec![
+                "rocolatey",
+                "export",
+                "-f",
+                "toml",
+                "--include-versions",
+                "--exclude-dependencies",
+                "-o",
+                "packages.toml",
+            ])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("export").unwrap();
+        assert_eq!(sub_matches.get_one::<String>("format").unwrap(), "toml");
+        assert!(sub_matches.get_flag("include-versions"));
+        assert!(sub_matches.get_flag("exclude-dependencies"));
+        assert_eq!(
+            sub_matches.get_one::<String>("output").unwrap(),
+            "packages.toml"
+        );
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "export"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("export").unwrap();
+        assert_eq!(sub_matches.get_one::<String>("format").unwrap(), "config");
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "export", "-f", "yaml"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_diff_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "diff", "local", "packages.config", "-r"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("diff").unwrap();
+        assert_eq!(sub_matches.get_one::<String>("a").unwrap(), "local");
+        assert_eq!(
+            sub_matches.get_one::<String>("b").unwrap(),
+            "packages.config"
+        );
+        assert!(sub_matches.get_flag("limitoutput"));
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "diff", "local"])
+            .is_err());
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "diff", "a", "b", "-r", "--json"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_drift_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec![
+                "rocolatey",
+                "drift",
+                "--baseline",
+                "baseline.json",
+                "-r",
+            ])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("drift").unwrap();
+        assert_eq!(
+            sub_matches.get_one::<String>("baseline").unwrap(),
+            "baseline.json"
+        );
+        assert!(sub_matches.get_flag("limitoutput"));
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "drift"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_search_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec![
+                "rocolatey",
+                "search",
+                "git",
+                "--exact",
+                "--pre",
+                "--all-versions",
+                "--page",
+                "2",
+                "--page-size",
+                "10",
+                "-r",
+            ])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("search").unwrap();
+        assert_eq!(sub_matches.get_one::<String>("term").unwrap(), "git");
+        assert!(sub_matches.get_flag("exact"));
+        assert!(sub_matches.get_flag("prerelease"));
+        assert!(sub_matches.get_flag("all-versions"));
+        assert_eq!(*sub_matches.get_one::<u32>("page").unwrap(), 2);
+        assert_eq!(*sub_matches.get_one::<u32>("page-size").unwrap(), 10);
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "search"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("search").unwrap();
+        assert_eq!(sub_matches.get_one::<String>("term").unwrap(), "");
+        assert_eq!(*sub_matches.get_one::<u32>("page-size").unwrap(), 30);
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "search", "git", "--page-size", "0"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_versions_command() {
+        let matches = build_cli()
+            .try_get_matches_from(vec!["rocolatey", "versions", "git", "--pre", "-r"])
+            .unwrap();
+        let sub_matches = matches.subcommand_matches("versions").unwrap();
+        assert_eq!(sub_matches.get_one::<String>("pkg").unwrap(), "git");
+        assert!(sub_matches.get_flag("prerelease"));
+        assert!(sub_matches.get_flag("limitoutput"));
+        assert!(build_cli()
+            .try_get_matches_from(vec!["rocolatey", "versions"])
+            .is_err());
+    }
+
     #[test]
     fn test_license_command() {
         let matches = build_cli()
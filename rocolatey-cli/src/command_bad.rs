@@ -1,7 +1,9 @@
 use rocolatey_lib::roco::local::get_local_bad_packages_text;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
 
-pub fn bad(matches: &clap::ArgMatches) {
+pub fn bad(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
     rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
     let r = matches.get_flag("limitoutput");
-    print!("{}", get_local_bad_packages_text(r));
+    print!("{}", get_local_bad_packages_text(home, r)?);
+    Ok(())
 }
@@ -0,0 +1,19 @@
+use rocolatey_lib::roco::local::{get_config_text, get_config_value_text};
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub fn config(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    match matches.subcommand() {
+        Some(("list", sub_matches)) => {
+            rocolatey_lib::set_verbose_mode(sub_matches.get_flag("verbose"));
+            let r = sub_matches.get_flag("limitoutput");
+            print!("{}", get_config_text(home, r)?);
+        }
+        Some(("get", sub_matches)) => {
+            rocolatey_lib::set_verbose_mode(sub_matches.get_flag("verbose"));
+            let name = sub_matches.get_one::<String>("name").unwrap();
+            println!("{}", get_config_value_text(home, name)?);
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
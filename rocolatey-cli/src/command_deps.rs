@@ -0,0 +1,34 @@
+use rocolatey_lib::roco::deps::get_dependency_check_text;
+use rocolatey_lib::roco::graph::{get_dependency_graph_text, get_orphans_text, GraphFormat};
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub fn deps(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    match matches.subcommand() {
+        Some(("check", sub_matches)) => {
+            rocolatey_lib::set_verbose_mode(sub_matches.get_flag("verbose"));
+            let r = sub_matches.get_flag("limitoutput");
+            let (problems, text) = get_dependency_check_text(home, r)?;
+            print!("{}", text);
+            if problems > 0 {
+                return Err(RocoError::DependencyProblems(problems));
+            }
+        }
+        Some(("orphans", sub_matches)) => {
+            rocolatey_lib::set_verbose_mode(sub_matches.get_flag("verbose"));
+            let r = sub_matches.get_flag("limitoutput");
+            print!("{}", get_orphans_text(home, r)?);
+        }
+        Some(("graph", sub_matches)) => {
+            rocolatey_lib::set_verbose_mode(sub_matches.get_flag("verbose"));
+            // value is restricted by clap
+            let format: GraphFormat = sub_matches
+                .get_one::<String>("format")
+                .unwrap()
+                .parse()
+                .unwrap();
+            print!("{}", get_dependency_graph_text(home, format)?);
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use rocolatey_lib::roco::diff::{get_diff_json, get_diff_text};
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub fn diff(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
+    let a = matches.get_one::<String>("a").unwrap();
+    let b = matches.get_one::<String>("b").unwrap();
+    let (differences, text) = match matches.get_flag("json") {
+        true => get_diff_json(home, a, b)?,
+        false => get_diff_text(home, a, b, matches.get_flag("limitoutput"))?,
+    };
+    print!("{}", text);
+    if differences > 0 {
+        return Err(RocoError::PackageDifferences(differences));
+    }
+    Ok(())
+}
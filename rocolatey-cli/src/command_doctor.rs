@@ -0,0 +1,13 @@
+use rocolatey_lib::roco::local::get_health_text;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub fn doctor(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
+    let r = matches.get_flag("limitoutput");
+    let (errors, text) = get_health_text(home, r)?;
+    print!("{}", text);
+    if errors > 0 {
+        return Err(RocoError::HealthProblems(errors));
+    }
+    Ok(())
+}
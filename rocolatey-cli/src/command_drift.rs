@@ -0,0 +1,14 @@
+use rocolatey_lib::roco::drift::get_drift_text;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub fn drift(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
+    let r = matches.get_flag("limitoutput");
+    let baseline = matches.get_one::<String>("baseline").unwrap();
+    let (violations, text) = get_drift_text(home, baseline, r)?;
+    print!("{}", text);
+    if violations > 0 {
+        return Err(RocoError::BaselineViolations(violations));
+    }
+    Ok(())
+}
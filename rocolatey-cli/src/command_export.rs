@@ -0,0 +1,26 @@
+use rocolatey_lib::roco::export::{get_export_text, ExportFormat, ExportOptions};
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub fn export(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
+    let options = ExportOptions {
+        // value is restricted by clap
+        format: matches
+            .get_one::<String>("format")
+            .unwrap()
+            .parse::<ExportFormat>()
+            .unwrap(),
+        include_versions: matches.get_flag("include-versions"),
+        exclude_dependencies: matches.get_flag("exclude-dependencies"),
+        source: matches.get_one::<String>("source").cloned(),
+    };
+    let text = get_export_text(home, &options)?;
+    match matches.get_one::<String>("output") {
+        Some(path) => {
+            std::fs::write(path, text)?;
+            println!("exported packages to '{}'", path);
+        }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use rocolatey_lib::roco::local::get_features_text;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub fn feature(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    match matches.subcommand() {
+        Some(("list", sub_matches)) => {
+            rocolatey_lib::set_verbose_mode(sub_matches.get_flag("verbose"));
+            let r = sub_matches.get_flag("limitoutput");
+            print!("{}", get_features_text(home, r)?);
+        }
+        _ => unreachable!(),
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use rocolatey_lib::roco::local::get_package_info_text;
+use rocolatey_lib::roco::remote::get_remote_package_info_text;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub async fn info(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    crate::apply_remote_flags(matches);
+    let r = matches.get_flag("limitoutput");
+    let pkg = matches.get_one::<String>("pkg").unwrap();
+    let text = match matches.get_one::<String>("source") {
+        Some(source) => {
+            let version = matches.get_one::<String>("version").map(|v| v.as_str());
+            let prerelease = matches.get_flag("prerelease");
+            get_remote_package_info_text(home, source, pkg, version, prerelease, r).await?
+        }
+        None => get_package_info_text(home, pkg, r)?,
+    };
+    print!("{}", text);
+    Ok(())
+}
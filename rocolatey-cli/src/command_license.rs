@@ -74,7 +74,7 @@ pub fn license(matches: &clap::ArgMatches) {
             let normalized_license = normalize_license(&library.license);
             license_map
                 .entry(normalized_license)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(library.package_name.clone());
         }
 
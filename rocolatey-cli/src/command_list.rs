@@ -1,13 +1,24 @@
+use rocolatey_lib::roco::details::{get_package_details_json, get_package_details_text};
 use rocolatey_lib::roco::local::get_dependency_tree_text;
+use rocolatey_lib::roco::local::get_dependents_text;
 use rocolatey_lib::roco::local::get_local_packages_text;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
 
-pub fn list(matches: &clap::ArgMatches) {
+pub fn list(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
     rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
     let r = matches.get_flag("limitoutput");
     let filter = matches.get_one::<String>("filter").unwrap();
-    if matches.get_flag("deptree") {
-        print!("{}", get_dependency_tree_text(filter));
+    if let Some(id) = matches.get_one::<String>("dependents") {
+        let transitive = matches.get_flag("transitive");
+        print!("{}", get_dependents_text(home, id, transitive, r)?);
+    } else if matches.get_flag("json") {
+        print!("{}", get_package_details_json(home, filter)?);
+    } else if matches.get_flag("details") {
+        print!("{}", get_package_details_text(home, filter, r)?);
+    } else if matches.get_flag("deptree") {
+        print!("{}", get_dependency_tree_text(home, filter)?);
     } else {
-        print!("{}", get_local_packages_text(filter, r));
+        print!("{}", get_local_packages_text(home, filter, r)?);
     }
+    Ok(())
 }
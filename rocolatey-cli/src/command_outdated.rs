@@ -1,8 +1,8 @@
 use rocolatey_lib::roco::remote::get_outdated_packages_text;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
 
-pub async fn outdated(matches: &clap::ArgMatches) {
-    rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
-    rocolatey_lib::set_ssl_enabled(matches.get_flag("ssl-validation-enabled"));
+pub async fn outdated(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    crate::apply_remote_flags(matches);
     let r = matches.get_flag("limitoutput");
     let l: bool = matches.get_flag("listoutput");
     let pre = matches.get_flag("prerelease");
@@ -12,6 +12,7 @@ pub async fn outdated(matches: &clap::ArgMatches) {
     let pkg = matches.get_one::<String>("pkg").unwrap();
     print!(
         "{}",
-        get_outdated_packages_text(pkg, r, l, pre, ignore_pinned, ignore_unfound).await
+        get_outdated_packages_text(home, pkg, r, l, pre, ignore_pinned, ignore_unfound).await?
     );
+    Ok(())
 }
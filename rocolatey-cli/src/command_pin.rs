@@ -0,0 +1,20 @@
+use rocolatey_lib::roco::pin::{edit_pin_text, get_pins_text, PinEdit};
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub fn pin(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    let (cmd, sub_matches) = matches.subcommand().unwrap();
+    rocolatey_lib::set_verbose_mode(sub_matches.get_flag("verbose"));
+    let edit = match cmd {
+        "list" => {
+            print!("{}", get_pins_text(home)?);
+            return Ok(());
+        }
+        "add" => PinEdit::Add,
+        "remove" => PinEdit::Remove,
+        _ => unreachable!(),
+    };
+    let name = sub_matches.get_one::<String>("name").unwrap();
+    let version = sub_matches.get_one::<String>("version").map(|v| v.as_str());
+    println!("{}", edit_pin_text(home, name, version, edit)?);
+    Ok(())
+}
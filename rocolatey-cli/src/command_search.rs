@@ -0,0 +1,22 @@
+use rocolatey_lib::roco::search::{search_packages_text, SearchOptions};
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub async fn search(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    crate::apply_remote_flags(matches);
+    let r = matches.get_flag("limitoutput");
+    let mut options = SearchOptions::new(matches.get_one::<String>("term").unwrap());
+    options.exact = matches.get_flag("exact");
+    options.prerelease = matches.get_flag("prerelease");
+    options.all_versions = matches.get_flag("all-versions");
+    // values are restricted by clap
+    options.page = *matches.get_one::<u32>("page").unwrap() as usize;
+    options.page_size = *matches.get_one::<u32>("page-size").unwrap() as usize;
+    let (text, errors) = search_packages_text(home, &options, r).await?;
+    if !r {
+        for e in errors {
+            eprintln!("failed to search packages: {}", e);
+        }
+    }
+    print!("{}", text);
+    Ok(())
+}
@@ -1,7 +1,57 @@
+use rocolatey_lib::roco::config::SourceConfig;
+use rocolatey_lib::roco::config_writer::{edit_choco_source_text, SourceEdit};
+use rocolatey_lib::roco::encrypt_choco_config_string;
 use rocolatey_lib::roco::local::get_sources_text;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
 
-pub fn source(matches: &clap::ArgMatches) {
+pub fn source(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    let edit = match matches.subcommand() {
+        None => return list(home, matches),
+        Some(("list", sub_matches)) => return list(home, sub_matches),
+        Some((cmd, sub_matches)) => {
+            rocolatey_lib::set_verbose_mode(sub_matches.get_flag("verbose"));
+            let name = sub_matches.get_one::<String>("name").unwrap().to_string();
+            match cmd {
+                "add" => SourceEdit::Add(source_from_args(name, sub_matches)?),
+                "remove" => SourceEdit::Remove(name),
+                "enable" => SourceEdit::Enable(name),
+                "disable" => SourceEdit::Disable(name),
+                "priority" => {
+                    SourceEdit::Priority(name, *sub_matches.get_one::<i64>("value").unwrap())
+                }
+                _ => unreachable!(),
+            }
+        }
+    };
+    println!("{}", edit_choco_source_text(home, &edit)?);
+    Ok(())
+}
+
+fn list(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
     rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
     let r = matches.get_flag("limitoutput");
-    print!("{}", get_sources_text(r));
+    print!("{}", get_sources_text(home, r)?);
+    Ok(())
+}
+
+fn source_from_args(id: String, matches: &clap::ArgMatches) -> Result<SourceConfig, RocoError> {
+    let encrypted = |arg: &str| -> Result<Option<String>, RocoError> {
+        match matches.get_one::<String>(arg) {
+            Some(v) if !v.is_empty() => Ok(Some(encrypt_choco_config_string(v)?)),
+            _ => Ok(None),
+        }
+    };
+    Ok(SourceConfig {
+        id,
+        value: matches.get_one::<String>("source").unwrap().to_string(),
+        disabled: false,
+        user: matches.get_one::<String>("user").cloned(),
+        password: encrypted("password")?,
+        certificate: matches.get_one::<String>("cert").cloned(),
+        certificate_password: encrypted("certpassword")?,
+        bypass_proxy: matches.get_flag("bypassproxy"),
+        self_service: matches.get_flag("allowselfservice"),
+        admin_only: matches.get_flag("adminonly"),
+        priority: *matches.get_one::<i64>("priority").unwrap(),
+    })
 }
@@ -1,14 +1,14 @@
 use rocolatey_lib::roco::remote::get_outdated_packages;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
 use std::process::{Command, Stdio};
 
-pub async fn upgrade(matches: &clap::ArgMatches) {
-    rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
-    rocolatey_lib::set_ssl_enabled(matches.get_flag("ssl-validation-enabled"));
+pub async fn upgrade(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    crate::apply_remote_flags(matches);
     let r = matches.get_flag("limitoutput");
     let pre = matches.get_flag("prerelease");
     let pkg = matches.get_one::<String>("pkg").unwrap();
 
-    let (_, outdated_packages) = get_outdated_packages(pkg, r, pre, true, true).await;
+    let (_, outdated_packages) = get_outdated_packages(home, pkg, r, pre, true, true).await?;
 
     let package_names: Vec<&str> = outdated_packages
         .iter()
@@ -17,7 +17,7 @@ pub async fn upgrade(matches: &clap::ArgMatches) {
 
     if package_names.is_empty() {
         println!("No outdated packages found.");
-        return;
+        return Ok(());
     }
 
     let mut choco_args = vec!["upgrade", "--ignore-http-cache", "-y"];
@@ -34,22 +34,24 @@ pub async fn upgrade(matches: &clap::ArgMatches) {
         choco_args.push("-v");
     }
 
+    // run choco against the installation the outdated packages were determined from
+    // (--choco-root may point somewhere else than the machine's env:ChocolateyInstall)
     let status = Command::new("choco")
+        .env("ChocolateyInstall", home.root())
         .args(&choco_args)
         .args(&package_names)
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .status()
-        .expect("Failed to start elevated choco upgrade process")
-        .success();
-
-    if status {
-        println!(
-            "Successfully upgraded packages: {}",
-            package_names.join(", ")
-        );
-    } else {
+        .status()?;
+
+    if !status.success() {
         eprintln!("Failed to upgrade packages: {}", package_names.join(", "));
+        return Err(RocoError::ChocoFailed(status.code()));
     }
+    println!(
+        "Successfully upgraded packages: {}",
+        package_names.join(", ")
+    );
+    Ok(())
 }
@@ -0,0 +1,14 @@
+use rocolatey_lib::roco::package_files::get_verify_text;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub fn verify(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
+    let r = matches.get_flag("limitoutput");
+    let pkg = matches.get_one::<String>("pkg").map(|p| p.as_str());
+    let (problems, text) = get_verify_text(home, pkg, r)?;
+    print!("{}", text);
+    if problems > 0 {
+        return Err(RocoError::IntegrityProblems(problems));
+    }
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use rocolatey_lib::roco::versions::get_package_versions_text;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+pub async fn versions(home: &ChocoHome, matches: &clap::ArgMatches) -> Result<(), RocoError> {
+    crate::apply_remote_flags(matches);
+    let r = matches.get_flag("limitoutput");
+    let pkg = matches.get_one::<String>("pkg").unwrap();
+    let prerelease = matches.get_flag("prerelease");
+    let (text, errors) = get_package_versions_text(home, pkg, prerelease, r).await?;
+    if !r {
+        for e in errors {
+            eprintln!("failed to fetch versions: {}", e);
+        }
+    }
+    print!("{}", text);
+    Ok(())
+}
@@ -2,28 +2,151 @@ extern crate clap;
 mod cli;
 
 mod command_bad;
+mod command_config;
+mod command_deps;
+mod command_diff;
+mod command_doctor;
+mod command_drift;
+mod command_export;
+mod command_feature;
+mod command_info;
 mod command_license;
 mod command_list;
 mod command_outdated;
+mod command_pin;
+mod command_search;
 mod command_source;
 mod command_upgrade;
+mod command_verify;
+mod command_versions;
+
+use rocolatey_lib::roco::version::VersionComparison;
+use rocolatey_lib::roco::{ChocoHome, RocoError};
+
+// 1: generic failure (matches choco), everything above gives a hint on what went wrong
+fn exit_code(e: &RocoError) -> i32 {
+    match e {
+        RocoError::ChocolateyDirMissing(_)
+        | RocoError::ConfigMissing(_)
+        | RocoError::ConfigParse { .. } => 2,
+        RocoError::NuspecParse { .. }
+        | RocoError::PackageInfoParse { .. }
+        | RocoError::SnapshotParse { .. }
+        | RocoError::BaselineParse { .. } => 3,
+        RocoError::PackageNotFound(_)
+        | RocoError::RemotePackageNotFound { .. }
+        | RocoError::SourceNotFound(_)
+        | RocoError::ConfigKeyNotFound(_) => 4,
+        RocoError::FeedUnreachable { .. }
+        | RocoError::FeedAuth { .. }
+        | RocoError::HttpStatus { .. }
+        | RocoError::FeedResponse { .. }
+        | RocoError::UnknownFeedType(_)
+        | RocoError::AllFeedsFailed(_) => 5,
+        RocoError::DependencyProblems(_) => 6,
+        RocoError::IntegrityProblems(_) => 7,
+        RocoError::HealthProblems(_) => 8,
+        RocoError::PackageDifferences(_) => 9,
+        RocoError::BaselineViolations(_) => 10,
+        // pass on choco's exit code
+        RocoError::ChocoFailed(Some(code)) if *code != 0 => *code,
+        _ => 1,
+    }
+}
+
+/// verbose, ssl and version comparison flags of the commands querying sources
+fn apply_remote_flags(matches: &clap::ArgMatches) {
+    rocolatey_lib::set_verbose_mode(matches.get_flag("verbose"));
+    rocolatey_lib::set_ssl_enabled(matches.get_flag("ssl-validation-enabled"));
+    rocolatey_lib::set_version_comparison(match matches.get_flag("semver2") {
+        true => VersionComparison::SemVer2,
+        false => VersionComparison::ChocoCompat,
+    });
+}
+
+fn choco_home(matches: &clap::ArgMatches) -> Result<ChocoHome, RocoError> {
+    match matches.get_one::<String>("choco-root") {
+        Some(root) => ChocoHome::from_dir(root),
+        None => ChocoHome::from_env(),
+    }
+}
 
 #[tokio::main]
 async fn main() {
     let matches = cli::build_cli().get_matches();
 
-    match matches.subcommand() {
-        Some(("list", matches)) => command_list::list(matches),
-        Some(("bad", matches)) => command_bad::bad(matches),
-        Some(("license", matches)) => command_license::license(matches),
-        Some(("outdated", matches)) => command_outdated::outdated(matches).await,
-        Some(("upgrade", matches)) => command_upgrade::upgrade(matches).await,
-        Some(("source", matches)) => command_source::source(matches),
+    let res = match matches.subcommand() {
+        Some(("list", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_list::list(&home, sub_matches))
+        }
+        Some(("info", sub_matches)) => match choco_home(&matches) {
+            Ok(home) => command_info::info(&home, sub_matches).await,
+            Err(e) => Err(e),
+        },
+        Some(("verify", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_verify::verify(&home, sub_matches))
+        }
+        Some(("doctor", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_doctor::doctor(&home, sub_matches))
+        }
+        Some(("bad", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_bad::bad(&home, sub_matches))
+        }
+        Some(("license", matches)) => {
+            command_license::license(matches);
+            Ok(())
+        }
+        Some(("outdated", sub_matches)) => match choco_home(&matches) {
+            Ok(home) => command_outdated::outdated(&home, sub_matches).await,
+            Err(e) => Err(e),
+        },
+        Some(("upgrade", sub_matches)) => match choco_home(&matches) {
+            Ok(home) => command_upgrade::upgrade(&home, sub_matches).await,
+            Err(e) => Err(e),
+        },
+        Some(("search", sub_matches)) => match choco_home(&matches) {
+            Ok(home) => command_search::search(&home, sub_matches).await,
+            Err(e) => Err(e),
+        },
+        Some(("versions", sub_matches)) => match choco_home(&matches) {
+            Ok(home) => command_versions::versions(&home, sub_matches).await,
+            Err(e) => Err(e),
+        },
+        Some(("source", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_source::source(&home, sub_matches))
+        }
+        Some(("pin", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_pin::pin(&home, sub_matches))
+        }
+        Some(("export", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_export::export(&home, sub_matches))
+        }
+        Some(("diff", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_diff::diff(&home, sub_matches))
+        }
+        Some(("drift", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_drift::drift(&home, sub_matches))
+        }
+        Some(("feature", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_feature::feature(&home, sub_matches))
+        }
+        Some(("deps", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_deps::deps(&home, sub_matches))
+        }
+        Some(("config", sub_matches)) => {
+            choco_home(&matches).and_then(|home| command_config::config(&home, sub_matches))
+        }
         _ => {
             if let Err(e) = cli::build_cli().print_help() {
                 eprintln!("Error printing help: {}", e);
             }
             println!(); // Add a newline after the help text
+            Ok(())
         }
+    };
+
+    if let Err(e) = res {
+        eprintln!("ERROR: {}", e);
+        std::process::exit(exit_code(&e));
     }
 }
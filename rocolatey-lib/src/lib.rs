@@ -4,6 +4,7 @@ pub mod roco;
 
 pub static ROCO_VERBOSE: AtomicBool = AtomicBool::new(false);
 pub static ROCO_REQUIRE_SSL: AtomicBool = AtomicBool::new(false);
+pub static ROCO_SEMVER2_ORDERING: AtomicBool = AtomicBool::new(false);
 
 pub fn set_ssl_enabled(enable_ssl: bool) {
     ROCO_REQUIRE_SSL.store(enable_ssl, Ordering::Relaxed);
@@ -13,6 +14,19 @@ pub fn is_ssl_required() -> bool {
     ROCO_REQUIRE_SSL.load(Ordering::Relaxed)
 }
 
+/// how remote and local package versions are compared (default: choco compatible)
+pub fn set_version_comparison(mode: roco::version::VersionComparison) {
+    let semver2 = mode == roco::version::VersionComparison::SemVer2;
+    ROCO_SEMVER2_ORDERING.store(semver2, Ordering::Relaxed);
+}
+
+pub fn version_comparison() -> roco::version::VersionComparison {
+    match ROCO_SEMVER2_ORDERING.load(Ordering::Relaxed) {
+        true => roco::version::VersionComparison::SemVer2,
+        false => roco::version::VersionComparison::ChocoCompat,
+    }
+}
+
 pub fn set_verbose_mode(verbose: bool) {
     ROCO_VERBOSE.store(verbose, Ordering::Relaxed);
 }
@@ -0,0 +1,343 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::path::Path;
+
+use crate::println_verbose;
+use crate::roco::{xml_attribs_to_map, RocoError};
+
+// model of $ChocolateyInstall/config/chocolatey.config
+// NOTE: encrypted values (source/proxy passwords, api keys) are kept as-is, decryption
+// only happens when a feed is actually used.
+
+#[derive(Debug, Clone, Default)]
+pub struct ChocolateyConfig {
+    pub config: Vec<ConfigValue>,
+    pub sources: Vec<SourceConfig>,
+    pub features: Vec<Feature>,
+    pub api_keys: Vec<ApiKey>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigValue {
+    pub key: String,
+    pub value: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceConfig {
+    pub id: String,
+    pub value: String,
+    pub disabled: bool,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    pub certificate: Option<String>,
+    pub certificate_password: Option<String>,
+    pub bypass_proxy: bool,
+    pub self_service: bool,
+    pub admin_only: bool,
+    pub priority: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feature {
+    pub name: String,
+    pub enabled: bool,
+    pub set_explicitly: bool,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKey {
+    pub source: String,
+    pub key: String,
+}
+
+fn non_empty(v: Option<&str>) -> Option<&str> {
+    v.filter(|v| !v.is_empty())
+}
+
+fn split_list(v: Option<&str>) -> Vec<&str> {
+    match v {
+        Some(v) => v
+            .split([',', ';'])
+            .map(|e| e.trim())
+            .filter(|e| !e.is_empty())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+impl ChocolateyConfig {
+    /// raw value of a `<config>` entry, keys are compared case-insensitive (like choco does)
+    pub fn config_value(&self, key: &str) -> Option<&str> {
+        self.config
+            .iter()
+            .find(|c| c.key.eq_ignore_ascii_case(key))
+            .map(|c| c.value.as_str())
+    }
+
+    pub fn feature(&self, name: &str) -> Option<&Feature> {
+        self.features
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn is_feature_enabled(&self, name: &str) -> bool {
+        self.feature(name).map(|f| f.enabled).unwrap_or(false)
+    }
+
+    pub fn cache_location(&self) -> Option<&str> {
+        non_empty(self.config_value("cacheLocation"))
+    }
+
+    pub fn command_execution_timeout_seconds(&self) -> Option<u64> {
+        self.config_value("commandExecutionTimeoutSeconds")
+            .and_then(|v| v.parse().ok())
+    }
+
+    pub fn web_request_timeout_seconds(&self) -> Option<u64> {
+        self.config_value("webRequestTimeoutSeconds")
+            .and_then(|v| v.parse().ok())
+    }
+
+    pub fn proxy(&self) -> Option<&str> {
+        non_empty(self.config_value("proxy"))
+    }
+
+    pub fn proxy_user(&self) -> Option<&str> {
+        non_empty(self.config_value("proxyUser"))
+    }
+
+    /// the (encrypted) proxy password
+    pub fn proxy_password(&self) -> Option<&str> {
+        non_empty(self.config_value("proxyPassword"))
+    }
+
+    pub fn proxy_bypass_list(&self) -> Vec<&str> {
+        split_list(self.config_value("proxyBypassList"))
+    }
+
+    pub fn proxy_bypass_on_local(&self) -> bool {
+        self.config_value("proxyBypassOnLocal")
+            .map(|v| v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false)
+    }
+
+    pub fn upgrade_all_exceptions(&self) -> Vec<&str> {
+        split_list(self.config_value("upgradeAllExceptions"))
+    }
+}
+
+fn get_source_from_attribs(
+    origin: &str,
+    attrs: &mut quick_xml::events::attributes::Attributes,
+) -> Result<SourceConfig, RocoError> {
+    let attrib_map = xml_attribs_to_map(attrs);
+
+    let id = attrib_map
+        .get("id")
+        .ok_or_else(|| RocoError::config_parse(origin, "source without 'id' attribute"))?;
+    let value = attrib_map.get("value").ok_or_else(|| {
+        RocoError::config_parse(origin, format!("source '{}' without 'value' attribute", id))
+    })?;
+
+    let flag = |name: &str| match attrib_map.get(name) {
+        Some(c) => c.eq_ignore_ascii_case("true"),
+        None => false,
+    };
+    // choco writes empty attributes for unset values
+    let opt = |name: &str| attrib_map.get(name).filter(|v| !v.is_empty()).cloned();
+
+    Ok(SourceConfig {
+        id: id.clone(),
+        value: value.clone(),
+        disabled: flag("disabled"),
+        user: opt("user"),
+        password: opt("password"),
+        certificate: opt("certificate"),
+        certificate_password: opt("certificatePassword"),
+        bypass_proxy: flag("bypassProxy"),
+        self_service: flag("selfService"),
+        admin_only: flag("adminOnly"),
+        priority: match attrib_map.get("priority") {
+            Some(c) => c.parse::<i64>().unwrap_or(0),
+            None => 0,
+        },
+    })
+}
+
+pub fn get_config_from_file(cfg_path: &Path) -> Result<ChocolateyConfig, RocoError> {
+    let path_str = cfg_path.to_string_lossy();
+    println_verbose(&format!("parse '{}'", path_str));
+    if !cfg_path.is_file() {
+        return Err(RocoError::ConfigMissing(path_str.to_string()));
+    }
+    let content = std::fs::read(cfg_path)?;
+    get_config_from_text(&path_str, &content)
+}
+
+pub fn get_config_from_text(origin: &str, content: &[u8]) -> Result<ChocolateyConfig, RocoError> {
+    let mut cfg = ChocolateyConfig::default();
+
+    let mut reader = Reader::from_reader(content);
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    // element names from the document root down to the current element
+    let mut path: Vec<Vec<u8>> = Vec::new();
+
+    loop {
+        let (e, is_empty) = match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => (e, false),
+            Ok(Event::Empty(e)) => (e, true),
+            Ok(Event::End(_)) => {
+                path.pop();
+                buf.clear();
+                continue;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(RocoError::config_parse(origin, e)),
+            _ => {
+                buf.clear();
+                continue;
+            }
+        };
+
+        let section = match path.as_slice() {
+            [_root, section] => section.as_slice(),
+            _ => b"",
+        };
+        match (section, e.name().as_ref()) {
+            (b"config", b"add") => {
+                let mut attrib_map = xml_attribs_to_map(&mut e.attributes());
+                if let Some(key) = attrib_map.remove("key") {
+                    cfg.config.push(ConfigValue {
+                        key,
+                        value: attrib_map.remove("value").unwrap_or_default(),
+                        description: attrib_map.remove("description").unwrap_or_default(),
+                    });
+                }
+            }
+            (b"sources", b"source") => {
+                cfg.sources
+                    .push(get_source_from_attribs(origin, &mut e.attributes())?);
+            }
+            (b"features", b"feature") => {
+                let mut attrib_map = xml_attribs_to_map(&mut e.attributes());
+                if let Some(name) = attrib_map.remove("name") {
+                    let is_true = |v: Option<&String>| {
+                        v.map(|v| v.eq_ignore_ascii_case("true")).unwrap_or(false)
+                    };
+                    cfg.features.push(Feature {
+                        enabled: is_true(attrib_map.get("enabled")),
+                        set_explicitly: is_true(attrib_map.get("setExplicitly")),
+                        description: attrib_map.remove("description").unwrap_or_default(),
+                        name,
+                    });
+                }
+            }
+            (b"apiKeys", b"apiKeys") => {
+                let mut attrib_map = xml_attribs_to_map(&mut e.attributes());
+                if let Some(source) = attrib_map.remove("source") {
+                    cfg.api_keys.push(ApiKey {
+                        source,
+                        key: attrib_map.remove("key").unwrap_or_default(),
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        if !is_empty {
+            path.push(e.name().as_ref().to_vec());
+        }
+        buf.clear();
+    }
+
+    Ok(cfg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    //NOTE: ChocolateyInstall, RocolateyTestRoot env-vars needs to be set in via Cargo [env]
+
+    #[test]
+    fn get_config_from_file_test() {
+        let mut cfg_path = PathBuf::from(std::env::var("ChocolateyInstall").unwrap());
+        cfg_path.push("config/chocolatey.config");
+        let cfg = get_config_from_file(&cfg_path).unwrap();
+
+        assert_eq!(cfg.sources.len(), 4);
+        assert_eq!(cfg.sources[2].id, "badurl");
+        assert!(cfg.sources[2].disabled);
+        assert!(cfg.sources[2].bypass_proxy);
+        assert_eq!(cfg.sources[2].user.as_deref(), Some("roco|user"));
+        assert_eq!(cfg.sources[2].certificate, None);
+        assert_eq!(cfg.sources[2].certificate_password, None);
+        assert!(cfg.sources[3].self_service);
+        assert!(cfg.sources[3].admin_only);
+        assert_eq!(cfg.features.len(), 46);
+        assert!(cfg.api_keys.is_empty());
+
+        assert!(cfg.is_feature_enabled("checksumFiles"));
+        assert!(!cfg.is_feature_enabled("allowGlobalConfirmation"));
+        assert!(
+            cfg.feature("stopOnFirstPackageFailure")
+                .unwrap()
+                .set_explicitly
+        );
+        assert!(!cfg.is_feature_enabled("doesNotExist"));
+
+        assert_eq!(cfg.cache_location(), None);
+        assert_eq!(cfg.command_execution_timeout_seconds(), Some(18000));
+        assert_eq!(cfg.web_request_timeout_seconds(), Some(30));
+        assert_eq!(cfg.proxy(), None);
+        assert!(cfg.proxy_bypass_list().is_empty());
+        assert!(cfg.proxy_bypass_on_local());
+        assert_eq!(cfg.config_value("VIRUSSCANNERTYPE"), Some("Generic"));
+    }
+
+    #[test]
+    fn get_config_from_text_test() {
+        let cfg = get_config_from_text(
+            "inline",
+            br#"<?xml version="1.0" encoding="utf-8"?>
+<chocolatey>
+  <config>
+    <add key="proxy" value="http://proxy:8080" />
+    <add key="proxyBypassList" value="*.local, intranet;build01" />
+    <add key="upgradeAllExceptions" value="jre8,python3" />
+  </config>
+  <sources />
+  <features>
+    <feature name="allowGlobalConfirmation" enabled="true" setExplicitly="true" />
+    <feature name="checksumFiles" enabled="True" setExplicitly="TRUE" />
+  </features>
+  <apiKeys>
+    <apiKeys source="https://push.chocolatey.org/" key="c2VjcmV0" />
+  </apiKeys>
+</chocolatey>"#,
+        )
+        .unwrap();
+
+        assert!(cfg.sources.is_empty());
+        assert_eq!(cfg.proxy(), Some("http://proxy:8080"));
+        assert_eq!(
+            cfg.proxy_bypass_list(),
+            vec!["*.local", "intranet", "build01"]
+        );
+        assert_eq!(cfg.upgrade_all_exceptions(), vec!["jre8", "python3"]);
+        assert!(cfg.is_feature_enabled("allowGlobalConfirmation"));
+        assert!(cfg.is_feature_enabled("checksumFiles"));
+        assert!(cfg.features[1].set_explicitly);
+        assert_eq!(
+            cfg.api_keys,
+            vec![ApiKey {
+                source: "https://push.chocolatey.org/".to_string(),
+                key: "c2VjcmV0".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,526 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::path::Path;
+
+use crate::println_verbose;
+use crate::roco::config::{get_config_from_text, SourceConfig};
+use crate::roco::{ChocoHome, RocoError};
+
+// edits the <sources> section of chocolatey.config in place:
+// everything outside of the touched <source> element (comments, whitespace, other sections)
+// is kept byte by byte, modified elements keep their attribute order and unknown attributes.
+
+#[derive(Debug, Clone)]
+pub enum SourceEdit {
+    /// add a new source, or replace the settings of an existing one (same as 'choco source add')
+    Add(SourceConfig),
+    Remove(String),
+    Enable(String),
+    Disable(String),
+    Priority(String, i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceEditResult {
+    Added,
+    Updated,
+    Removed,
+    Enabled,
+    Disabled,
+    Unchanged,
+}
+
+struct SourceElement {
+    start: usize,
+    end: usize,
+    attributes: Vec<(String, String)>,
+}
+
+struct SourcesSection {
+    // span of the opening '<sources>' (or '<sources />') tag
+    open_start: usize,
+    open_end: usize,
+    // start of '</sources>'
+    close_start: Option<usize>,
+    sources: Vec<SourceElement>,
+}
+
+fn find_sources_section(origin: &str, content: &str) -> Result<SourcesSection, RocoError> {
+    let mut reader = Reader::from_str(content);
+    let mut depth = 0;
+    let mut section: Option<SourcesSection> = None;
+    let mut in_sources = false;
+
+    loop {
+        let start = reader.buffer_position();
+        let event = reader
+            .read_event()
+            .map_err(|e| RocoError::config_parse(origin, e))?;
+        let end = reader.buffer_position();
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                match (depth, in_sources, e.name().as_ref()) {
+                    (1, _, b"sources") => {
+                        section = Some(SourcesSection {
+                            open_start: start,
+                            open_end: end,
+                            close_start: None,
+                            sources: Vec::new(),
+                        });
+                        in_sources = !is_empty;
+                    }
+                    (2, true, b"source") => {
+                        let attributes = e
+                            .attributes()
+                            .map(|a| {
+                                a.map(|a| {
+                                    (
+                                        String::from_utf8_lossy(a.key.as_ref()).to_string(),
+                                        String::from_utf8_lossy(&a.value).to_string(),
+                                    )
+                                })
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                            .map_err(|e| RocoError::config_parse(origin, e))?;
+                        if let Some(s) = section.as_mut() {
+                            s.sources.push(SourceElement {
+                                start,
+                                end,
+                                attributes,
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+                if !is_empty {
+                    depth += 1;
+                }
+            }
+            Event::End(ref e) => {
+                depth -= 1;
+                if depth == 1 && in_sources && e.name().as_ref() == b"sources" {
+                    if let Some(s) = section.as_mut() {
+                        s.close_start = Some(start);
+                    }
+                    in_sources = false;
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    section.ok_or_else(|| RocoError::config_parse(origin, "no <sources> section"))
+}
+
+fn attribute<'a>(attributes: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+}
+
+fn set_attribute(attributes: &mut Vec<(String, String)>, key: &str, value: Option<&str>) {
+    let value = value.map(|v| quick_xml::escape::escape(v).to_string());
+    let pos = attributes.iter().position(|(k, _)| k == key);
+    match (pos, value) {
+        (Some(i), Some(v)) => attributes[i].1 = v,
+        (Some(i), None) => {
+            attributes.remove(i);
+        }
+        (None, Some(v)) => attributes.push((key.to_string(), v)),
+        (None, None) => {}
+    }
+}
+
+fn c_bool(v: bool) -> &'static str {
+    match v {
+        true => "true",
+        false => "false",
+    }
+}
+
+// attribute order as written by choco
+fn set_source_attributes(attributes: &mut Vec<(String, String)>, source: &SourceConfig) {
+    let priority = source.priority.to_string();
+    set_attribute(attributes, "id", Some(&source.id));
+    set_attribute(attributes, "value", Some(&source.value));
+    set_attribute(attributes, "disabled", Some(c_bool(source.disabled)));
+    set_attribute(attributes, "bypassProxy", Some(c_bool(source.bypass_proxy)));
+    set_attribute(attributes, "selfService", Some(c_bool(source.self_service)));
+    set_attribute(attributes, "adminOnly", Some(c_bool(source.admin_only)));
+    set_attribute(attributes, "user", source.user.as_deref());
+    set_attribute(attributes, "password", source.password.as_deref());
+    set_attribute(attributes, "priority", Some(&priority));
+    set_attribute(attributes, "certificate", source.certificate.as_deref());
+    set_attribute(
+        attributes,
+        "certificatePassword",
+        source.certificate_password.as_deref(),
+    );
+}
+
+fn format_source_element(attributes: &[(String, String)]) -> String {
+    let mut res = String::from("<source");
+    for (k, v) in attributes {
+        res.push_str(&format!(" {}=\"{}\"", k, v));
+    }
+    res.push_str(" />");
+    res
+}
+
+fn line_start(content: &str, pos: usize) -> usize {
+    content[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0)
+}
+
+fn indentation_at(content: &str, pos: usize) -> &str {
+    let start = line_start(content, pos);
+    let prefix = &content[start..pos];
+    match prefix.trim().is_empty() {
+        true => prefix,
+        false => "",
+    }
+}
+
+pub fn apply_source_edit(
+    origin: &str,
+    content: &str,
+    edit: &SourceEdit,
+) -> Result<(String, SourceEditResult), RocoError> {
+    let section = find_sources_section(origin, content)?;
+    let id = match edit {
+        SourceEdit::Add(s) => &s.id,
+        SourceEdit::Remove(id)
+        | SourceEdit::Enable(id)
+        | SourceEdit::Disable(id)
+        | SourceEdit::Priority(id, _) => id,
+    };
+    // source ids are case-insensitive in choco
+    let existing = section.sources.iter().find(|s| {
+        attribute(&s.attributes, "id")
+            .map(|i| i.eq_ignore_ascii_case(id))
+            .unwrap_or(false)
+    });
+
+    let newline = match content.contains("\r\n") {
+        true => "\r\n",
+        false => "\n",
+    };
+
+    let (start, end, replacement, result) = match (edit, existing) {
+        (SourceEdit::Add(source), None) => {
+            let mut attributes = Vec::new();
+            set_source_attributes(&mut attributes, source);
+            let element = format_source_element(&attributes);
+            match (section.sources.last(), section.close_start) {
+                (Some(last), _) => {
+                    let indent = indentation_at(content, last.start);
+                    (
+                        last.end,
+                        last.end,
+                        format!("{}{}{}", newline, indent, element),
+                        SourceEditResult::Added,
+                    )
+                }
+                (None, Some(close_start)) => {
+                    let indent = indentation_at(content, close_start);
+                    let pos = line_start(content, close_start);
+                    match indent.is_empty() {
+                        // '</sources>' shares its line with other content
+                        true => (close_start, close_start, element, SourceEditResult::Added),
+                        false => (
+                            pos,
+                            pos,
+                            format!("{}  {}{}", indent, element, newline),
+                            SourceEditResult::Added,
+                        ),
+                    }
+                }
+                (None, None) => {
+                    // '<sources />'
+                    let indent = indentation_at(content, section.open_start);
+                    (
+                        section.open_start,
+                        section.open_end,
+                        format!(
+                            "<sources>{nl}{indent}  {element}{nl}{indent}</sources>",
+                            nl = newline,
+                            indent = indent,
+                            element = element
+                        ),
+                        SourceEditResult::Added,
+                    )
+                }
+            }
+        }
+        (SourceEdit::Add(source), Some(s)) => {
+            let mut attributes = s.attributes.clone();
+            set_source_attributes(&mut attributes, source);
+            (
+                s.start,
+                s.end,
+                format_source_element(&attributes),
+                SourceEditResult::Updated,
+            )
+        }
+        (SourceEdit::Remove(_), Some(s)) => {
+            // drop the whole line if the element is the only thing on it
+            let ls = line_start(content, s.start);
+            let rest = &content[s.end..];
+            let line_end = rest.find('\n').map(|i| s.end + i + 1);
+            match (content[ls..s.start].trim().is_empty(), line_end) {
+                (true, Some(le)) if content[s.end..le].trim().is_empty() => {
+                    (ls, le, String::new(), SourceEditResult::Removed)
+                }
+                _ => (s.start, s.end, String::new(), SourceEditResult::Removed),
+            }
+        }
+        (SourceEdit::Enable(_), Some(s)) | (SourceEdit::Disable(_), Some(s)) => {
+            let disable = matches!(edit, SourceEdit::Disable(_));
+            let currently_disabled = attribute(&s.attributes, "disabled")
+                .map(|v| v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false);
+            if currently_disabled == disable {
+                return Ok((content.to_string(), SourceEditResult::Unchanged));
+            }
+            let mut attributes = s.attributes.clone();
+            set_attribute(&mut attributes, "disabled", Some(c_bool(disable)));
+            let result = match disable {
+                true => SourceEditResult::Disabled,
+                false => SourceEditResult::Enabled,
+            };
+            (s.start, s.end, format_source_element(&attributes), result)
+        }
+        (SourceEdit::Priority(_, priority), Some(s)) => {
+            let priority = priority.to_string();
+            if attribute(&s.attributes, "priority") == Some(priority.as_str()) {
+                return Ok((content.to_string(), SourceEditResult::Unchanged));
+            }
+            let mut attributes = s.attributes.clone();
+            set_attribute(&mut attributes, "priority", Some(&priority));
+            (
+                s.start,
+                s.end,
+                format_source_element(&attributes),
+                SourceEditResult::Updated,
+            )
+        }
+        (_, None) => return Err(RocoError::SourceNotFound(id.to_string())),
+    };
+
+    let mut res = String::with_capacity(content.len() + replacement.len());
+    res.push_str(&content[..start]);
+    res.push_str(&replacement);
+    res.push_str(&content[end..]);
+
+    // never write something we can't read back ourselves
+    get_config_from_text(origin, res.as_bytes())?;
+    Ok((res, result))
+}
+
+/// writes the config via a temporary file + rename,
+/// the previous version is kept as 'chocolatey.config.backup' (same as choco does)
+pub fn write_config_file(cfg_path: &Path, content: &str) -> Result<(), RocoError> {
+    let file_name = cfg_path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_else(|| "chocolatey.config".to_string());
+    let tmp_path = cfg_path.with_file_name(format!("{}.roco-tmp", file_name));
+    let backup_path = cfg_path.with_file_name(format!("{}.backup", file_name));
+
+    {
+        use std::io::Write;
+        let mut tmp = std::fs::File::create(&tmp_path)?;
+        tmp.write_all(content.as_bytes())?;
+        tmp.sync_all()?;
+    }
+    if cfg_path.is_file() {
+        println_verbose(&format!("backup '{}'", backup_path.to_string_lossy()));
+        if let Err(e) = std::fs::copy(cfg_path, &backup_path) {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(e.into());
+        }
+    }
+    std::fs::rename(&tmp_path, cfg_path)?;
+    Ok(())
+}
+
+pub fn edit_choco_source(
+    home: &ChocoHome,
+    edit: &SourceEdit,
+) -> Result<SourceEditResult, RocoError> {
+    let cfg_path = home.config_file();
+    let origin = cfg_path.to_string_lossy().to_string();
+    if !cfg_path.is_file() {
+        return Err(RocoError::ConfigMissing(origin));
+    }
+    let content = std::fs::read_to_string(&cfg_path)?;
+    let (new_content, result) = apply_source_edit(&origin, &content, edit)?;
+    if result != SourceEditResult::Unchanged {
+        write_config_file(&cfg_path, &new_content)?;
+    }
+    Ok(result)
+}
+
+/// applies the edit and returns a choco-like status message
+pub fn edit_choco_source_text(home: &ChocoHome, edit: &SourceEdit) -> Result<String, RocoError> {
+    let result = edit_choco_source(home, edit)?;
+    Ok(match (result, edit) {
+        (SourceEditResult::Unchanged, _) => "Nothing to change. Config already set.".to_string(),
+        (SourceEditResult::Added, SourceEdit::Add(s)) => {
+            format!("Added {} - {} (Priority {})", s.id, s.value, s.priority)
+        }
+        (SourceEditResult::Updated, SourceEdit::Add(s)) => {
+            format!("Updated {} - {} (Priority {})", s.id, s.value, s.priority)
+        }
+        (SourceEditResult::Updated, SourceEdit::Priority(id, p)) => {
+            format!("Updated {} (Priority {})", id, p)
+        }
+        (SourceEditResult::Removed, SourceEdit::Remove(id)) => format!("Removed {}", id),
+        (SourceEditResult::Enabled, SourceEdit::Enable(id)) => format!("Enabled {}", id),
+        (SourceEditResult::Disabled, SourceEdit::Disable(id)) => format!("Disabled {}", id),
+        (r, _) => format!("{:?}", r),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::TestDir;
+
+    const CFG: &str = concat!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\r\n",
+        "<chocolatey>\r\n",
+        "  <config>\r\n",
+        "    <add key=\"proxy\" value=\"\" />\r\n",
+        "  </config>\r\n",
+        "  <sources>\r\n",
+        "    <!-- internal feeds -->\r\n",
+        "    <source id=\"chocolatey\" value=\"https://chocolatey.org/api/v2\" disabled=\"false\" bypassProxy=\"false\" selfService=\"false\" adminOnly=\"false\" priority=\"0\" />\r\n",
+        "    <source id=\"internal\" value=\"https://repo/nuget\" disabled=\"true\" customAttrib=\"keep &amp; me\" priority=\"10\" />\r\n",
+        "  </sources>\r\n",
+        "  <features />\r\n",
+        "</chocolatey>\r\n"
+    );
+
+    fn new_source(id: &str, value: &str) -> SourceConfig {
+        SourceConfig {
+            id: id.to_string(),
+            value: value.to_string(),
+            disabled: false,
+            user: None,
+            password: None,
+            certificate: None,
+            certificate_password: None,
+            bypass_proxy: false,
+            self_service: false,
+            admin_only: false,
+            priority: 5,
+        }
+    }
+
+    #[test]
+    fn add_source_test() {
+        let (res, r) = apply_source_edit(
+            "test",
+            CFG,
+            &SourceEdit::Add(new_source("local", "c:\\pkgs")),
+        )
+        .unwrap();
+        assert_eq!(r, SourceEditResult::Added);
+        assert!(res.contains("customAttrib=\"keep &amp; me\" priority=\"10\" />\r\n    <source id=\"local\" value=\"c:\\pkgs\" disabled=\"false\" bypassProxy=\"false\" selfService=\"false\" adminOnly=\"false\" priority=\"5\" />\r\n  </sources>"));
+        assert!(res.contains("<!-- internal feeds -->"));
+        let cfg = get_config_from_text("test", res.as_bytes()).unwrap();
+        assert_eq!(cfg.sources.len(), 3);
+        assert_eq!(cfg.sources[2].priority, 5);
+    }
+
+    #[test]
+    fn add_existing_source_test() {
+        let mut source = new_source("INTERNAL", "https://repo/v3/index.json");
+        source.user = Some("ci".to_string());
+        source.password = Some("c2VjcmV0".to_string());
+        let (res, r) = apply_source_edit("test", CFG, &SourceEdit::Add(source)).unwrap();
+        assert_eq!(r, SourceEditResult::Updated);
+        // attribute order + unknown attributes are kept
+        assert!(res.contains("<source id=\"INTERNAL\" value=\"https://repo/v3/index.json\" disabled=\"false\" customAttrib=\"keep &amp; me\" priority=\"5\" bypassProxy=\"false\" selfService=\"false\" adminOnly=\"false\" user=\"ci\" password=\"c2VjcmV0\" />"));
+        assert_eq!(
+            get_config_from_text("test", res.as_bytes())
+                .unwrap()
+                .sources
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn add_source_to_empty_section_test() {
+        let cfg = "<chocolatey>\n  <sources />\n</chocolatey>\n";
+        let (res, _) =
+            apply_source_edit("test", cfg, &SourceEdit::Add(new_source("a", "c:\\a"))).unwrap();
+        assert_eq!(res, "<chocolatey>\n  <sources>\n    <source id=\"a\" value=\"c:\\a\" disabled=\"false\" bypassProxy=\"false\" selfService=\"false\" adminOnly=\"false\" priority=\"5\" />\n  </sources>\n</chocolatey>\n");
+
+        let cfg = "<chocolatey>\n  <sources>\n  </sources>\n</chocolatey>\n";
+        let (res, _) =
+            apply_source_edit("test", cfg, &SourceEdit::Add(new_source("a", "c:\\a"))).unwrap();
+        assert_eq!(res, "<chocolatey>\n  <sources>\n    <source id=\"a\" value=\"c:\\a\" disabled=\"false\" bypassProxy=\"false\" selfService=\"false\" adminOnly=\"false\" priority=\"5\" />\n  </sources>\n</chocolatey>\n");
+    }
+
+    #[test]
+    fn remove_source_test() {
+        let (res, r) =
+            apply_source_edit("test", CFG, &SourceEdit::Remove("chocolatey".to_string())).unwrap();
+        assert_eq!(r, SourceEditResult::Removed);
+        assert_eq!(
+            res,
+            CFG.replace("    <source id=\"chocolatey\" value=\"https://chocolatey.org/api/v2\" disabled=\"false\" bypassProxy=\"false\" selfService=\"false\" adminOnly=\"false\" priority=\"0\" />\r\n", "")
+        );
+
+        assert!(matches!(
+            apply_source_edit("test", CFG, &SourceEdit::Remove("nope".to_string())),
+            Err(RocoError::SourceNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn enable_disable_priority_test() {
+        let (res, r) =
+            apply_source_edit("test", CFG, &SourceEdit::Enable("internal".to_string())).unwrap();
+        assert_eq!(r, SourceEditResult::Enabled);
+        assert!(res.contains("<source id=\"internal\" value=\"https://repo/nuget\" disabled=\"false\" customAttrib=\"keep &amp; me\" priority=\"10\" />"));
+
+        let (res, r) =
+            apply_source_edit("test", CFG, &SourceEdit::Disable("internal".to_string())).unwrap();
+        assert_eq!(r, SourceEditResult::Unchanged);
+        assert_eq!(res, CFG);
+
+        let (res, r) = apply_source_edit(
+            "test",
+            CFG,
+            &SourceEdit::Priority("chocolatey".to_string(), 42),
+        )
+        .unwrap();
+        assert_eq!(r, SourceEditResult::Updated);
+        let cfg = get_config_from_text("test", res.as_bytes()).unwrap();
+        assert_eq!(cfg.sources[0].priority, 42);
+        assert_eq!(cfg.sources[1].priority, 10);
+    }
+
+    #[test]
+    fn write_config_file_test() {
+        let dir = TestDir::new("cfg-writer");
+        let cfg_path = dir.write("chocolatey.config", CFG);
+
+        let (res, _) =
+            apply_source_edit("test", CFG, &SourceEdit::Disable("chocolatey".to_string())).unwrap();
+        write_config_file(&cfg_path, &res).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&cfg_path).unwrap(), res);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("chocolatey.config.backup")).unwrap(),
+            CFG
+        );
+        assert!(!dir.join("chocolatey.config.roco-tmp").exists());
+    }
+}
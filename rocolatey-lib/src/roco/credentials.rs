@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::println_verbose;
+use crate::roco::RocoError;
+
+// decryption of the passwords stored in chocolatey.config (sources + proxy)
+// choco protects them using DPAPI (LocalMachine scope, entropy "Chocolatey"), which is only
+// available on Windows - the other backends allow using credentialed feeds elsewhere (e.g. tests).
+
+/// what an encrypted value belongs to, backends not able to decrypt the value itself
+/// can use this to look up the plain-text value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CredentialTarget<'a> {
+    /// password of the source with the given id
+    Source(&'a str),
+    Proxy,
+}
+
+impl CredentialTarget<'_> {
+    pub fn name(&self) -> &str {
+        match self {
+            CredentialTarget::Source(id) => id,
+            CredentialTarget::Proxy => "proxy",
+        }
+    }
+}
+
+pub trait CredentialDecryptor: Send + Sync {
+    fn decrypt(&self, target: CredentialTarget, encrypted: &str) -> Result<String, RocoError>;
+}
+
+/// decrypts values the same way choco does (Windows only)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DpapiDecryptor;
+
+impl CredentialDecryptor for DpapiDecryptor {
+    fn decrypt(&self, target: CredentialTarget, encrypted: &str) -> Result<String, RocoError> {
+        println_verbose(&format!("decypher '{}' ({})", encrypted, target.name()));
+        dpapi::unprotect(encrypted).map_err(|message| RocoError::CredentialDecrypt {
+            name: target.name().to_string(),
+            message,
+        })
+    }
+}
+
+/// encrypt a value the same way choco does for source/proxy passwords (Windows only)
+pub fn encrypt_choco_config_string(plain: &str) -> Result<String, RocoError> {
+    dpapi::protect(plain).map_err(RocoError::CredentialEncrypt)
+}
+
+/// plain-text credentials supplied out of band, looked up by source id (or 'proxy'):
+/// 1. env var `ROCO_CREDENTIAL_<NAME>` (upper case, non-alphanumeric characters replaced by '_')
+/// 2. `<name>=<password>` lines of a key file (names are case-insensitive, '#' starts a comment)
+#[derive(Debug, Clone, Default)]
+pub struct EnvCredentialDecryptor {
+    entries: HashMap<String, String>,
+    /// `ROCO_CREDENTIAL_*` variables, by name
+    env_vars: HashMap<String, String>,
+}
+
+const CREDENTIAL_ENV_PREFIX: &str = "ROCO_CREDENTIAL_";
+/// env var pointing to a key file used by `EnvCredentialDecryptor::from_env`
+pub const CREDENTIAL_FILE_ENV: &str = "RocolateyCredentialFile";
+
+fn env_var_name(name: &str) -> String {
+    let normalized: String = name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_uppercase(),
+            false => '_',
+        })
+        .collect();
+    format!("{}{}", CREDENTIAL_ENV_PREFIX, normalized)
+}
+
+impl EnvCredentialDecryptor {
+    /// key file from `RocolateyCredentialFile` (if set) and the `ROCO_CREDENTIAL_*` env vars
+    pub fn from_env() -> Result<EnvCredentialDecryptor, RocoError> {
+        let decryptor = match std::env::var(CREDENTIAL_FILE_ENV) {
+            Ok(path) if !path.is_empty() => EnvCredentialDecryptor::from_file(Path::new(&path))?,
+            _ => EnvCredentialDecryptor::default(),
+        };
+        Ok(decryptor.with_env_vars(std::env::vars()))
+    }
+
+    /// uses the `ROCO_CREDENTIAL_*` variables of the given environment,
+    /// they take precedence over the key file
+    pub fn with_env_vars<I, K, V>(mut self, vars: I) -> EnvCredentialDecryptor
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env_vars = vars
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .filter(|(k, _)| k.starts_with(CREDENTIAL_ENV_PREFIX))
+            .collect();
+        self
+    }
+
+    pub fn from_file(path: &Path) -> Result<EnvCredentialDecryptor, RocoError> {
+        println_verbose(&format!(
+            "read credentials from '{}'",
+            path.to_string_lossy()
+        ));
+        let content = std::fs::read_to_string(path)?;
+        Ok(EnvCredentialDecryptor::from_text(&content))
+    }
+
+    pub fn from_text(content: &str) -> EnvCredentialDecryptor {
+        let entries = content
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .filter_map(|l| l.split_once('='))
+            .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+            .collect();
+        EnvCredentialDecryptor {
+            entries,
+            env_vars: HashMap::new(),
+        }
+    }
+
+    /// true if there is a plain-text value for the given target
+    pub fn contains(&self, target: CredentialTarget) -> bool {
+        self.lookup(target).is_some()
+    }
+
+    fn lookup(&self, target: CredentialTarget) -> Option<String> {
+        self.env_vars
+            .get(&env_var_name(target.name()))
+            .cloned()
+            .or_else(|| self.entries.get(&target.name().to_lowercase()).cloned())
+    }
+}
+
+impl CredentialDecryptor for EnvCredentialDecryptor {
+    fn decrypt(&self, target: CredentialTarget, _encrypted: &str) -> Result<String, RocoError> {
+        self.lookup(target)
+            .ok_or_else(|| RocoError::CredentialDecrypt {
+                name: target.name().to_string(),
+                message: format!(
+                    "no plain-text credential, set '{}' or add it to '{}'",
+                    env_var_name(target.name()),
+                    CREDENTIAL_FILE_ENV
+                ),
+            })
+    }
+}
+
+/// test stand-in: maps encrypted values to plain-text values
+#[derive(Debug, Clone, Default)]
+pub struct StaticDecryptor {
+    values: HashMap<String, String>,
+}
+
+impl StaticDecryptor {
+    pub fn new<I, K, V>(values: I) -> StaticDecryptor
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        StaticDecryptor {
+            values: values
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        }
+    }
+}
+
+impl CredentialDecryptor for StaticDecryptor {
+    fn decrypt(&self, target: CredentialTarget, encrypted: &str) -> Result<String, RocoError> {
+        self.values
+            .get(encrypted)
+            .cloned()
+            .ok_or_else(|| RocoError::CredentialDecrypt {
+                name: target.name().to_string(),
+                message: "unknown value".to_string(),
+            })
+    }
+}
+
+/// uses plain-text credentials from the environment (see `EnvCredentialDecryptor`) if any target
+/// is resolved that way, DPAPI otherwise
+struct DefaultDecryptor {
+    env: EnvCredentialDecryptor,
+}
+
+impl CredentialDecryptor for DefaultDecryptor {
+    fn decrypt(&self, target: CredentialTarget, encrypted: &str) -> Result<String, RocoError> {
+        match self.env.contains(target) {
+            true => self.env.decrypt(target, encrypted),
+            false => DpapiDecryptor.decrypt(target, encrypted),
+        }
+    }
+}
+
+pub fn default_decryptor() -> Result<Box<dyn CredentialDecryptor>, RocoError> {
+    Ok(Box::new(DefaultDecryptor {
+        env: EnvCredentialDecryptor::from_env()?,
+    }))
+}
+
+#[cfg(windows)]
+mod dpapi {
+    use base64::Engine;
+    use windows_sys::Win32::Foundation::LocalFree;
+    use windows_sys::Win32::Security::Cryptography::{
+        CryptProtectData, CryptUnprotectData, CRYPTPROTECT_LOCAL_MACHINE,
+        CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB,
+    };
+
+    const ENTROPY: &[u8] = b"Chocolatey";
+
+    fn blob(data: &[u8]) -> CRYPT_INTEGER_BLOB {
+        CRYPT_INTEGER_BLOB {
+            cbData: data.len() as u32,
+            pbData: data.as_ptr() as *mut u8,
+        }
+    }
+
+    fn take_blob(out: CRYPT_INTEGER_BLOB) -> Vec<u8> {
+        let res = unsafe { std::slice::from_raw_parts(out.pbData, out.cbData as usize) }.to_vec();
+        unsafe {
+            LocalFree(out.pbData as _);
+        }
+        res
+    }
+
+    pub fn unprotect(encrypted: &str) -> Result<String, String> {
+        let encrypted = base64::engine::general_purpose::STANDARD
+            .decode(encrypted.trim())
+            .map_err(|e| e.to_string())?;
+        let input = blob(&encrypted);
+        let entropy = blob(ENTROPY);
+        let mut out = blob(&[]);
+        let ok = unsafe {
+            CryptUnprotectData(
+                &input,
+                std::ptr::null_mut(),
+                &entropy,
+                std::ptr::null(),
+                std::ptr::null(),
+                CRYPTPROTECT_UI_FORBIDDEN,
+                &mut out,
+            )
+        };
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        String::from_utf8(take_blob(out)).map_err(|e| e.to_string())
+    }
+
+    pub fn protect(plain: &str) -> Result<String, String> {
+        let input = blob(plain.as_bytes());
+        let entropy = blob(ENTROPY);
+        let mut out = blob(&[]);
+        let ok = unsafe {
+            CryptProtectData(
+                &input,
+                std::ptr::null(),
+                &entropy,
+                std::ptr::null(),
+                std::ptr::null(),
+                CRYPTPROTECT_LOCAL_MACHINE | CRYPTPROTECT_UI_FORBIDDEN,
+                &mut out,
+            )
+        };
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        Ok(base64::engine::general_purpose::STANDARD.encode(take_blob(out)))
+    }
+}
+
+#[cfg(not(windows))]
+mod dpapi {
+    const UNSUPPORTED: &str = "DPAPI is only available on Windows, use ROCO_CREDENTIAL_<NAME> or RocolateyCredentialFile to provide plain-text credentials";
+
+    pub fn unprotect(_encrypted: &str) -> Result<String, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub fn protect(_plain: &str) -> Result<String, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_credential_decryptor_test() {
+        let d = EnvCredentialDecryptor::from_text(
+            "# roco credentials\r\nInternal-Feed = s3cr=t\r\n\r\nproxy=pr0xy\r\nbroken line\r\n",
+        );
+        assert_eq!(
+            d.decrypt(CredentialTarget::Source("internal-feed"), "AQAA")
+                .unwrap(),
+            "s3cr=t"
+        );
+        assert_eq!(d.decrypt(CredentialTarget::Proxy, "AQAA").unwrap(), "pr0xy");
+        assert!(matches!(
+            d.decrypt(CredentialTarget::Source("other"), "AQAA"),
+            Err(RocoError::CredentialDecrypt { .. })
+        ));
+
+        // env vars take precedence over the key file
+        let d = d.with_env_vars([
+            ("ROCO_CREDENTIAL_INTERNAL_FEED", "from-env"),
+            ("PATH", "/usr/bin"),
+        ]);
+        assert_eq!(
+            d.decrypt(CredentialTarget::Source("internal-feed"), "AQAA")
+                .unwrap(),
+            "from-env"
+        );
+        assert_eq!(d.decrypt(CredentialTarget::Proxy, "AQAA").unwrap(), "pr0xy");
+    }
+
+    #[test]
+    fn static_decryptor_test() {
+        let d = StaticDecryptor::new([("AQAAANCM", "secret")]);
+        assert_eq!(
+            d.decrypt(CredentialTarget::Proxy, "AQAAANCM").unwrap(),
+            "secret"
+        );
+        assert!(d.decrypt(CredentialTarget::Proxy, "other").is_err());
+    }
+}
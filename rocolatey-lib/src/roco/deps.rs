@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use crate::roco::graph::DependencyGraph;
+use crate::roco::local::get_local_packages;
+use crate::roco::{ChocoHome, Package, RocoError};
+
+// analysis of the dependencies declared in the nuspecs of installed packages
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DependencyProblem {
+    /// no package with the dependency id is installed
+    Missing,
+    /// the installed version is not within the declared version range
+    OutOfRange { installed_version: String },
+    /// installed, but the id differs in case (e.g. 'chocolatey' vs 'Chocolatey')
+    CaseMismatch { installed_id: String },
+    /// the declared version range can't be parsed
+    InvalidRange,
+    /// the package depends on itself
+    SelfDependency,
+    /// the package is part of a dependency cycle, e.g. [a, b, c] for a -> b -> c -> a
+    Cycle { path: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyIssue {
+    pub package_id: String,
+    pub package_version: String,
+    pub dependency_id: String,
+    /// the version range as declared in the nuspec
+    pub dependency_range: String,
+    pub problem: DependencyProblem,
+}
+
+/// installed packages by lower case id
+pub(crate) fn packages_by_id(packages: &[Package]) -> HashMap<String, &Package> {
+    packages.iter().map(|p| (p.id.to_lowercase(), p)).collect()
+}
+
+fn check_dependency(dependency: &Package, installed: Option<&&Package>) -> Vec<DependencyProblem> {
+    let installed = match installed {
+        Some(installed) => installed,
+        None => return vec![DependencyProblem::Missing],
+    };
+    let mut problems = Vec::new();
+    if installed.id != dependency.id {
+        problems.push(DependencyProblem::CaseMismatch {
+            installed_id: installed.id.clone(),
+        });
+    }
+    match dependency.version_range() {
+        Ok(range) => match installed.version.parse() {
+            Ok(version) if range.satisfies(&version) => {}
+            // an installed version we can't parse can't be within the range either
+            _ => problems.push(DependencyProblem::OutOfRange {
+                installed_version: installed.version.clone(),
+            }),
+        },
+        Err(_) => problems.push(DependencyProblem::InvalidRange),
+    }
+    problems
+}
+
+/// evaluates the dependencies of all given packages against the given (installed) packages
+pub fn check_dependencies(packages: &[Package]) -> Vec<DependencyIssue> {
+    let lookup = packages_by_id(packages);
+    let mut issues = Vec::new();
+    for p in packages {
+        for d in p.dependencies.iter().flatten() {
+            for problem in check_dependency(d, lookup.get(&d.id.to_lowercase())) {
+                issues.push(DependencyIssue {
+                    package_id: p.id.clone(),
+                    package_version: p.version.clone(),
+                    dependency_id: d.id.clone(),
+                    dependency_range: d.version.clone(),
+                    problem,
+                });
+            }
+        }
+    }
+    let graph = DependencyGraph::from_packages(packages);
+    let edge_range = |from: usize, to: usize| {
+        graph
+            .edges
+            .iter()
+            .find(|e| e.from == from && e.to == to)
+            .map(|e| e.range.clone())
+            .unwrap_or_default()
+    };
+    for i in graph.self_dependencies() {
+        let node = &graph.nodes[i];
+        issues.push(DependencyIssue {
+            package_id: node.id.clone(),
+            package_version: node.version.clone().unwrap_or_default(),
+            dependency_id: node.id.clone(),
+            dependency_range: edge_range(i, i),
+            problem: DependencyProblem::SelfDependency,
+        });
+    }
+    for cycle in graph.cycles() {
+        let (from, to) = (cycle[0], cycle[1]);
+        issues.push(DependencyIssue {
+            package_id: graph.nodes[from].id.clone(),
+            package_version: graph.nodes[from].version.clone().unwrap_or_default(),
+            dependency_id: graph.nodes[to].id.clone(),
+            dependency_range: edge_range(from, to),
+            problem: DependencyProblem::Cycle {
+                path: cycle.iter().map(|i| graph.nodes[*i].id.clone()).collect(),
+            },
+        });
+    }
+    issues.sort_by_key(|i| (i.package_id.to_lowercase(), i.dependency_id.to_lowercase()));
+    issues
+}
+
+fn get_cycle_text(path: &[String]) -> String {
+    let mut ids = path.to_vec();
+    ids.extend(path.first().cloned());
+    ids.join(" -> ")
+}
+
+fn get_dependency_issue_text(issue: &DependencyIssue, limitoutput: bool) -> String {
+    let (kind, detail) = match &issue.problem {
+        DependencyProblem::Missing => ("missing", String::new()),
+        DependencyProblem::OutOfRange { installed_version } => {
+            ("out-of-range", installed_version.clone())
+        }
+        DependencyProblem::CaseMismatch { installed_id } => ("case-mismatch", installed_id.clone()),
+        DependencyProblem::InvalidRange => ("invalid-range", String::new()),
+        DependencyProblem::SelfDependency => ("self-dependency", String::new()),
+        DependencyProblem::Cycle { path } => ("cycle", get_cycle_text(path)),
+    };
+    match limitoutput {
+        true => format!(
+            "{}|{}|{}|{}|{}|{}",
+            issue.package_id,
+            issue.package_version,
+            issue.dependency_id,
+            issue.dependency_range,
+            kind,
+            detail
+        ),
+        false => {
+            let message = match &issue.problem {
+                DependencyProblem::Missing => "not installed".to_string(),
+                DependencyProblem::OutOfRange { installed_version } => format!(
+                    "installed version {} does not satisfy '{}'",
+                    installed_version, issue.dependency_range
+                ),
+                DependencyProblem::CaseMismatch { installed_id } => {
+                    format!("installed as '{}' (id case differs)", installed_id)
+                }
+                DependencyProblem::InvalidRange => {
+                    format!("invalid version range '{}'", issue.dependency_range)
+                }
+                DependencyProblem::SelfDependency => "package depends on itself".to_string(),
+                DependencyProblem::Cycle { path } => {
+                    format!("dependency cycle {}", get_cycle_text(path))
+                }
+            };
+            format!(
+                "{} {} -> {}: {}",
+                issue.package_id, issue.package_version, issue.dependency_id, message
+            )
+        }
+    }
+}
+
+/// returns the number of issues found + the report
+pub fn get_dependency_check_text(
+    home: &ChocoHome,
+    limitoutput: bool,
+) -> Result<(usize, String), RocoError> {
+    let packages = get_local_packages(home)?;
+    let issues = check_dependencies(&packages);
+    let mut lines: Vec<String> = issues
+        .iter()
+        .map(|i| get_dependency_issue_text(i, limitoutput))
+        .collect();
+    if !limitoutput {
+        lines.push(match issues.len() {
+            0 => format!(
+                "all dependencies of {} packages are satisfied.",
+                packages.len()
+            ),
+            n => format!("{} dependency problems found.", n),
+        });
+    }
+    Ok((issues.len(), lines.join("\r\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::pkg;
+
+    #[test]
+    fn check_dependencies_test() {
+        let packages = vec![
+            pkg(
+                "app",
+                "1.0",
+                &[
+                    ("lib.a", "[1.0,2.0)"),
+                    ("Lib.B", "1.0"),
+                    ("lib.missing", ""),
+                    ("lib.c", "[1.0"),
+                ],
+            ),
+            pkg("lib.a", "2.0", &[]),
+            pkg("lib.b", "1.2", &[]),
+            pkg("lib.c", "1.0", &[]),
+        ];
+        let issues = check_dependencies(&packages);
+        let problems: Vec<(&str, &DependencyProblem)> = issues
+            .iter()
+            .map(|i| (i.dependency_id.as_str(), &i.problem))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (
+                    "lib.a",
+                    &DependencyProblem::OutOfRange {
+                        installed_version: "2.0".to_string()
+                    }
+                ),
+                (
+                    "Lib.B",
+                    &DependencyProblem::CaseMismatch {
+                        installed_id: "lib.b".to_string()
+                    }
+                ),
+                ("lib.c", &DependencyProblem::InvalidRange),
+                ("lib.missing", &DependencyProblem::Missing),
+            ]
+        );
+        assert_eq!(
+            get_dependency_issue_text(&issues[0], false),
+            "app 1.0 -> lib.a: installed version 2.0 does not satisfy '[1.0,2.0)'"
+        );
+        assert_eq!(
+            get_dependency_issue_text(&issues[0], true),
+            "app|1.0|lib.a|[1.0,2.0)|out-of-range|2.0"
+        );
+        assert_eq!(
+            get_dependency_issue_text(&issues[3], true),
+            "app|1.0|lib.missing||missing|"
+        );
+
+        assert!(check_dependencies(&packages[1..]).is_empty());
+    }
+
+    #[test]
+    fn check_dependencies_cycles_test() {
+        let packages = vec![
+            pkg("a", "1.0", &[("b", "1.0")]),
+            pkg("b", "1.0", &[("a", "1.0"), ("b", "")]),
+        ];
+        let issues = check_dependencies(&packages);
+        let texts: Vec<String> = issues
+            .iter()
+            .map(|i| get_dependency_issue_text(i, false))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                "a 1.0 -> b: dependency cycle a -> b -> a",
+                "b 1.0 -> b: package depends on itself",
+            ]
+        );
+        assert_eq!(
+            get_dependency_issue_text(&issues[0], true),
+            "a|1.0|b|1.0|cycle|a -> b -> a"
+        );
+    }
+
+    #[test]
+    fn get_dependency_check_text_test() {
+        // test/fake_choco_home: 'chocolatey' is installed as 'Chocolatey'
+        let (count, text) =
+            get_dependency_check_text(&ChocoHome::from_env().unwrap(), true).unwrap();
+        assert!(count > 0);
+        assert!(text
+            .split("\r\n")
+            .any(|l| l == "adobeair|0.0.42|chocolatey|0.10|case-mismatch|Chocolatey"));
+    }
+}
@@ -0,0 +1,284 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::roco::local::get_local_packages;
+use crate::roco::package_files::{get_package_files, package_dir, resolve_recorded_path};
+use crate::roco::{ChocoHome, Package, RocoError};
+
+// disk usage and install time of local packages (`roco list --details`)
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageDetails {
+    pub id: String,
+    pub version: String,
+    pub pinned: bool,
+    /// bytes used by lib/<id>
+    pub size: u64,
+    /// number of files in lib/<id>
+    pub file_count: usize,
+    /// bytes used by the files recorded in .chocolatey/<id>.<version>/.files (if available)
+    pub tracked_size: Option<u64>,
+    /// last install/upgrade (latest modification of the package and package info directories)
+    pub installed: Option<SystemTime>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn get_package_details_single(home: &ChocoHome, pkg: &Package) -> PackageDetails {
+    let dir = package_dir(home, &pkg.id).unwrap_or_else(|| home.lib_dir().join(&pkg.id));
+    let (size, file_count) = walkdir::WalkDir::new(&dir)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .fold((0, 0), |(size, count), e| {
+            (size + e.metadata().map(|m| m.len()).unwrap_or(0), count + 1)
+        });
+    // a broken .files is reported by `roco verify`, here it's just not tracked
+    let tracked_size = match get_package_files(home, &pkg.id, &pkg.version) {
+        Ok(Some(files)) => Some(
+            files
+                .iter()
+                .filter_map(|f| {
+                    std::fs::metadata(resolve_recorded_path(&f.path, &pkg.id, &dir)).ok()
+                })
+                .map(|m| m.len())
+                .sum(),
+        ),
+        _ => None,
+    };
+    let installed = [
+        modified(&dir),
+        modified(&dir.join(format!("{}.nupkg", pkg.id))),
+        modified(&home.package_info_dir(&pkg.id, &pkg.version)),
+    ]
+    .iter()
+    .flatten()
+    .max()
+    .copied();
+
+    PackageDetails {
+        id: pkg.id.clone(),
+        version: pkg.version.clone(),
+        pinned: pkg.pinned,
+        size,
+        file_count,
+        tracked_size,
+        installed,
+    }
+}
+
+/// details of the given packages, the package directories are scanned in parallel
+pub fn get_package_details(home: &ChocoHome, packages: &[Package]) -> Vec<PackageDetails> {
+    let num_threads = num_cpus::get().max(1);
+    let chunk_size = packages.len().div_ceil(num_threads).max(1);
+    std::thread::scope(|s| {
+        let tasks: Vec<_> = packages
+            .chunks(chunk_size)
+            .map(|chunk| {
+                s.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|p| get_package_details_single(home, p))
+                        .collect::<Vec<PackageDetails>>()
+                })
+            })
+            .collect();
+        tasks
+            .into_iter()
+            .flat_map(|t| t.join().expect("package details thread panicked"))
+            .collect()
+    })
+}
+
+/// e.g. '12.3 MB'
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} B", bytes),
+        _ => format!("{:.1} {}", size, UNITS[unit]),
+    }
+}
+
+/// ISO 8601 UTC timestamp, e.g. '2024-01-31T12:34:56Z'
+pub fn format_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let (days, secs_of_day) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+    // civil date from days since epoch (http://howardhinnant.github.io/date_algorithms.html)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+fn get_package_details_line(d: &PackageDetails, limitoutput: bool) -> String {
+    let installed = d.installed.map(format_timestamp).unwrap_or_default();
+    match limitoutput {
+        true => format!(
+            "{}|{}|{}|{}|{}|{}|{}",
+            d.id,
+            d.version,
+            d.size,
+            d.file_count,
+            d.tracked_size.map(|s| s.to_string()).unwrap_or_default(),
+            installed,
+            d.pinned
+        ),
+        false => {
+            let mut line = format!(
+                "{} {} | {} in {} files",
+                d.id,
+                d.version,
+                format_size(d.size),
+                d.file_count
+            );
+            if let Some(tracked_size) = d.tracked_size {
+                line.push_str(&format!(" ({} tracked)", format_size(tracked_size)));
+            }
+            if !installed.is_empty() {
+                line.push_str(&format!(" | installed {}", installed));
+            }
+            if d.pinned {
+                line.push_str(" | pinned");
+            }
+            line
+        }
+    }
+}
+
+fn get_filtered_package_details(
+    home: &ChocoHome,
+    filter: &str,
+) -> Result<Vec<PackageDetails>, RocoError> {
+    let filter = filter.to_lowercase();
+    let mut packages = get_local_packages(home)?;
+    packages.retain(|p| filter == "all" || p.id.to_lowercase().contains(&filter));
+    Ok(get_package_details(home, &packages))
+}
+
+/// `id version | size in n files (tracked size) | installed time | pinned`,
+/// limitoutput: `id|version|size|files|tracked size|installed|pinned` (sizes in bytes)
+pub fn get_package_details_text(
+    home: &ChocoHome,
+    filter: &str,
+    limitoutput: bool,
+) -> Result<String, RocoError> {
+    let details = get_filtered_package_details(home, filter)?;
+    let mut lines: Vec<String> = details
+        .iter()
+        .map(|d| get_package_details_line(d, limitoutput))
+        .collect();
+    if !limitoutput {
+        lines.push(format!(
+            "{} packages installed, {} on disk.",
+            details.len(),
+            format_size(details.iter().map(|d| d.size).sum())
+        ));
+    }
+    Ok(lines.join("\r\n"))
+}
+
+pub fn get_package_details_json(home: &ChocoHome, filter: &str) -> Result<String, RocoError> {
+    let details: serde_json::Value = get_filtered_package_details(home, filter)?
+        .iter()
+        .map(|d| {
+            serde_json::json!({
+                "id": d.id,
+                "version": d.version,
+                "pinned": d.pinned,
+                "size": d.size,
+                "fileCount": d.file_count,
+                "trackedSize": d.tracked_size,
+                "installed": d.installed.map(format_timestamp),
+            })
+        })
+        .collect();
+    Ok(format!("{:#}", details))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::TestDir;
+    use std::time::Duration;
+
+    #[test]
+    fn format_test() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GB");
+
+        let at = |secs: u64| format_timestamp(UNIX_EPOCH + Duration::from_secs(secs));
+        assert_eq!(at(0), "1970-01-01T00:00:00Z");
+        assert_eq!(at(951782400), "2000-02-29T00:00:00Z");
+        assert_eq!(at(1706704496), "2024-01-31T12:34:56Z");
+    }
+
+    #[test]
+    fn get_package_details_test() {
+        let root = TestDir::new("details");
+        root.add_package("Foo", "foo", "1.0");
+        root.add_package("bar", "Bar", "2.0");
+        root.write("lib/Foo/tools/data.bin", vec![0u8; 2048]);
+        root.write(
+            ".chocolatey/foo.1.0/.files",
+            "<fileSnapshot><files><file path=\"C:\\choco\\lib\\foo\\tools\\data.bin\" /></files></fileSnapshot>",
+        );
+        root.write(".chocolatey/foo.1.0/.pin", "");
+
+        let home = root.home();
+        let mut details = get_package_details(&home, &get_local_packages(&home).unwrap());
+        details.sort_by_key(|d| d.id.clone());
+        assert_eq!(details.len(), 2);
+        assert_eq!((details[0].id.as_str(), details[0].file_count), ("Bar", 1));
+        assert_eq!(details[0].tracked_size, None);
+        assert_eq!(details[1].file_count, 2);
+        assert_eq!(details[1].size, 2048 + 74);
+        assert_eq!(details[1].tracked_size, Some(2048));
+        assert!(details[1].pinned);
+        assert!(details[1].installed.is_some());
+
+        let text = get_package_details_text(&home, "FOO", true).unwrap();
+        let columns: Vec<&str> = text.split('|').collect();
+        assert_eq!(columns.len(), 7);
+        assert_eq!(columns[..5], ["foo", "1.0", "2122", "2", "2048"]);
+        assert!(columns[5].ends_with('Z'));
+        assert_eq!(columns[6], "true");
+        let text = get_package_details_text(&home, "all", false).unwrap();
+        assert!(text.contains("foo 1.0 | 2.1 KB in 2 files (2.0 KB tracked) | installed "));
+        assert!(text.contains(" | pinned"));
+        assert!(text.ends_with("\r\n2 packages installed, 2.1 KB on disk."));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&get_package_details_json(&home, "bar").unwrap()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 1);
+        assert_eq!(json[0]["id"], "Bar");
+        assert_eq!(json[0]["trackedSize"], serde_json::Value::Null);
+        assert_eq!(json[0]["pinned"], false);
+    }
+}
@@ -0,0 +1,287 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::roco::export::{get_export_entries, parse_snapshot_text, ExportEntry, ExportOptions};
+use crate::roco::{version, ChocoHome, RocoError};
+use crate::{println_verbose, version_comparison};
+
+// compares two package sets: the live installation, another Chocolatey root
+// or a snapshot written by `roco export` (packages.config, JSON, TOML)
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackageChange {
+    Added { version: Option<String> },
+    Removed { version: Option<String> },
+    Upgraded { from: String, to: String },
+    Downgraded { from: String, to: String },
+    PinChanged { pinned: bool },
+}
+
+impl PackageChange {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PackageChange::Added { .. } => "added",
+            PackageChange::Removed { .. } => "removed",
+            PackageChange::Upgraded { .. } => "upgraded",
+            PackageChange::Downgraded { .. } => "downgraded",
+            PackageChange::PinChanged { pinned: true } => "pinned",
+            PackageChange::PinChanged { pinned: false } => "unpinned",
+        }
+    }
+
+    /// version in the first and second package set (if known)
+    pub fn versions(&self) -> (Option<&str>, Option<&str>) {
+        match self {
+            PackageChange::Added { version } => (None, version.as_deref()),
+            PackageChange::Removed { version } => (version.as_deref(), None),
+            PackageChange::Upgraded { from, to } | PackageChange::Downgraded { from, to } => {
+                (Some(from), Some(to))
+            }
+            PackageChange::PinChanged { .. } => (None, None),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageDifference {
+    pub id: String,
+    pub change: PackageChange,
+}
+
+/// loads a package set: `local` (the given installation), a Chocolatey root directory
+/// or a snapshot file
+pub fn load_package_set(home: &ChocoHome, spec: &str) -> Result<Vec<ExportEntry>, RocoError> {
+    let options = ExportOptions {
+        include_versions: true,
+        ..Default::default()
+    };
+    if spec == "local" {
+        return get_export_entries(home, &options);
+    }
+    let path = Path::new(spec);
+    if path.is_dir() {
+        println_verbose(&format!("read packages of Chocolatey root '{}'", spec));
+        return get_export_entries(&ChocoHome::from_dir(path)?, &options);
+    }
+    println_verbose(&format!("parse package snapshot '{}'", spec));
+    parse_snapshot_text(spec, &std::fs::read_to_string(path)?)
+}
+
+/// differences from package set `a` to `b`, sorted by id;
+/// versions are only compared if both sets record them
+pub fn diff_package_sets(a: &[ExportEntry], b: &[ExportEntry]) -> Vec<PackageDifference> {
+    let index = |set: &[ExportEntry]| -> HashMap<String, ExportEntry> {
+        set.iter()
+            .map(|e| (e.id.to_lowercase(), e.clone()))
+            .collect()
+    };
+    let (a, b) = (index(a), index(b));
+    let mut diffs = Vec::new();
+    for (key, ea) in &a {
+        let eb = match b.get(key) {
+            Some(eb) => eb,
+            None => {
+                diffs.push(PackageDifference {
+                    id: ea.id.clone(),
+                    change: PackageChange::Removed {
+                        version: ea.version.clone(),
+                    },
+                });
+                continue;
+            }
+        };
+        if let (Some(va), Some(vb)) = (&ea.version, &eb.version) {
+            let (from, to) = (va.clone(), vb.clone());
+            match version::compare(va, vb, version_comparison()) {
+                Ordering::Less => diffs.push(PackageDifference {
+                    id: eb.id.clone(),
+                    change: PackageChange::Upgraded { from, to },
+                }),
+                Ordering::Greater => diffs.push(PackageDifference {
+                    id: eb.id.clone(),
+                    change: PackageChange::Downgraded { from, to },
+                }),
+                Ordering::Equal => (),
+            }
+        }
+        if ea.pinned != eb.pinned {
+            diffs.push(PackageDifference {
+                id: eb.id.clone(),
+                change: PackageChange::PinChanged { pinned: eb.pinned },
+            });
+        }
+    }
+    for (key, eb) in &b {
+        if !a.contains_key(key) {
+            diffs.push(PackageDifference {
+                id: eb.id.clone(),
+                change: PackageChange::Added {
+                    version: eb.version.clone(),
+                },
+            });
+        }
+    }
+    // upgrade/downgrade before pin change of the same package
+    diffs.sort_by_key(|d| {
+        (
+            d.id.to_lowercase(),
+            matches!(d.change, PackageChange::PinChanged { .. }),
+        )
+    });
+    diffs
+}
+
+pub fn get_package_differences(
+    home: &ChocoHome,
+    a: &str,
+    b: &str,
+) -> Result<Vec<PackageDifference>, RocoError> {
+    let set_a = load_package_set(home, a)?;
+    let set_b = load_package_set(home, b)?;
+    Ok(diff_package_sets(&set_a, &set_b))
+}
+
+fn get_difference_line(d: &PackageDifference, limitoutput: bool) -> String {
+    let (from, to) = d.change.versions();
+    if limitoutput {
+        return format!(
+            "{}|{}|{}|{}",
+            d.id,
+            d.change.kind(),
+            from.unwrap_or_default(),
+            to.unwrap_or_default()
+        );
+    }
+    match (from, to) {
+        (Some(from), Some(to)) => format!("{} {} {} -> {}", d.change.kind(), d.id, from, to),
+        (Some(v), None) | (None, Some(v)) => format!("{} {} {}", d.change.kind(), d.id, v),
+        (None, None) => format!("{} {}", d.change.kind(), d.id),
+    }
+}
+
+/// `kind id [version(s)]`, limitoutput: `id|kind|version a|version b`,
+/// returns the number of differences + text
+pub fn get_diff_text(
+    home: &ChocoHome,
+    a: &str,
+    b: &str,
+    limitoutput: bool,
+) -> Result<(usize, String), RocoError> {
+    let diffs = get_package_differences(home, a, b)?;
+    let mut lines: Vec<String> = diffs
+        .iter()
+        .map(|d| get_difference_line(d, limitoutput))
+        .collect();
+    if !limitoutput {
+        lines.push(match diffs.len() {
+            0 => "no differences found.".to_string(),
+            n => format!("{} differences found.", n),
+        });
+    }
+    Ok((diffs.len(), lines.join("\r\n")))
+}
+
+pub fn get_diff_json(home: &ChocoHome, a: &str, b: &str) -> Result<(usize, String), RocoError> {
+    let diffs = get_package_differences(home, a, b)?;
+    let json: serde_json::Value = diffs
+        .iter()
+        .map(|d| {
+            let (from, to) = d.change.versions();
+            serde_json::json!({
+                "id": d.id,
+                "change": d.change.kind(),
+                "from": from,
+                "to": to,
+            })
+        })
+        .collect();
+    Ok((diffs.len(), format!("{:#}", json)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::TestDir;
+
+    fn entry(id: &str, version: Option<&str>, pinned: bool) -> ExportEntry {
+        ExportEntry {
+            id: id.to_string(),
+            version: version.map(|v| v.to_string()),
+            pinned,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn diff_package_sets_test() {
+        let a = vec![
+            entry("firefox", Some("84.0.1"), false),
+            entry("jre8", Some("8.0.351"), false),
+            entry("vlc", Some("3.0.18"), true),
+            entry("git", Some("2.40.0"), false),
+            entry("zoom", Some("5.13"), false),
+            entry("7zip", None, false),
+        ];
+        let b = vec![
+            entry("Firefox", Some("85.0"), true),
+            entry("vlc", Some("3.0.18.0"), false),
+            entry("git", Some("2.39.1"), false),
+            entry("nodejs", Some("18.0.0"), false),
+            entry("zoom", Some("5.13"), false),
+            entry("7zip", Some("23.1"), false),
+        ];
+        let diffs = diff_package_sets(&a, &b);
+        assert_eq!(
+            diffs
+                .iter()
+                .map(|d| format!("{} {}", d.change.kind(), d.id))
+                .collect::<Vec<String>>(),
+            [
+                "upgraded Firefox",
+                "pinned Firefox",
+                "downgraded git",
+                "removed jre8",
+                "added nodejs",
+                "unpinned vlc",
+            ]
+        );
+        assert_eq!(diffs[0].change.versions(), (Some("84.0.1"), Some("85.0")));
+        assert!(diff_package_sets(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn get_diff_text_test() {
+        let root = TestDir::new("diff");
+        let snapshot = root.write(
+            "packages.config",
+            "<packages><package id=\"vlc\" version=\"3.0.17\" /><package id=\"foo\" /></packages>",
+        );
+        let home = ChocoHome::from_env().unwrap();
+        let snapshot = snapshot.to_string_lossy().to_string();
+
+        let (n, text) = get_diff_text(&home, &snapshot, "local", true).unwrap();
+        assert!(n > 2);
+        assert!(text.contains("vlc|upgraded|3.0.17|3.0.18"));
+        assert!(text.contains("foo|removed||"));
+        assert!(text.contains("firefox|added||84.0.1"));
+        let (_, text) = get_diff_text(&home, "local", &snapshot, false).unwrap();
+        assert!(text.contains("downgraded vlc 3.0.18 -> 3.0.17"));
+        assert!(text.ends_with(&format!("\r\n{} differences found.", n)));
+
+        let (n, text) =
+            get_diff_text(&home, "local", home.root().to_str().unwrap(), false).unwrap();
+        assert_eq!((n, text.as_str()), (0, "no differences found."));
+
+        let (_, json) = get_diff_json(&home, &snapshot, "local").unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let foo = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|d| d["id"] == "foo")
+            .unwrap();
+        assert_eq!(foo["change"], "removed");
+        assert_eq!(foo["from"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::println_verbose;
+use crate::roco::local::get_local_packages;
+use crate::roco::{ChocoHome, NuGetVersion, Package, RocoError, VersionRange};
+
+// desired state of the local installation, declared in a JSON manifest:
+// {"packages": [{"id": "firefox", "range": "[84.0,85.0)", "pin": true}, {"id": "adobeair", "absent": true}]}
+// (a JSON snapshot written by `roco export` is a valid baseline, too)
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineRule {
+    pub id: String,
+    /// exact version that has to be installed
+    pub version: Option<NuGetVersion>,
+    /// version range the installed version has to satisfy
+    pub range: Option<VersionRange>,
+    /// required pin state
+    pub pin: Option<bool>,
+    /// the package must not be installed
+    pub absent: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BaselineViolation {
+    NotInstalled,
+    VersionMismatch { installed: String },
+    OutOfRange { installed: String },
+    PinMismatch { pinned: bool },
+    Installed { version: String },
+}
+
+impl BaselineViolation {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BaselineViolation::NotInstalled => "not-installed",
+            BaselineViolation::VersionMismatch { .. } => "version-mismatch",
+            BaselineViolation::OutOfRange { .. } => "out-of-range",
+            BaselineViolation::PinMismatch { .. } => "pin-mismatch",
+            BaselineViolation::Installed { .. } => "not-absent",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineDrift {
+    pub rule: BaselineRule,
+    pub violation: BaselineViolation,
+}
+
+impl BaselineDrift {
+    /// what the rule expects, e.g. '[84.0,85.0)', 'pinned', 'absent'
+    pub fn expected(&self) -> String {
+        match &self.violation {
+            BaselineViolation::VersionMismatch { .. } => self
+                .rule
+                .version
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            BaselineViolation::OutOfRange { .. } => self
+                .rule
+                .range
+                .as_ref()
+                .map(|r| r.to_string())
+                .unwrap_or_default(),
+            BaselineViolation::PinMismatch { pinned: true } => "unpinned".to_string(),
+            BaselineViolation::PinMismatch { pinned: false } => "pinned".to_string(),
+            BaselineViolation::NotInstalled => "installed".to_string(),
+            BaselineViolation::Installed { .. } => "absent".to_string(),
+        }
+    }
+
+    /// current state, e.g. the installed version
+    pub fn actual(&self) -> String {
+        match &self.violation {
+            BaselineViolation::NotInstalled => "not installed".to_string(),
+            BaselineViolation::VersionMismatch { installed }
+            | BaselineViolation::OutOfRange { installed }
+            | BaselineViolation::Installed { version: installed } => installed.clone(),
+            BaselineViolation::PinMismatch { pinned: true } => "pinned".to_string(),
+            BaselineViolation::PinMismatch { pinned: false } => "unpinned".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for BaselineDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.violation {
+            BaselineViolation::NotInstalled => write!(f, "not installed"),
+            BaselineViolation::VersionMismatch { installed } => write!(
+                f,
+                "version {} installed, expected {}",
+                installed,
+                self.expected()
+            ),
+            BaselineViolation::OutOfRange { installed } => write!(
+                f,
+                "version {} installed, expected range {}",
+                installed,
+                self.expected()
+            ),
+            BaselineViolation::PinMismatch { pinned: true } => {
+                write!(f, "pinned, expected unpinned")
+            }
+            BaselineViolation::PinMismatch { pinned: false } => write!(f, "not pinned"),
+            BaselineViolation::Installed { version } => {
+                write!(f, "version {} installed, expected absent", version)
+            }
+        }
+    }
+}
+
+pub fn parse_baseline_text(origin: &str, text: &str) -> Result<Vec<BaselineRule>, RocoError> {
+    let json: serde_json::Value = serde_json::from_str(text.trim_start_matches('\u{feff}'))
+        .map_err(|e| RocoError::baseline_parse(origin, e))?;
+    let packages = json
+        .get("packages")
+        .and_then(|p| p.as_array())
+        .ok_or_else(|| RocoError::baseline_parse(origin, "expected a 'packages' array"))?;
+    packages
+        .iter()
+        .map(|p| {
+            let string = |key: &str| p.get(key).and_then(|v| v.as_str());
+            let id = string("id")
+                .filter(|id| !id.is_empty())
+                .ok_or_else(|| RocoError::baseline_parse(origin, "package without 'id'"))?;
+            let error = |message: String| {
+                RocoError::baseline_parse(origin, format!("package '{}': {}", id, message))
+            };
+            let rule = BaselineRule {
+                id: id.to_string(),
+                version: string("version")
+                    .map(|v| v.parse::<NuGetVersion>())
+                    .transpose()
+                    .map_err(|e| error(e.to_string()))?,
+                range: string("range")
+                    .map(|r| r.parse::<VersionRange>())
+                    .transpose()
+                    .map_err(|e| error(e.to_string()))?,
+                pin: p.get("pin").and_then(|v| v.as_bool()),
+                absent: p.get("absent").and_then(|v| v.as_bool()) == Some(true),
+            };
+            let constrained = rule.version.is_some() || rule.range.is_some() || rule.pin.is_some();
+            match rule.absent && constrained {
+                true => Err(error(
+                    "'absent' can't be combined with 'version', 'range' or 'pin'".to_string(),
+                )),
+                false => Ok(rule),
+            }
+        })
+        .collect()
+}
+
+/// violations of the given rules, in order of the rules
+pub fn check_baseline(packages: &[Package], rules: &[BaselineRule]) -> Vec<BaselineDrift> {
+    let installed: HashMap<String, &Package> =
+        packages.iter().map(|p| (p.id.to_lowercase(), p)).collect();
+    let mut drifts = Vec::new();
+    for rule in rules {
+        let mut violations = Vec::new();
+        match (installed.get(&rule.id.to_lowercase()), rule.absent) {
+            (None, true) => (),
+            (None, false) => violations.push(BaselineViolation::NotInstalled),
+            (Some(p), true) => violations.push(BaselineViolation::Installed {
+                version: p.version.clone(),
+            }),
+            (Some(p), false) => {
+                let version = p.version.parse::<NuGetVersion>().ok();
+                if rule
+                    .version
+                    .as_ref()
+                    .is_some_and(|expected| version.as_ref() != Some(expected))
+                {
+                    violations.push(BaselineViolation::VersionMismatch {
+                        installed: p.version.clone(),
+                    });
+                }
+                if rule
+                    .range
+                    .as_ref()
+                    .is_some_and(|range| version.as_ref().is_none_or(|v| !range.satisfies(v)))
+                {
+                    violations.push(BaselineViolation::OutOfRange {
+                        installed: p.version.clone(),
+                    });
+                }
+                if rule.pin.is_some_and(|pin| pin != p.pinned) {
+                    violations.push(BaselineViolation::PinMismatch { pinned: p.pinned });
+                }
+            }
+        }
+        drifts.extend(violations.into_iter().map(|violation| BaselineDrift {
+            rule: rule.clone(),
+            violation,
+        }));
+    }
+    drifts
+}
+
+/// `id: violation` lines, limitoutput: `id|kind|expected|actual`,
+/// returns the number of violations + text
+pub fn get_drift_text(
+    home: &ChocoHome,
+    baseline: &str,
+    limitoutput: bool,
+) -> Result<(usize, String), RocoError> {
+    println_verbose(&format!("parse baseline '{}'", baseline));
+    let rules = parse_baseline_text(baseline, &std::fs::read_to_string(baseline)?)?;
+    let drifts = check_baseline(&get_local_packages(home)?, &rules);
+    let mut lines: Vec<String> = drifts
+        .iter()
+        .map(|d| match limitoutput {
+            true => format!(
+                "{}|{}|{}|{}",
+                d.rule.id,
+                d.violation.kind(),
+                d.expected(),
+                d.actual()
+            ),
+            false => format!("{}: {}", d.rule.id, d),
+        })
+        .collect();
+    if !limitoutput {
+        let mut violated: Vec<&str> = drifts.iter().map(|d| d.rule.id.as_str()).collect();
+        violated.dedup();
+        lines.push(match drifts.len() {
+            0 => format!("baseline satisfied ({} rules checked).", rules.len()),
+            n => format!(
+                "{} violations of {} of {} rules found.",
+                n,
+                violated.len(),
+                rules.len()
+            ),
+        });
+    }
+    Ok((drifts.len(), lines.join("\r\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::{pkg, TestDir};
+
+    #[test]
+    fn check_baseline_test() {
+        let rules = parse_baseline_text(
+            "baseline.json",
+            r#"{"packages": [
+                {"id": "Firefox", "version": "84.0.1.0", "pin": true},
+                {"id": "vlc", "range": "[3.0,3.0.18)", "pin": true},
+                {"id": "git"},
+                {"id": "adobeair", "absent": true},
+                {"id": "flash", "absent": true}
+            ]}"#,
+        )
+        .unwrap();
+        let packages = vec![
+            Package {
+                pinned: true,
+                ..pkg("firefox", "84.0.1", &[])
+            },
+            pkg("vlc", "3.0.18", &[]),
+            pkg("adobeair", "1.0", &[]),
+        ];
+        let drifts = check_baseline(&packages, &rules);
+        assert_eq!(
+            drifts
+                .iter()
+                .map(|d| format!(
+                    "{}|{}|{}|{}",
+                    d.rule.id,
+                    d.violation.kind(),
+                    d.expected(),
+                    d.actual()
+                ))
+                .collect::<Vec<String>>(),
+            [
+                "vlc|out-of-range|[3.0.0, 3.0.18)|3.0.18",
+                "vlc|pin-mismatch|pinned|unpinned",
+                "git|not-installed|installed|not installed",
+                "adobeair|not-absent|absent|1.0",
+            ]
+        );
+        assert_eq!(
+            drifts[0].to_string(),
+            "version 3.0.18 installed, expected range [3.0.0, 3.0.18)"
+        );
+
+        for broken in [
+            "[]",
+            r#"{"packages": [{"version": "1.0"}]}"#,
+            r#"{"packages": [{"id": "a", "range": "[1.0"}]}"#,
+            r#"{"packages": [{"id": "a", "absent": true, "pin": false}]}"#,
+        ] {
+            assert!(matches!(
+                parse_baseline_text("broken", broken),
+                Err(RocoError::BaselineParse { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn get_drift_text_test() {
+        let root = TestDir::new("drift");
+        let baseline = root.write(
+            "baseline.json",
+            r#"{"packages": [{"id": "vlc", "version": "3.0.18"}, {"id": "firefox", "range": "[85.0,)"}]}"#,
+        );
+        let home = ChocoHome::from_env().unwrap();
+        let baseline = baseline.to_string_lossy().to_string();
+        let (n, text) = get_drift_text(&home, &baseline, false).unwrap();
+        assert_eq!(n, 1);
+        assert_eq!(
+            text,
+            "firefox: version 84.0.1 installed, expected range [85.0.0, )\r\n1 violations of 1 of 2 rules found."
+        );
+        root.write(
+            "baseline.json",
+            r#"{"packages": [{"id": "vlc", "version": "3.0.18"}]}"#,
+        );
+        let (n, text) = get_drift_text(&home, &baseline, true).unwrap();
+        assert_eq!((n, text.as_str()), (0, ""));
+    }
+}
@@ -0,0 +1,267 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum RocoError {
+    /// the Chocolatey install directory could not be determined
+    ChocolateyDirMissing(String),
+    /// config/chocolatey.config does not exist
+    ConfigMissing(String),
+    /// chocolatey.config exists, but could not be parsed
+    ConfigParse {
+        path: String,
+        message: String,
+    },
+    /// a nuspec (from lib/ or a nupkg) could not be parsed
+    NuspecParse {
+        path: String,
+        message: String,
+    },
+    /// a package information file (.chocolatey/<id>.<version>/.files) could not be parsed
+    PackageInfoParse {
+        path: String,
+        message: String,
+    },
+    /// a package snapshot (packages.config, exported JSON/TOML) could not be parsed
+    SnapshotParse {
+        path: String,
+        message: String,
+    },
+    /// a baseline manifest (`roco drift`) could not be parsed
+    BaselineParse {
+        path: String,
+        message: String,
+    },
+    /// a package requested by the caller is not installed
+    PackageNotFound(String),
+    /// a package requested by the caller is not available on the given source
+    RemotePackageNotFound {
+        source: String,
+        id: String,
+    },
+    /// a source requested by the caller is not configured
+    SourceNotFound(String),
+    /// a config key requested by the caller is not present in chocolatey.config
+    ConfigKeyNotFound(String),
+    /// an encrypted value of chocolatey.config could not be decrypted
+    CredentialDecrypt {
+        name: String,
+        message: String,
+    },
+    /// a value could not be encrypted for chocolatey.config
+    CredentialEncrypt(String),
+    /// installed packages have unsatisfied dependencies (number of problems found)
+    DependencyProblems(usize),
+    /// installed files are missing or were modified (number of problems found)
+    IntegrityProblems(usize),
+    /// the installation health scan found errors (number of errors found)
+    HealthProblems(usize),
+    /// two package sets differ (number of differences found)
+    PackageDifferences(usize),
+    /// the installation does not match the baseline (number of violations found)
+    BaselineViolations(usize),
+    /// a choco invocation failed (its exit code, if it wasn't terminated by a signal)
+    ChocoFailed(Option<i32>),
+    /// a feed could not be reached at all (DNS, connection refused, TLS, ...)
+    FeedUnreachable {
+        feed: String,
+        message: String,
+    },
+    /// a feed refused our credentials (HTTP 401/403)
+    FeedAuth {
+        feed: String,
+        status: u16,
+    },
+    /// a feed responded with an unexpected HTTP status
+    HttpStatus {
+        url: String,
+        status: u16,
+    },
+    /// a feed responded, but the response could not be understood
+    FeedResponse {
+        feed: String,
+        message: String,
+    },
+    /// the feed type could not be determined
+    UnknownFeedType(String),
+    /// none of the queried feeds answered (the error of each feed)
+    AllFeedsFailed(Vec<RocoError>),
+    /// a version string could not be parsed
+    InvalidVersion(String),
+    Io(std::io::Error),
+}
+
+impl RocoError {
+    pub(crate) fn nuspec_parse(path: &str, message: impl fmt::Display) -> RocoError {
+        RocoError::NuspecParse {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn package_info_parse(path: &str, message: impl fmt::Display) -> RocoError {
+        RocoError::PackageInfoParse {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn snapshot_parse(path: &str, message: impl fmt::Display) -> RocoError {
+        RocoError::SnapshotParse {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn baseline_parse(path: &str, message: impl fmt::Display) -> RocoError {
+        RocoError::BaselineParse {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn config_parse(path: &str, message: impl fmt::Display) -> RocoError {
+        RocoError::ConfigParse {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn feed_response(feed: &str, message: impl fmt::Display) -> RocoError {
+        RocoError::FeedResponse {
+            feed: feed.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn from_reqwest(feed: &str, e: reqwest::Error) -> RocoError {
+        match e.status() {
+            Some(s) if s == 401 || s == 403 => RocoError::FeedAuth {
+                feed: feed.to_string(),
+                status: s.as_u16(),
+            },
+            Some(s) => RocoError::HttpStatus {
+                url: e.url().map(|u| u.to_string()).unwrap_or_default(),
+                status: s.as_u16(),
+            },
+            None => RocoError::FeedUnreachable {
+                feed: feed.to_string(),
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for RocoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RocoError::ChocolateyDirMissing(m) => {
+                write!(f, "failed to get choco dir: {}", m)
+            }
+            RocoError::ConfigMissing(p) => write!(f, "chocolatey config '{}' not found", p),
+            RocoError::ConfigParse { path, message } => {
+                write!(f, "failed to parse config '{}': {}", path, message)
+            }
+            RocoError::NuspecParse { path, message } => {
+                write!(f, "failed to parse nuspec '{}': {}", path, message)
+            }
+            RocoError::PackageInfoParse { path, message } => {
+                write!(
+                    f,
+                    "failed to parse package information '{}': {}",
+                    path, message
+                )
+            }
+            RocoError::SnapshotParse { path, message } => {
+                write!(
+                    f,
+                    "failed to parse package snapshot '{}': {}",
+                    path, message
+                )
+            }
+            RocoError::BaselineParse { path, message } => {
+                write!(f, "failed to parse baseline '{}': {}", path, message)
+            }
+            RocoError::PackageNotFound(id) => {
+                write!(f, "package '{}' not present in local packages.", id)
+            }
+            RocoError::RemotePackageNotFound { source, id } => {
+                write!(f, "package '{}' not found on source '{}'.", id, source)
+            }
+            RocoError::SourceNotFound(id) => {
+                write!(f, "source '{}' not present in chocolatey config.", id)
+            }
+            RocoError::ConfigKeyNotFound(key) => {
+                write!(f, "no configuration value by the name '{}'", key)
+            }
+            RocoError::CredentialDecrypt { name, message } => {
+                write!(
+                    f,
+                    "failed to decrypt credential for '{}': {}",
+                    name, message
+                )
+            }
+            RocoError::CredentialEncrypt(message) => {
+                write!(f, "failed to encrypt credential: {}", message)
+            }
+            RocoError::DependencyProblems(n) => write!(f, "{} dependency problems found", n),
+            RocoError::IntegrityProblems(n) => write!(f, "{} file integrity problems found", n),
+            RocoError::HealthProblems(n) => write!(f, "{} installation health errors found", n),
+            RocoError::PackageDifferences(n) => write!(f, "{} package differences found", n),
+            RocoError::BaselineViolations(n) => write!(f, "{} baseline violations found", n),
+            RocoError::ChocoFailed(Some(code)) => write!(f, "choco exited with code {}", code),
+            RocoError::ChocoFailed(None) => write!(f, "choco was terminated"),
+            RocoError::FeedUnreachable { feed, message } => {
+                write!(f, "feed '{}' is not reachable: {}", feed, message)
+            }
+            RocoError::FeedAuth { feed, status } => {
+                write!(f, "feed '{}' rejected credentials (HTTP {})", feed, status)
+            }
+            RocoError::HttpStatus { url, status } => write!(f, "HTTP {} for '{}'", status, url),
+            RocoError::FeedResponse { feed, message } => {
+                write!(f, "invalid response from feed '{}': {}", feed, message)
+            }
+            RocoError::UnknownFeedType(feed) => write!(
+                f,
+                "cannot communicate with unknown feed type, please check feed '{}'",
+                feed
+            ),
+            RocoError::AllFeedsFailed(errors) => {
+                let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(
+                    f,
+                    "none of the sources could be queried: {}",
+                    errors.join("; ")
+                )
+            }
+            RocoError::InvalidVersion(v) => write!(f, "invalid version string '{}'", v),
+            RocoError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for RocoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RocoError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for RocoError {
+    fn from(e: std::io::Error) -> Self {
+        RocoError::Io(e)
+    }
+}
+
+impl From<glob::GlobError> for RocoError {
+    fn from(e: glob::GlobError) -> Self {
+        RocoError::Io(e.into_error())
+    }
+}
+
+impl From<glob::PatternError> for RocoError {
+    fn from(e: glob::PatternError) -> Self {
+        RocoError::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
+    }
+}
@@ -0,0 +1,428 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::roco::local::{get_local_packages, DependentsIndex};
+use crate::roco::{ChocoHome, RocoError};
+
+// snapshot of the installed packages, to rebuild a machine via `choco install packages.config`
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    /// Chocolatey packages.config
+    PackagesConfig,
+    Json,
+    Toml,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "config" => Ok(ExportFormat::PackagesConfig),
+            "json" => Ok(ExportFormat::Json),
+            "toml" => Ok(ExportFormat::Toml),
+            _ => Err(format!("unknown export format '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    /// record the installed versions (otherwise the latest version is installed on import)
+    pub include_versions: bool,
+    /// only record packages no other installed package depends on
+    pub exclude_dependencies: bool,
+    /// source to install all packages from
+    pub source: Option<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            format: ExportFormat::PackagesConfig,
+            include_versions: false,
+            exclude_dependencies: false,
+            source: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportEntry {
+    pub id: String,
+    pub version: Option<String>,
+    pub pinned: bool,
+    pub source: Option<String>,
+}
+
+pub fn get_export_entries(
+    home: &ChocoHome,
+    options: &ExportOptions,
+) -> Result<Vec<ExportEntry>, RocoError> {
+    let packages = get_local_packages(home)?;
+    let dependents = DependentsIndex::new(&packages);
+    let mut entries: Vec<ExportEntry> = packages
+        .iter()
+        .filter(|p| !options.exclude_dependencies || dependents.direct(&p.id).is_empty())
+        .map(|p| ExportEntry {
+            id: p.id.clone(),
+            version: match options.include_versions {
+                true => Some(p.version.clone()),
+                false => None,
+            },
+            pinned: p.pinned,
+            source: options.source.clone(),
+        })
+        .collect();
+    entries.sort_by_key(|e| e.id.to_lowercase());
+    Ok(entries)
+}
+
+fn xml_attribute(name: &str, value: &str) -> String {
+    format!(" {}=\"{}\"", name, quick_xml::escape::escape(value))
+}
+
+fn get_packages_config_text(entries: &[ExportEntry]) -> String {
+    let mut lines = vec![
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>".to_string(),
+        "<packages>".to_string(),
+    ];
+    for e in entries {
+        let mut line = format!("  <package{}", xml_attribute("id", &e.id));
+        if let Some(version) = &e.version {
+            line.push_str(&xml_attribute("version", version));
+        }
+        if let Some(source) = &e.source {
+            line.push_str(&xml_attribute("source", source));
+        }
+        if e.pinned {
+            line.push_str(&xml_attribute("pinPackage", "true"));
+        }
+        line.push_str(" />");
+        lines.push(line);
+    }
+    lines.push("</packages>".to_string());
+    lines.push(String::new());
+    lines.join("\r\n")
+}
+
+fn get_json_text(entries: &[ExportEntry]) -> String {
+    let packages: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|e| {
+            let mut package = serde_json::json!({ "id": e.id });
+            if let Some(version) = &e.version {
+                package["version"] = serde_json::json!(version);
+            }
+            if let Some(source) = &e.source {
+                package["source"] = serde_json::json!(source);
+            }
+            if e.pinned {
+                package["pin"] = serde_json::json!(true);
+            }
+            package
+        })
+        .collect();
+    format!("{:#}", serde_json::json!({ "packages": packages }))
+}
+
+fn toml_string(value: &str) -> String {
+    let mut res = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            c if c.is_control() => res.push_str(&format!("\\u{:04X}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+fn get_toml_text(entries: &[ExportEntry]) -> String {
+    let mut res = String::new();
+    for e in entries {
+        if !res.is_empty() {
+            res.push('\n');
+        }
+        res.push_str("[[package]]\n");
+        res.push_str(&format!("id = {}\n", toml_string(&e.id)));
+        if let Some(version) = &e.version {
+            res.push_str(&format!("version = {}\n", toml_string(version)));
+        }
+        if let Some(source) = &e.source {
+            res.push_str(&format!("source = {}\n", toml_string(source)));
+        }
+        if e.pinned {
+            res.push_str("pin = true\n");
+        }
+    }
+    res
+}
+
+fn parse_packages_config(origin: &str, text: &str) -> Result<Vec<ExportEntry>, RocoError> {
+    let mut entries = Vec::new();
+    let mut reader = Reader::from_str(text);
+    reader.trim_text(true);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if e.local_name().as_ref() == b"package" =>
+            {
+                let mut entry = ExportEntry {
+                    id: String::new(),
+                    version: None,
+                    pinned: false,
+                    source: None,
+                };
+                for a in e.attributes().flatten() {
+                    let value = a
+                        .unescape_value()
+                        .map_err(|e| RocoError::snapshot_parse(origin, e))?
+                        .to_string();
+                    match a.key.as_ref() {
+                        b"id" => entry.id = value,
+                        b"version" => entry.version = Some(value).filter(|v| !v.is_empty()),
+                        b"source" => entry.source = Some(value).filter(|s| !s.is_empty()),
+                        b"pinPackage" => entry.pinned = value.eq_ignore_ascii_case("true"),
+                        _ => (),
+                    }
+                }
+                if entry.id.is_empty() {
+                    return Err(RocoError::snapshot_parse(
+                        origin,
+                        "package without 'id' attribute",
+                    ));
+                }
+                entries.push(entry);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(RocoError::snapshot_parse(origin, e)),
+            _ => (),
+        }
+    }
+    Ok(entries)
+}
+
+fn parse_json(origin: &str, text: &str) -> Result<Vec<ExportEntry>, RocoError> {
+    let json: serde_json::Value =
+        serde_json::from_str(text).map_err(|e| RocoError::snapshot_parse(origin, e))?;
+    // our own export ({"packages": [...]}) or `roco list --details --json` ([...])
+    let packages = json
+        .get("packages")
+        .unwrap_or(&json)
+        .as_array()
+        .ok_or_else(|| RocoError::snapshot_parse(origin, "expected an array of packages"))?;
+    packages
+        .iter()
+        .map(|p| {
+            let string = |key: &str| p.get(key).and_then(|v| v.as_str()).map(|v| v.to_string());
+            Ok(ExportEntry {
+                id: string("id")
+                    .filter(|id| !id.is_empty())
+                    .ok_or_else(|| RocoError::snapshot_parse(origin, "package without 'id'"))?,
+                version: string("version"),
+                pinned: ["pin", "pinned"]
+                    .iter()
+                    .any(|key| p.get(key).and_then(|v| v.as_bool()) == Some(true)),
+                source: string("source"),
+            })
+        })
+        .collect()
+}
+
+fn parse_toml_string(value: &str) -> Option<String> {
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut res = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    res.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                }
+                'n' => res.push('\n'),
+                't' => res.push('\t'),
+                'r' => res.push('\r'),
+                c => res.push(c),
+            },
+            c => res.push(c),
+        }
+    }
+    Some(res)
+}
+
+/// the subset of TOML written by `get_toml_text` (`[[package]]` tables with string/bool values)
+fn parse_toml(origin: &str, text: &str) -> Result<Vec<ExportEntry>, RocoError> {
+    let mut entries: Vec<ExportEntry> = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let error = |message: &str| {
+            RocoError::snapshot_parse(origin, format!("line {}: {}", n + 1, message))
+        };
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line == "[[package]]" {
+            entries.push(ExportEntry {
+                id: String::new(),
+                version: None,
+                pinned: false,
+                source: None,
+            });
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .ok_or_else(|| error("expected 'key = value'"))?;
+        let entry = entries
+            .last_mut()
+            .ok_or_else(|| error("value outside of a [[package]] table"))?;
+        match key {
+            "pin" => entry.pinned = value == "true",
+            "id" | "version" | "source" => {
+                let value = parse_toml_string(value).ok_or_else(|| error("invalid string"))?;
+                match key {
+                    "id" => entry.id = value,
+                    "version" => entry.version = Some(value),
+                    _ => entry.source = Some(value),
+                }
+            }
+            _ => (),
+        }
+    }
+    match entries.iter().any(|e| e.id.is_empty()) {
+        true => Err(RocoError::snapshot_parse(origin, "package without 'id'")),
+        false => Ok(entries),
+    }
+}
+
+/// parses a package snapshot (packages.config, exported JSON or TOML),
+/// the format is detected by the content
+pub fn parse_snapshot_text(origin: &str, text: &str) -> Result<Vec<ExportEntry>, RocoError> {
+    let text = text.trim_start_matches('\u{feff}');
+    match text.trim_start().chars().next() {
+        Some('<') => parse_packages_config(origin, text),
+        Some('{') | Some('[') if !text.trim_start().starts_with("[[") => parse_json(origin, text),
+        _ => parse_toml(origin, text),
+    }
+}
+
+pub fn get_export_text(home: &ChocoHome, options: &ExportOptions) -> Result<String, RocoError> {
+    let entries = get_export_entries(home, options)?;
+    Ok(match options.format {
+        ExportFormat::PackagesConfig => get_packages_config_text(&entries),
+        ExportFormat::Json => get_json_text(&entries),
+        ExportFormat::Toml => get_toml_text(&entries),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<ExportEntry> {
+        vec![
+            ExportEntry {
+                id: "firefox".to_string(),
+                version: Some("84.0.1".to_string()),
+                pinned: true,
+                source: Some("https://repo/?a=1&b=\"2\"".to_string()),
+            },
+            ExportEntry {
+                id: "vlc".to_string(),
+                version: None,
+                pinned: false,
+                source: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn export_format_test() {
+        assert_eq!(
+            get_packages_config_text(&entries()),
+            [
+                "<?xml version=\"1.0\" encoding=\"utf-8\"?>",
+                "<packages>",
+                "  <package id=\"firefox\" version=\"84.0.1\" source=\"https://repo/?a=1&amp;b=&quot;2&quot;\" pinPackage=\"true\" />",
+                "  <package id=\"vlc\" />",
+                "</packages>",
+                "",
+            ]
+            .join("\r\n")
+        );
+        assert_eq!(
+            get_toml_text(&entries()),
+            concat!(
+                "[[package]]\n",
+                "id = \"firefox\"\n",
+                "version = \"84.0.1\"\n",
+                "source = \"https://repo/?a=1&b=\\\"2\\\"\"\n",
+                "pin = true\n",
+                "\n",
+                "[[package]]\n",
+                "id = \"vlc\"\n",
+            )
+        );
+        let json: serde_json::Value = serde_json::from_str(&get_json_text(&entries())).unwrap();
+        assert_eq!(json["packages"][0]["pin"], true);
+        assert_eq!(json["packages"][1], serde_json::json!({ "id": "vlc" }));
+    }
+
+    #[test]
+    fn parse_snapshot_test() {
+        for text in [
+            get_packages_config_text(&entries()),
+            get_json_text(&entries()),
+            get_toml_text(&entries()),
+        ] {
+            assert_eq!(parse_snapshot_text("snapshot", &text).unwrap(), entries());
+        }
+        let details = "[{\"id\": \"vlc\", \"version\": \"3.0.18\", \"pinned\": true}]";
+        let parsed = parse_snapshot_text("details.json", details).unwrap();
+        assert_eq!(parsed[0].version.as_deref(), Some("3.0.18"));
+        assert!(parsed[0].pinned);
+
+        for broken in [
+            "<packages><package version=\"1.0\" /></packages>",
+            "{\"packages\": {}}",
+            "id = \"vlc\"",
+            "[[package]]\nversion = \"1.0\"",
+        ] {
+            assert!(matches!(
+                parse_snapshot_text("broken", broken),
+                Err(RocoError::SnapshotParse { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn get_export_entries_test() {
+        let home = ChocoHome::from_env().unwrap();
+        let all = get_export_entries(&home, &ExportOptions::default()).unwrap();
+        assert!(all
+            .iter()
+            .all(|e| e.version.is_none() && e.source.is_none()));
+        assert!(all.iter().any(|e| e.id == "jre8"));
+
+        let options = ExportOptions {
+            include_versions: true,
+            exclude_dependencies: true,
+            source: Some("internal".to_string()),
+            ..Default::default()
+        };
+        let top_level = get_export_entries(&home, &options).unwrap();
+        // jre8 is installed as dependency of firefox and adobeair
+        assert!(!top_level.iter().any(|e| e.id == "jre8"));
+        let firefox = top_level.iter().find(|e| e.id == "firefox").unwrap();
+        assert_eq!(firefox.version.as_deref(), Some("84.0.1"));
+        assert_eq!(firefox.source.as_deref(), Some("internal"));
+        assert!(top_level.len() < all.len());
+    }
+}
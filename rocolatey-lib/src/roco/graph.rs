@@ -0,0 +1,527 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::roco::local::get_local_packages;
+use crate::roco::{ChocoHome, Package, RocoError};
+
+// dependency graph of installed packages, every package is a single node (shared dependencies
+// are not duplicated), dependencies that are not installed are added as 'missing' nodes
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    /// package id as installed (or as referenced, for missing packages)
+    pub id: String,
+    /// installed version, None for missing packages
+    pub version: Option<String>,
+}
+
+impl GraphNode {
+    pub fn is_missing(&self) -> bool {
+        self.version.is_none()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEdge {
+    /// index into `DependencyGraph::nodes`
+    pub from: usize,
+    pub to: usize,
+    /// version range as declared in the nuspec (may be empty)
+    pub range: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+    Json,
+}
+
+impl std::str::FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" => Ok(GraphFormat::Dot),
+            "mermaid" => Ok(GraphFormat::Mermaid),
+            "json" => Ok(GraphFormat::Json),
+            _ => Err(format!("unknown graph format '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyGraph {
+    /// sorted by (lower case) id
+    pub nodes: Vec<GraphNode>,
+    /// sorted by (from, to)
+    pub edges: Vec<GraphEdge>,
+}
+
+/// id suffixes of packages that are meant to be installed as a dependency only
+const DEPENDENCY_ONLY_SUFFIXES: [&str; 3] = [".install", ".portable", ".extension"];
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+impl DependencyGraph {
+    pub fn from_packages(packages: &[Package]) -> DependencyGraph {
+        let mut nodes: Vec<GraphNode> = packages
+            .iter()
+            .map(|p| GraphNode {
+                id: p.id.clone(),
+                version: Some(p.version.clone()),
+            })
+            .collect();
+        let mut known: BTreeSet<String> = nodes.iter().map(|n| n.id.to_lowercase()).collect();
+        for d in packages
+            .iter()
+            .flat_map(|p| p.dependencies.iter().flatten())
+        {
+            if known.insert(d.id.to_lowercase()) {
+                nodes.push(GraphNode {
+                    id: d.id.clone(),
+                    version: None,
+                });
+            }
+        }
+        nodes.sort_by_key(|n| n.id.to_lowercase());
+        nodes.dedup_by_key(|n| n.id.to_lowercase());
+
+        let index: HashMap<String, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.to_lowercase(), i))
+            .collect();
+        let mut edges: Vec<GraphEdge> = Vec::new();
+        for p in packages {
+            let from = index[&p.id.to_lowercase()];
+            for d in p.dependencies.iter().flatten() {
+                let to = index[&d.id.to_lowercase()];
+                if !edges.iter().any(|e| e.from == from && e.to == to) {
+                    edges.push(GraphEdge {
+                        from,
+                        to,
+                        range: d.version.clone(),
+                    });
+                }
+            }
+        }
+        edges.sort_by_key(|e| (e.from, e.to));
+
+        DependencyGraph { nodes, edges }
+    }
+
+    pub fn node_index(&self, id: &str) -> Option<usize> {
+        let id = id.to_lowercase();
+        self.nodes.iter().position(|n| n.id.to_lowercase() == id)
+    }
+
+    /// nodes depending on themselves
+    pub fn self_dependencies(&self) -> Vec<usize> {
+        let mut res: Vec<usize> = self
+            .edges
+            .iter()
+            .filter(|e| e.from == e.to)
+            .map(|e| e.from)
+            .collect();
+        res.dedup();
+        res
+    }
+
+    fn successors(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges
+            .iter()
+            .filter(move |e| e.from == node && e.to != node)
+            .map(|e| e.to)
+    }
+
+    /// strongly connected components with more than one node (tarjan)
+    fn cyclic_components(&self) -> Vec<Vec<usize>> {
+        struct State {
+            index: Vec<Option<usize>>,
+            lowlink: Vec<usize>,
+            on_stack: Vec<bool>,
+            stack: Vec<usize>,
+            next: usize,
+            components: Vec<Vec<usize>>,
+        }
+
+        fn visit(g: &DependencyGraph, v: usize, s: &mut State) {
+            s.index[v] = Some(s.next);
+            s.lowlink[v] = s.next;
+            s.next += 1;
+            s.stack.push(v);
+            s.on_stack[v] = true;
+            for w in g.successors(v) {
+                match s.index[w] {
+                    None => {
+                        visit(g, w, s);
+                        s.lowlink[v] = s.lowlink[v].min(s.lowlink[w]);
+                    }
+                    Some(i) if s.on_stack[w] => s.lowlink[v] = s.lowlink[v].min(i),
+                    _ => {}
+                }
+            }
+            if Some(s.lowlink[v]) == s.index[v] {
+                let mut component = Vec::new();
+                while let Some(w) = s.stack.pop() {
+                    s.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    component.sort();
+                    s.components.push(component);
+                }
+            }
+        }
+
+        let n = self.nodes.len();
+        let mut state = State {
+            index: vec![None; n],
+            lowlink: vec![0; n],
+            on_stack: vec![false; n],
+            stack: Vec::new(),
+            next: 0,
+            components: Vec::new(),
+        };
+        for v in 0..n {
+            if state.index[v].is_none() {
+                visit(self, v, &mut state);
+            }
+        }
+        state.components.sort();
+        state.components
+    }
+
+    /// one cycle per group of packages depending on each other (self dependencies excluded),
+    /// as path starting at the lowest node, e.g. [a, b, c] for a -> b -> c -> a
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        self.cyclic_components()
+            .iter()
+            .filter_map(|component| {
+                // shortest path back to the start within the component (bfs)
+                let start = component[0];
+                let mut previous: HashMap<usize, usize> = HashMap::new();
+                let mut queue = std::collections::VecDeque::from([start]);
+                while let Some(v) = queue.pop_front() {
+                    for w in self.successors(v) {
+                        if w == start {
+                            let mut path = vec![v];
+                            while let Some(p) = previous.get(path.last().unwrap()) {
+                                path.push(*p);
+                            }
+                            path.reverse();
+                            return Some(path);
+                        }
+                        if component.binary_search(&w).is_ok() && !previous.contains_key(&w) {
+                            previous.insert(w, v);
+                            queue.push_back(w);
+                        }
+                    }
+                }
+                None
+            })
+            .collect()
+    }
+
+    /// installed dependency-only packages (.install, .portable, .extension) nothing depends on
+    /// (anymore), typically left behind after uninstalling the package that pulled them in
+    pub fn orphans(&self) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|i| {
+                let id = self.nodes[*i].id.to_lowercase();
+                !self.nodes[*i].is_missing()
+                    && DEPENDENCY_ONLY_SUFFIXES.iter().any(|s| id.ends_with(s))
+                    && !self.edges.iter().any(|e| e.to == *i && e.from != *i)
+            })
+            .collect()
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut res = String::from("digraph dependencies {\n");
+        for n in &self.nodes {
+            let id = dot_escape(&n.id);
+            match &n.version {
+                Some(v) => res.push_str(&format!(
+                    "  \"{}\" [label=\"{}\\n{}\"];\n",
+                    id,
+                    id,
+                    dot_escape(v)
+                )),
+                None => res.push_str(&format!(
+                    "  \"{}\" [label=\"{}\\n(missing)\", style=dashed, color=red];\n",
+                    id, id
+                )),
+            }
+        }
+        for e in &self.edges {
+            let from = dot_escape(&self.nodes[e.from].id);
+            let to = dot_escape(&self.nodes[e.to].id);
+            match e.range.is_empty() {
+                true => res.push_str(&format!("  \"{}\" -> \"{}\";\n", from, to)),
+                false => res.push_str(&format!(
+                    "  \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                    from,
+                    to,
+                    dot_escape(&e.range)
+                )),
+            }
+        }
+        res.push_str("}\n");
+        res
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let mut res = String::from("graph LR\n");
+        for (i, n) in self.nodes.iter().enumerate() {
+            match &n.version {
+                Some(v) => res.push_str(&format!(
+                    "  n{}[\"{} {}\"]\n",
+                    i,
+                    mermaid_escape(&n.id),
+                    mermaid_escape(v)
+                )),
+                None => res.push_str(&format!(
+                    "  n{}[\"{} (missing)\"]:::missing\n",
+                    i,
+                    mermaid_escape(&n.id)
+                )),
+            }
+        }
+        for e in &self.edges {
+            match e.range.is_empty() {
+                true => res.push_str(&format!("  n{} --> n{}\n", e.from, e.to)),
+                false => res.push_str(&format!(
+                    "  n{} -->|\"{}\"| n{}\n",
+                    e.from,
+                    mermaid_escape(&e.range),
+                    e.to
+                )),
+            }
+        }
+        if self.nodes.iter().any(|n| n.is_missing()) {
+            res.push_str("  classDef missing stroke:#f00,stroke-dasharray:5 5\n");
+        }
+        res
+    }
+
+    pub fn to_json(&self) -> String {
+        let nodes: Vec<serde_json::Value> = self
+            .nodes
+            .iter()
+            .map(|n| {
+                serde_json::json!({
+                    "id": n.id,
+                    "version": n.version,
+                    "missing": n.is_missing(),
+                })
+            })
+            .collect();
+        let edges: Vec<serde_json::Value> = self
+            .edges
+            .iter()
+            .map(|e| {
+                serde_json::json!({
+                    "from": self.nodes[e.from].id,
+                    "to": self.nodes[e.to].id,
+                    "range": match e.range.is_empty() {
+                        true => None,
+                        false => Some(&e.range),
+                    },
+                })
+            })
+            .collect();
+        let graph = serde_json::json!({ "nodes": nodes, "edges": edges });
+        format!("{:#}", graph)
+    }
+
+    pub fn format(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+            GraphFormat::Json => self.to_json(),
+        }
+    }
+}
+
+pub fn get_dependency_graph(home: &ChocoHome) -> Result<DependencyGraph, RocoError> {
+    Ok(DependencyGraph::from_packages(&get_local_packages(home)?))
+}
+
+pub fn get_orphans_text(home: &ChocoHome, limitoutput: bool) -> Result<String, RocoError> {
+    let graph = get_dependency_graph(home)?;
+    let orphans = graph.orphans();
+    let mut lines: Vec<String> = orphans
+        .iter()
+        .map(|i| {
+            let n = &graph.nodes[*i];
+            let version = n.version.as_deref().unwrap_or_default();
+            match limitoutput {
+                true => format!("{}|{}", n.id, version),
+                false => format!("{} {}", n.id, version),
+            }
+        })
+        .collect();
+    if !limitoutput {
+        lines.push(format!("{} orphaned packages found.", orphans.len()));
+    }
+    Ok(lines.join("\r\n"))
+}
+
+pub fn get_dependency_graph_text(
+    home: &ChocoHome,
+    format: GraphFormat,
+) -> Result<String, RocoError> {
+    Ok(get_dependency_graph(home)?.format(format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::pkg;
+
+    fn graph() -> DependencyGraph {
+        DependencyGraph::from_packages(&[
+            pkg("app", "1.0", &[("Lib", "[1.0,2.0)"), ("gone", "")]),
+            pkg("tool", "2.0", &[("lib", ""), ("lib", "")]),
+            pkg("lib", "1.5", &[]),
+        ])
+    }
+
+    #[test]
+    fn dependency_graph_test() {
+        let g = graph();
+        let ids: Vec<&str> = g.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["app", "gone", "lib", "tool"]);
+        assert!(g.nodes[1].is_missing());
+        assert_eq!(
+            g.edges,
+            vec![
+                GraphEdge {
+                    from: 0,
+                    to: 1,
+                    range: String::new()
+                },
+                GraphEdge {
+                    from: 0,
+                    to: 2,
+                    range: "[1.0,2.0)".to_string()
+                },
+                GraphEdge {
+                    from: 3,
+                    to: 2,
+                    range: String::new()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn dependency_graph_format_test() {
+        let g = graph();
+        assert_eq!(
+            g.to_dot(),
+            "digraph dependencies {
+  \"app\" [label=\"app\\n1.0\"];
+  \"gone\" [label=\"gone\\n(missing)\", style=dashed, color=red];
+  \"lib\" [label=\"lib\\n1.5\"];
+  \"tool\" [label=\"tool\\n2.0\"];
+  \"app\" -> \"gone\";
+  \"app\" -> \"lib\" [label=\"[1.0,2.0)\"];
+  \"tool\" -> \"lib\";
+}
+"
+        );
+        assert_eq!(
+            g.to_mermaid(),
+            "graph LR
+  n0[\"app 1.0\"]
+  n1[\"gone (missing)\"]:::missing
+  n2[\"lib 1.5\"]
+  n3[\"tool 2.0\"]
+  n0 --> n1
+  n0 -->|\"[1.0,2.0)\"| n2
+  n3 --> n2
+  classDef missing stroke:#f00,stroke-dasharray:5 5
+"
+        );
+        let json: serde_json::Value = serde_json::from_str(&g.to_json()).unwrap();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 4);
+        assert_eq!(json["nodes"][1]["missing"], true);
+        assert_eq!(json["nodes"][1]["version"], serde_json::Value::Null);
+        assert_eq!(json["edges"][1]["from"], "app");
+        assert_eq!(json["edges"][1]["to"], "lib");
+        assert_eq!(json["edges"][1]["range"], "[1.0,2.0)");
+        assert_eq!(json["edges"][0]["range"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn cycles_test() {
+        let g = DependencyGraph::from_packages(&[
+            pkg("a", "1.0", &[("b", "")]),
+            pkg("b", "1.0", &[("c", ""), ("x", "")]),
+            pkg("c", "1.0", &[("a", ""), ("c", "")]),
+            pkg("x", "1.0", &[("y", "")]),
+            pkg("y", "1.0", &[("x", "")]),
+            pkg("z", "1.0", &[("a", "")]),
+        ]);
+        let ids = |path: &Vec<usize>| -> Vec<String> {
+            path.iter().map(|i| g.nodes[*i].id.clone()).collect()
+        };
+        let cycles: Vec<Vec<String>> = g.cycles().iter().map(ids).collect();
+        assert_eq!(cycles, vec![vec!["a", "b", "c"], vec!["x", "y"]]);
+        assert_eq!(g.self_dependencies(), vec![g.node_index("C").unwrap()]);
+        assert!(graph().cycles().is_empty());
+        assert!(graph().self_dependencies().is_empty());
+    }
+
+    #[test]
+    fn orphans_test() {
+        let g = DependencyGraph::from_packages(&[
+            pkg("app", "1.0", &[("app.install", ""), ("gone.portable", "")]),
+            pkg("app.install", "1.0", &[]),
+            pkg("old.install", "1.0", &[("old.install", "")]),
+            pkg("tool.Extension", "1.0", &[]),
+            pkg("lib", "1.0", &[]),
+        ]);
+        let orphans: Vec<&str> = g
+            .orphans()
+            .iter()
+            .map(|i| g.nodes[*i].id.as_str())
+            .collect();
+        assert_eq!(orphans, vec!["old.install", "tool.Extension"]);
+    }
+
+    #[test]
+    fn get_orphans_text_test() {
+        let home = ChocoHome::from_env().unwrap();
+        assert_eq!(
+            get_orphans_text(&home, true).unwrap(),
+            "demo.myenv.portable|0.0.1"
+        );
+        assert_eq!(
+            get_orphans_text(&home, false).unwrap(),
+            "demo.myenv.portable 0.0.1\r\n1 orphaned packages found."
+        );
+    }
+
+    #[test]
+    fn get_dependency_graph_test() {
+        let g = get_dependency_graph(&ChocoHome::from_env().unwrap()).unwrap();
+        assert!(g.nodes.iter().all(|n| !n.is_missing()));
+        // jre8 is a dependency of firefox and adobeair, but only a single node
+        let jre8 = g.nodes.iter().position(|n| n.id == "jre8").unwrap();
+        assert_eq!(g.edges.iter().filter(|e| e.to == jre8).count(), 2);
+        // referenced as 'chocolatey', the node uses the installed id
+        assert!(g.nodes.iter().any(|n| n.id == "Chocolatey"));
+        assert!(!g.nodes.iter().any(|n| n.id == "chocolatey"));
+    }
+}
@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+
+use crate::roco::{config, ChocolateyConfig, RocoError};
+
+/// root of a Chocolatey installation, every lookup of installed packages, pins and config
+/// is done relative to it (allows inspecting installations other than the running one)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChocoHome {
+    root: PathBuf,
+}
+
+impl ChocoHome {
+    pub fn new(root: impl Into<PathBuf>) -> ChocoHome {
+        ChocoHome { root: root.into() }
+    }
+
+    /// same as `new`, but fails if the directory does not exist
+    pub fn from_dir(root: impl Into<PathBuf>) -> Result<ChocoHome, RocoError> {
+        let root = root.into();
+        match root.is_dir() {
+            true => Ok(ChocoHome::new(root)),
+            false => Err(RocoError::ChocolateyDirMissing(format!(
+                "'{}' is not a directory",
+                root.to_string_lossy()
+            ))),
+        }
+    }
+
+    /// the installation referenced by env:ChocolateyInstall
+    pub fn from_env() -> Result<ChocoHome, RocoError> {
+        let key = "ChocolateyInstall";
+        std::env::var(key)
+            .map(ChocoHome::new)
+            .map_err(|e| RocoError::ChocolateyDirMissing(format!("env:{}: {}", key, e)))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn lib_dir(&self) -> PathBuf {
+        self.root.join("lib")
+    }
+
+    pub fn lib_bad_dir(&self) -> PathBuf {
+        self.root.join("lib-bad")
+    }
+
+    /// backups choco keeps while upgrading a package (left over if an upgrade was interrupted)
+    pub fn lib_bkp_dir(&self) -> PathBuf {
+        self.root.join("lib-bkp")
+    }
+
+    pub fn config_file(&self) -> PathBuf {
+        self.root.join("config").join("chocolatey.config")
+    }
+
+    /// per-package state maintained by choco (pins, remembered arguments, ...)
+    pub fn package_info_dir(&self, id: &str, version: &str) -> PathBuf {
+        self.root
+            .join(".chocolatey")
+            .join(format!("{}.{}", id, version))
+    }
+
+    /// files (+ checksums) recorded by choco when the package was installed
+    pub fn package_files_file(&self, id: &str, version: &str) -> PathBuf {
+        self.package_info_dir(id, version).join(".files")
+    }
+
+    /// marker file of a pinned package (excluded from upgrades)
+    pub fn pin_file(&self, id: &str, version: &str) -> PathBuf {
+        self.package_info_dir(id, version).join(".pin")
+    }
+
+    pub fn is_pinned(&self, id: &str, version: &str) -> bool {
+        self.pin_file(id, version).exists()
+    }
+
+    pub fn config(&self) -> Result<ChocolateyConfig, RocoError> {
+        config::get_config_from_file(&self.config_file())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    //NOTE: ChocolateyInstall, RocolateyTestRoot env-vars needs to be set in via Cargo [env]
+
+    #[test]
+    fn choco_home_test() {
+        let home = ChocoHome::from_env().unwrap();
+        assert_eq!(
+            home.root(),
+            Path::new(&std::env::var("ChocolateyInstall").unwrap())
+        );
+        assert!(home.lib_dir().is_dir());
+        assert!(home.config_file().is_file());
+        assert_eq!(home.config().unwrap().sources.len(), 4);
+
+        let tests_root = std::env::var("RocolateyTestRoot").unwrap();
+        assert!(ChocoHome::from_dir(Path::new(&tests_root).join("fake_choco_home")).is_ok());
+        assert!(matches!(
+            ChocoHome::from_dir(Path::new(&tests_root).join("does_not_exist")),
+            Err(RocoError::ChocolateyDirMissing(_))
+        ));
+    }
+}
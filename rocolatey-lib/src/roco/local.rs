@@ -1,63 +1,73 @@
-use quick_xml::events::Event;
-use quick_xml::Reader;
-
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use std::fs::File;
 use std::io::Read;
 use zip::ZipArchive;
 
 use crate::println_verbose;
-use crate::roco::{
-    get_choco_sources, get_chocolatey_dir, xml_attribs_to_map, Feed, NuspecTag, Package,
-};
+use crate::roco::{ChocoHome, Feed, Package, PackageMetadata, RocoError};
 
-pub fn get_local_packages() -> Result<Vec<Package>, Box<dyn std::error::Error>> {
+/// packages of all nuspecs matching the pattern; unparseable nuspecs are skipped
+/// (`roco doctor` reports them)
+fn get_packages_from_nuspecs(
+    home: &ChocoHome,
+    pattern: &std::path::Path,
+) -> Result<Vec<Package>, RocoError> {
     let mut pkgs: Vec<Package> = Vec::new();
-    let choco_dir = get_chocolatey_dir().unwrap_or_else(|_| String::from("."));
-    let mut pkg_dir = PathBuf::from(choco_dir);
-    pkg_dir.push("lib");
-    pkg_dir.push("*/*.nuspec");
-    for entry in glob::glob(&pkg_dir.to_string_lossy()).expect("Failed to read glob pattern") {
-        match entry {
-            Ok(path) => pkgs.push(get_package_from_nuspec(&path)),
-            Err(e) => println!("{:?}", e),
+    for entry in glob::glob(&pattern.to_string_lossy())? {
+        // unreadable directory entries are skipped like unparseable nuspecs
+        match entry
+            .map_err(RocoError::from)
+            .and_then(|path| get_package_from_nuspec(home, &path))
+        {
+            Ok(p) => pkgs.push(p),
+            Err(e) => println_verbose(&format!("skipping package: {}", e)),
         }
     }
     Ok(pkgs)
 }
 
-pub fn get_local_bad_packages() -> Result<Vec<Package>, Box<dyn std::error::Error>> {
-    let mut pkgs: Vec<Package> = Vec::new();
-    let choco_dir = get_chocolatey_dir().unwrap_or_else(|_| String::from("."));
-    let mut pkg_dir = PathBuf::from(choco_dir);
-    pkg_dir.push("lib-bad");
+pub fn get_local_packages(home: &ChocoHome) -> Result<Vec<Package>, RocoError> {
+    let mut pkg_dir = home.lib_dir();
+    pkg_dir.push("*/*.nuspec");
+    get_packages_from_nuspecs(home, &pkg_dir)
+}
+
+pub fn get_local_bad_packages(home: &ChocoHome) -> Result<Vec<Package>, RocoError> {
+    let mut pkg_dir = home.lib_bad_dir();
     // NOTE: using a '**' glob may be a bad idea
     // (recursive search may be slow + what if someone includes nuspec in a package?)
     pkg_dir.push("**/*.nuspec");
-    for entry in glob::glob(&pkg_dir.to_string_lossy()).expect("Failed to read glob pattern") {
-        match entry {
-            Ok(path) => pkgs.push(get_package_from_nuspec(&path)),
-            Err(e) => println!("{:?}", e),
-        }
+    get_packages_from_nuspecs(home, &pkg_dir)
+}
+
+/// true if the installed package matches the version range of the dependency
+/// (unparseable versions/ranges are not reported here)
+fn dependency_satisfied(dependency: &Package, installed: &Package) -> bool {
+    match (dependency.version_range(), installed.version.parse()) {
+        (Ok(range), Ok(version)) => range.satisfies(&version),
+        _ => true,
     }
-    Ok(pkgs)
 }
 
+/// `ancestors`: lower case ids of the packages on the path to `pkg` (excluding `pkg`),
+/// used to stop at dependency cycles
 fn get_dependency_tree_pkg_text(
     level: usize,
     pkg: &Package,
     packages: &HashMap<String, &Package>,
+    ancestors: &mut Vec<String>,
 ) -> String {
     let mut res = String::new();
 
     if level == 1 {
         res.push_str(&format!("{} ({})\r\n", pkg.id, pkg.version));
     }
+    ancestors.push(pkg.id.to_lowercase());
 
-    if pkg.dependencies.is_some() {
-        for (_i, p) in pkg.dependencies.as_ref().unwrap().iter().enumerate() {
+    if let Some(dependencies) = &pkg.dependencies {
+        for p in dependencies.iter() {
             let v = if p.version.is_empty() {
                 String::new()
             } else {
@@ -66,12 +76,28 @@ fn get_dependency_tree_pkg_text(
 
             res.push_str(&format!("{}-{} {}\r\n", " |".repeat(level), p.id, v));
 
-            let package = packages.get(&p.id.to_lowercase());
-            if package.is_some() {
+            let lowercase_id = p.id.to_lowercase();
+            if let Some(package) = packages.get(&lowercase_id) {
+                if !dependency_satisfied(p, package) {
+                    res.push_str(&format!(
+                        "ERROR: installed {} ({}) does not satisfy {}\r\n",
+                        package.id, package.version, p.version
+                    ));
+                }
+                if let Some(start) = ancestors.iter().position(|a| *a == lowercase_id) {
+                    let mut cycle = ancestors[start..].to_vec();
+                    cycle.push(lowercase_id);
+                    res.push_str(&format!(
+                        "ERROR: dependency cycle {}\r\n",
+                        cycle.join(" -> ")
+                    ));
+                    continue;
+                }
                 res.push_str(&get_dependency_tree_pkg_text(
                     level + 1,
-                    package.unwrap(),
+                    package,
                     packages,
+                    ancestors,
                 ));
             } else {
                 res.push_str(&format!(
@@ -81,59 +107,432 @@ fn get_dependency_tree_pkg_text(
             }
         }
     }
+    ancestors.pop();
 
     res
 }
 
-pub fn get_dependency_tree_text(filter: &str) -> String {
+pub fn get_dependency_tree_text(home: &ChocoHome, filter: &str) -> Result<String, RocoError> {
     let mut res = String::new();
 
-    let packages = get_local_packages().unwrap();
+    let packages = get_local_packages(home)?;
     let filter = filter.to_lowercase();
 
     let mut packages_lookup = HashMap::new();
-    for (_i, p) in packages.iter().enumerate() {
+    for p in packages.iter() {
         let lowercase_id = p.id.to_lowercase();
         packages_lookup.insert(lowercase_id, p);
     }
 
-    for (_i, p) in packages.iter().enumerate() {
-        if filter != "all" {
-            if !p.id.contains(&filter) {
-                continue;
+    for p in packages.iter() {
+        if filter != "all" && !p.id.contains(&filter) {
+            continue;
+        }
+        res.push_str(&get_dependency_tree_pkg_text(
+            1,
+            p,
+            &packages_lookup,
+            &mut Vec::new(),
+        ));
+    }
+
+    Ok(res)
+}
+
+/// reverse dependency index: which packages depend on a given package id (case-insensitive)
+pub struct DependentsIndex<'a> {
+    dependents: HashMap<String, Vec<&'a Package>>,
+}
+
+impl<'a> DependentsIndex<'a> {
+    pub fn new(packages: &'a [Package]) -> DependentsIndex<'a> {
+        let mut dependents: HashMap<String, Vec<&'a Package>> = HashMap::new();
+        for p in packages {
+            for d in p.dependencies.iter().flatten() {
+                let entry = dependents.entry(d.id.to_lowercase()).or_default();
+                // a package may list the same dependency more than once
+                if !entry.iter().any(|e| std::ptr::eq(*e, p)) {
+                    entry.push(p);
+                }
             }
         }
-        res.push_str(&get_dependency_tree_pkg_text(1, p, &packages_lookup));
+        DependentsIndex { dependents }
     }
 
-    res
+    /// packages directly depending on `id`
+    pub fn direct(&self, id: &str) -> Vec<&'a Package> {
+        self.dependents
+            .get(&id.to_lowercase())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// packages depending on `id`, directly or via other packages
+    pub fn transitive(&self, id: &str) -> Vec<&'a Package> {
+        let mut res: Vec<&'a Package> = Vec::new();
+        let mut visited = std::collections::HashSet::new();
+        visited.insert(id.to_lowercase());
+        let mut queue = vec![id.to_lowercase()];
+        while let Some(current) = queue.pop() {
+            for p in self.direct(&current) {
+                let lowercase_id = p.id.to_lowercase();
+                if visited.insert(lowercase_id.clone()) {
+                    res.push(p);
+                    queue.push(lowercase_id);
+                }
+            }
+        }
+        res
+    }
+}
+
+pub fn get_dependents(
+    home: &ChocoHome,
+    id: &str,
+    transitive: bool,
+) -> Result<Vec<Package>, RocoError> {
+    let packages = get_local_packages(home)?;
+    let index = DependentsIndex::new(&packages);
+    let mut dependents: Vec<Package> = match transitive {
+        true => index.transitive(id),
+        false => index.direct(id),
+    }
+    .into_iter()
+    .cloned()
+    .collect();
+    dependents.sort_by_key(|p| p.id.to_lowercase());
+    Ok(dependents)
+}
+
+pub fn get_dependents_text(
+    home: &ChocoHome,
+    id: &str,
+    transitive: bool,
+    limitoutput: bool,
+) -> Result<String, RocoError> {
+    let packages = get_dependents(home, id, transitive)?;
+    let num_packages = packages.len();
+    let mut res = String::new();
+    res.push_str(get_package_list_text("all", packages, limitoutput).as_ref());
+    if !limitoutput {
+        if num_packages > 0 {
+            res.push_str("\r\n");
+        }
+        res.push_str(&format!("{} packages depend on {}.", num_packages, id));
+    }
+    Ok(res)
 }
 
-pub fn get_local_packages_text(filter: &str, limitoutput: bool) -> String {
-    let packages = get_local_packages().unwrap();
+pub fn get_local_packages_text(
+    home: &ChocoHome,
+    filter: &str,
+    limitoutput: bool,
+) -> Result<String, RocoError> {
+    let packages = get_local_packages(home)?;
     let num_packages = packages.len();
     let mut res = String::new();
     res.push_str(get_package_list_text(filter, packages, limitoutput).as_ref());
     if !limitoutput {
         res.push_str(&format!("\r\n{} packages installed.", num_packages));
     }
-    res
+    Ok(res)
 }
 
-pub fn get_local_bad_packages_text(limitoutput: bool) -> String {
-    let packages = get_local_bad_packages().unwrap();
+pub fn get_local_bad_packages_text(
+    home: &ChocoHome,
+    limitoutput: bool,
+) -> Result<String, RocoError> {
+    let packages = get_local_bad_packages(home)?;
     let num_packages = packages.len();
     let mut res = String::new();
     res.push_str(get_package_list_text("all", packages, limitoutput).as_ref());
     if !limitoutput {
         res.push_str(&format!("\r\n{} packages in lib-bad.", num_packages));
     }
-    res
+    Ok(res)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthProblem {
+    /// lib/<dir> without a nuspec, choco does not consider it installed
+    MissingNuspec,
+    /// lib/<dir> with more than one nuspec (number of nuspecs)
+    MultipleNuspecs(usize),
+    /// the nuspec can't be parsed
+    InvalidNuspec { message: String },
+    /// the nuspec id differs from the lib folder name
+    IdMismatch { nuspec_id: String },
+    /// several lib folders contain a package with the same id (ignoring case)
+    DuplicateId { other: PathBuf },
+    /// lib-bkp/<dir> left over from an interrupted upgrade
+    BackupLeftover,
+    /// .chocolateyPending marker of an interrupted install
+    PendingInstall,
+    /// package in lib-bad (failed install)
+    FailedInstall,
+}
+
+impl HealthProblem {
+    /// short name, as used in limitoutput mode
+    pub fn kind(&self) -> &'static str {
+        match self {
+            HealthProblem::MissingNuspec => "missing-nuspec",
+            HealthProblem::MultipleNuspecs(_) => "multiple-nuspecs",
+            HealthProblem::InvalidNuspec { .. } => "invalid-nuspec",
+            HealthProblem::IdMismatch { .. } => "id-mismatch",
+            HealthProblem::DuplicateId { .. } => "duplicate-id",
+            HealthProblem::BackupLeftover => "backup-leftover",
+            HealthProblem::PendingInstall => "pending-install",
+            HealthProblem::FailedInstall => "failed-install",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthIssue {
+    pub severity: Severity,
+    /// package id (as found in the nuspec or the folder name)
+    pub package_id: String,
+    pub path: PathBuf,
+    pub problem: HealthProblem,
+}
+
+impl HealthIssue {
+    fn new(severity: Severity, package_id: &str, path: PathBuf, problem: HealthProblem) -> Self {
+        HealthIssue {
+            severity,
+            package_id: package_id.to_string(),
+            path,
+            problem,
+        }
+    }
+
+    /// suggested choco command to repair the installation
+    pub fn fix(&self) -> String {
+        match self.problem {
+            HealthProblem::BackupLeftover => format!("choco upgrade {} --force", self.package_id),
+            _ => format!("choco install {} --force", self.package_id),
+        }
+    }
+}
+
+fn sub_dirs(dir: &std::path::Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    dirs.sort();
+    dirs
+}
+
+fn dir_name(dir: &std::path::Path) -> String {
+    dir.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// scans lib/, lib-bkp/ and lib-bad/ for broken, duplicate and interrupted installs
+pub fn get_health_issues(home: &ChocoHome) -> Result<Vec<HealthIssue>, RocoError> {
+    let mut issues = Vec::new();
+    // lower case id -> (id, lib folder)
+    let mut installed: HashMap<String, (String, PathBuf)> = HashMap::new();
+
+    for dir in sub_dirs(&home.lib_dir()) {
+        let folder = dir_name(&dir);
+        let mut nuspecs: Vec<PathBuf> = std::fs::read_dir(&dir)?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "nuspec"))
+            .collect();
+        nuspecs.sort();
+
+        if dir.join(".chocolateyPending").exists() {
+            issues.push(HealthIssue::new(
+                Severity::Error,
+                &folder,
+                dir.join(".chocolateyPending"),
+                HealthProblem::PendingInstall,
+            ));
+        }
+        let nuspec = match nuspecs.len() {
+            0 => {
+                issues.push(HealthIssue::new(
+                    Severity::Error,
+                    &folder,
+                    dir.clone(),
+                    HealthProblem::MissingNuspec,
+                ));
+                continue;
+            }
+            1 => &nuspecs[0],
+            n => {
+                issues.push(HealthIssue::new(
+                    Severity::Warning,
+                    &folder,
+                    dir.clone(),
+                    HealthProblem::MultipleNuspecs(n),
+                ));
+                match nuspecs.iter().find(|p| {
+                    p.file_stem()
+                        .is_some_and(|s| s.to_string_lossy().eq_ignore_ascii_case(&folder))
+                }) {
+                    Some(nuspec) => nuspec,
+                    None => continue,
+                }
+            }
+        };
+
+        let path_str = nuspec.to_string_lossy().to_string();
+        let metadata = match PackageMetadata::from_nuspec_text(&path_str, &std::fs::read(nuspec)?) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                issues.push(HealthIssue::new(
+                    Severity::Error,
+                    &folder,
+                    nuspec.clone(),
+                    HealthProblem::InvalidNuspec {
+                        message: e.to_string(),
+                    },
+                ));
+                continue;
+            }
+        };
+        if !metadata.id.eq_ignore_ascii_case(&folder) {
+            issues.push(HealthIssue::new(
+                Severity::Warning,
+                &metadata.id,
+                dir.clone(),
+                HealthProblem::IdMismatch {
+                    nuspec_id: metadata.id.clone(),
+                },
+            ));
+        }
+        match installed.get(&metadata.id.to_lowercase()) {
+            Some((_, other)) => issues.push(HealthIssue::new(
+                Severity::Error,
+                &metadata.id,
+                dir.clone(),
+                HealthProblem::DuplicateId {
+                    other: other.clone(),
+                },
+            )),
+            None => {
+                installed.insert(metadata.id.to_lowercase(), (metadata.id.clone(), dir));
+            }
+        }
+    }
+
+    for dir in sub_dirs(&home.lib_bkp_dir()) {
+        let folder = dir_name(&dir);
+        let id = installed
+            .get(&folder.to_lowercase())
+            .map(|(id, _)| id.clone())
+            .unwrap_or(folder);
+        issues.push(HealthIssue::new(
+            Severity::Warning,
+            &id,
+            dir,
+            HealthProblem::BackupLeftover,
+        ));
+    }
+
+    for dir in sub_dirs(&home.lib_bad_dir()) {
+        let folder = dir_name(&dir);
+        issues.push(HealthIssue::new(
+            Severity::Error,
+            &folder,
+            dir,
+            HealthProblem::FailedInstall,
+        ));
+    }
+
+    issues.sort_by_key(|i| (i.severity, i.package_id.to_lowercase()));
+    Ok(issues)
+}
+
+fn get_health_issue_text(home: &ChocoHome, issue: &HealthIssue, limitoutput: bool) -> String {
+    let path = issue
+        .path
+        .strip_prefix(home.root())
+        .unwrap_or(&issue.path)
+        .to_string_lossy();
+    let message = match &issue.problem {
+        HealthProblem::MissingNuspec => "no nuspec found".to_string(),
+        HealthProblem::MultipleNuspecs(n) => format!("{} nuspecs found", n),
+        HealthProblem::InvalidNuspec { message } => message.clone(),
+        HealthProblem::IdMismatch { nuspec_id } => {
+            format!("nuspec id '{}' does not match the folder name", nuspec_id)
+        }
+        HealthProblem::DuplicateId { other } => format!(
+            "same package id as '{}'",
+            other
+                .strip_prefix(home.root())
+                .unwrap_or(other)
+                .to_string_lossy()
+        ),
+        HealthProblem::BackupLeftover => "backup of an interrupted upgrade".to_string(),
+        HealthProblem::PendingInstall => "install was interrupted".to_string(),
+        HealthProblem::FailedInstall => "install failed".to_string(),
+    };
+    let severity = match issue.severity {
+        Severity::Error => "ERROR",
+        Severity::Warning => "WARNING",
+    };
+    match limitoutput {
+        true => format!(
+            "{}|{}|{}|{}|{}",
+            severity,
+            issue.problem.kind(),
+            issue.package_id,
+            path,
+            issue.fix()
+        ),
+        false => format!(
+            "{}: {} ({}): {}\r\n  -> {}",
+            severity,
+            issue.package_id,
+            path,
+            message,
+            issue.fix()
+        ),
+    }
+}
+
+/// returns the number of errors found + the report
+pub fn get_health_text(home: &ChocoHome, limitoutput: bool) -> Result<(usize, String), RocoError> {
+    let issues = get_health_issues(home)?;
+    let errors = issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .count();
+    let mut lines: Vec<String> = issues
+        .iter()
+        .map(|i| get_health_issue_text(home, i, limitoutput))
+        .collect();
+    if !limitoutput {
+        lines.push(match issues.len() {
+            0 => "no problems found.".to_string(),
+            n => format!("{} errors, {} warnings found.", errors, n - errors),
+        });
+    }
+    Ok((errors, lines.join("\r\n")))
 }
 
-pub fn get_sources_text(limitoutput: bool) -> String {
+pub fn get_sources_text(home: &ChocoHome, limitoutput: bool) -> Result<String, RocoError> {
     let mut res = String::new();
-    let sources = get_choco_sources().unwrap();
+    // NOTE: listing sources doesn't need any credentials, stick to the config model
+    // (no decryption required)
+    let sources = home.config()?.sources;
 
     fn c_bool(v: bool) -> &'static str {
         match v {
@@ -142,22 +541,27 @@ pub fn get_sources_text(limitoutput: bool) -> String {
         }
     }
 
-    let num_iterations = sources.len() - 1;
+    let num_iterations = sources.len();
+    // same as choco: wrap values that contain the separator in quotes
+    fn quote_if_contains_pipe(v: &str) -> String {
+        match v.contains('|') {
+            true => format!("\"{}\"", v),
+            false => v.to_string(),
+        }
+    }
+
     for (i, f) in sources.iter().enumerate() {
         res.push_str(
             &(match limitoutput {
                 true => {
-                    let user = match &f.credential {
-                        Some(auth) => auth.user.clone(),
-                        None => String::new(),
-                    };
-                    let certificate = (f.certificate.as_ref().unwrap_or(&String::new())).clone();
+                    let user = f.user.as_deref().unwrap_or_default();
+                    let certificate = f.certificate.as_deref().unwrap_or_default();
                     format!(
                         "{}|{}|{}|{}|{}|{}|{}|{}|{}",
-                        f.name,
-                        f.url,
+                        quote_if_contains_pipe(&f.id),
+                        f.value,
                         c_bool(f.disabled),
-                        user,
+                        quote_if_contains_pipe(user),
                         certificate,
                         f.priority,
                         c_bool(f.bypass_proxy),
@@ -167,12 +571,12 @@ pub fn get_sources_text(limitoutput: bool) -> String {
                 }
                 false => {
                     let name_1 = match f.disabled {
-                        true => format!("{} [Disabled]", f.name),
-                        false => f.name.clone(),
+                        true => format!("{} [Disabled]", f.id),
+                        false => f.id.clone(),
                     };
-                    let name_2 = match &f.credential {
-                        Some(_) => format!("{} (Authenticated)", f.url),
-                        None => format!("{} ", f.url),
+                    let name_2 = match f.user.is_some() || f.certificate.is_some() {
+                        true => format!("{} (Authenticated)", f.value),
+                        false => format!("{} ", f.value),
                     };
                     format!(
                         "{} - {}| Priority {}|Bypass Proxy - {}|Self-Service - {}|Admin Only - {}.",
@@ -186,12 +590,59 @@ pub fn get_sources_text(limitoutput: bool) -> String {
                 }
             }),
         );
-        if i < num_iterations {
+        if i + 1 < num_iterations {
             res.push_str("\r\n");
         }
     }
 
-    res
+    Ok(res)
+}
+
+pub fn get_features_text(home: &ChocoHome, limitoutput: bool) -> Result<String, RocoError> {
+    let features = home.config()?.features;
+    let lines: Vec<String> = features
+        .iter()
+        .map(|f| match limitoutput {
+            true => format!(
+                "{}|{}|{}",
+                f.name,
+                match f.enabled {
+                    true => "Enabled",
+                    false => "Disabled",
+                },
+                f.description
+            ),
+            false => format!(
+                "[{}] {} - {}",
+                match f.enabled {
+                    true => "x",
+                    false => " ",
+                },
+                f.name,
+                f.description
+            ),
+        })
+        .collect();
+    Ok(lines.join("\r\n"))
+}
+
+pub fn get_config_text(home: &ChocoHome, limitoutput: bool) -> Result<String, RocoError> {
+    let config = home.config()?.config;
+    let lines: Vec<String> = config
+        .iter()
+        .map(|c| match limitoutput {
+            true => format!("{}|{}|{}", c.key, c.value, c.description),
+            false => format!("{} = {} | {}", c.key, c.value, c.description),
+        })
+        .collect();
+    Ok(lines.join("\r\n"))
+}
+
+pub fn get_config_value_text(home: &ChocoHome, key: &str) -> Result<String, RocoError> {
+    let cfg = home.config()?;
+    cfg.config_value(key)
+        .map(|v| v.to_string())
+        .ok_or_else(|| RocoError::ConfigKeyNotFound(key.to_string()))
 }
 
 fn get_package_list_text(filter: &str, packages: Vec<Package>, limitoutput: bool) -> String {
@@ -201,195 +652,206 @@ fn get_package_list_text(filter: &str, packages: Vec<Package>, limitoutput: bool
     let filter = filter.to_lowercase();
 
     for (i, p) in packages.iter().enumerate() {
-        if filter != "all" {
-            if !p.id.contains(&filter) {
-                continue;
-            }
+        if filter != "all" && !p.id.contains(&filter) {
+            continue;
         }
         res.push_str(&format!("{}{}{}", p.id(), sep, p.version()));
-        if i < (num_iterations - 1) {
+        if i + 1 < num_iterations {
             res.push_str("\r\n");
         }
     }
     res
 }
 
-fn get_package_from_nuspec(pkgs_path: &std::path::PathBuf) -> Package {
-    assert!(pkgs_path.is_file(), "The provided path is not a file");
-    println_verbose(&format!("parse nuspec '{}'", pkgs_path.to_str().unwrap()));
+fn get_package_from_nuspec(
+    home: &ChocoHome,
+    pkgs_path: &std::path::Path,
+) -> Result<Package, RocoError> {
+    let path_str = pkgs_path.to_string_lossy();
+    println_verbose(&format!("parse nuspec '{}'", path_str));
 
-    let nuspec_content = std::fs::read(pkgs_path).expect("Failed to read nuspec file");
+    let nuspec_content = std::fs::read(pkgs_path)?;
 
-    get_package_from_nuspec_text(&nuspec_content)
+    get_package_from_nuspec_text(&path_str, &nuspec_content, Some(home))
 }
 
-fn get_package_from_nuspec_text(nuspec_content: &[u8]) -> Package {
-    let mut pkg_name = String::new();
-    let mut pkg_version = String::new();
+fn get_package_from_nuspec_text(
+    origin: &str,
+    nuspec_content: &[u8],
+    home: Option<&ChocoHome>,
+) -> Result<Package, RocoError> {
+    let metadata = PackageMetadata::from_nuspec_text(origin, nuspec_content)?;
 
-    let mut reader = Reader::from_reader(nuspec_content);
-    reader.trim_text(true);
-    let mut buf = Vec::new();
-    let mut tag: NuspecTag = NuspecTag::Null;
+    // Check if the package is pinned
+    // (packages read from a feed can't be pinned, there may not even be a local choco dir)
+    let pinned = match home {
+        Some(home) => home.is_pinned(&metadata.id, &metadata.version),
+        None => false,
+    };
 
-    let mut dependencies = Vec::new();
+    Ok(metadata.to_package(pinned))
+}
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) => match e.name().as_ref() {
-                b"id" => tag = NuspecTag::Id,
-                b"version" => tag = NuspecTag::Version,
-                _ => tag = NuspecTag::Null,
-            },
-            Ok(Event::Empty(ref e)) => match e.name().as_ref() {
-                b"dependency" => {
-                    tag = NuspecTag::Dependency;
-                    let attrib_map = xml_attribs_to_map(&mut e.attributes());
-                    let id = attrib_map.get("id").unwrap();
-                    let empty = String::new();
-                    let version = attrib_map.get("version").unwrap_or(&empty);
-                    dependencies.push(Package {
-                        id: id.to_string(),
-                        version: version.to_string(),
-                        pinned: false,
-                        dependencies: None,
-                    });
-                }
-                _ => (),
-            },
-            Ok(Event::Text(e)) => match tag {
-                // Always ensure package IDs are lowercase
-                NuspecTag::Id => pkg_name = String::from_utf8(e.to_vec()).unwrap(),
-                NuspecTag::Version => pkg_version = String::from_utf8(e.to_vec()).unwrap(),
-                _ => (),
-            },
-            Ok(Event::Eof) => break,
-            _ => (),
+/// nuspec metadata of the installed package with the given id (case-insensitive)
+pub fn get_package_metadata(home: &ChocoHome, id: &str) -> Result<PackageMetadata, RocoError> {
+    let mut pkg_dir = home.lib_dir();
+    pkg_dir.push("*/*.nuspec");
+    for path in glob::glob(&pkg_dir.to_string_lossy())?.flatten() {
+        let path_str = path.to_string_lossy();
+        println_verbose(&format!("parse nuspec '{}'", path_str));
+        match PackageMetadata::from_nuspec_text(&path_str, &std::fs::read(&path)?) {
+            Ok(metadata) if metadata.id.eq_ignore_ascii_case(id) => return Ok(metadata),
+            Ok(_) => (),
+            Err(e) => println_verbose(&format!("skipping package: {}", e)),
         }
-        buf.clear();
     }
+    Err(RocoError::PackageNotFound(id.to_string()))
+}
 
-    // Check if the package is pinned
-    let choco_dir = get_chocolatey_dir().unwrap();
-    let mut pinned_file = PathBuf::from(choco_dir);
-    pinned_file.push(".chocolatey");
-    pinned_file.push(format!(
-        "{}.{}",
-        pkg_name.to_string(),
-        pkg_version.to_string()
-    ));
-    pinned_file.push(".pin");
-
-    let the_dependencies = if dependencies.is_empty() {
-        None
-    } else {
-        Some(dependencies)
-    };
-
-    Package {
-        id: pkg_name.to_string(),
-        version: pkg_version.to_string(),
-        pinned: pinned_file.exists(),
-        dependencies: the_dependencies,
+pub fn get_package_info_text(
+    home: &ChocoHome,
+    id: &str,
+    limitoutput: bool,
+) -> Result<String, RocoError> {
+    let metadata = get_package_metadata(home, id)?;
+    let mut res = metadata.to_text(limitoutput);
+    if !limitoutput {
+        if home.is_pinned(&metadata.id, &metadata.version) {
+            res.push_str("\r\n Pinned: true");
+        }
+        res.push_str("\r\n\r\n1 packages installed.");
     }
+    Ok(res)
+}
+
+/// content of the nuspec inside a nupkg (zip)
+fn read_nuspec_from_nupkg(nupkg_path: &str) -> Option<Vec<u8>> {
+    let file = File::open(nupkg_path).ok()?;
+    let mut archive = ZipArchive::new(file).ok()?;
+    let nuspec_file_name = archive
+        .file_names()
+        .find(|name| name.ends_with(".nuspec"))?
+        .to_string();
+    let mut nuspec_file = archive.by_name(&nuspec_file_name).ok()?;
+    let mut nuspec_content = Vec::new();
+    nuspec_file.read_to_end(&mut nuspec_content).ok()?;
+    Some(nuspec_content)
 }
 
+/// package from the nuspec inside the nupkg, falls back to its file name
 fn get_package_from_nupkg(nupkg_path: &str) -> Option<Package> {
-    let file = match File::open(nupkg_path).ok() {
-        Some(it) => it,
+    match read_nuspec_from_nupkg(nupkg_path)
+        .and_then(|content| get_package_from_nuspec_text(nupkg_path, &content, None).ok())
+    {
+        Some(p) => Some(p),
         None => {
-            return get_package_from_nupkg_filename(nupkg_path);
-        }
-    };
-    let mut archive = match ZipArchive::new(file) {
-        Ok(archive) => archive,
-        Err(_) => {
-            return get_package_from_nupkg_filename(nupkg_path);
+            let file_name = Path::new(nupkg_path).file_name()?;
+            get_package_from_nupkg_filename(&file_name.to_string_lossy())
         }
-    };
+    }
+}
 
-    let nuspec_file_name = match archive.file_names().find(|name| name.ends_with(".nuspec")) {
-        Some(name) => name.to_string(),
-        None => {
-            return get_package_from_nupkg_filename(nupkg_path);
-        }
-    };
+/// metadata of all versions of a package in a folder feed,
+/// read from the nuspec inside the nupkg (only id and version if it can't be read)
+pub(crate) fn get_folder_feed_metadata(
+    feed: &Feed,
+    id: &str,
+) -> Result<Vec<PackageMetadata>, RocoError> {
+    let mut feed_dir = PathBuf::from(&feed.url);
+    feed_dir.push("**/*.nupkg");
 
-    let mut nuspec_file = match archive.by_name(&nuspec_file_name) {
-        Ok(file) => file,
-        Err(_) => {
-            return get_package_from_nupkg_filename(nupkg_path);
+    let mut res = Vec::new();
+    for entry in glob::glob(&feed_dir.to_string_lossy())? {
+        let entry = entry?;
+        let file_name = match entry.file_name() {
+            Some(f) => f.to_string_lossy().to_string(),
+            None => continue,
+        };
+        match get_package_from_nupkg_filename(&file_name) {
+            Some(p) if p.id.eq_ignore_ascii_case(id) => {
+                let path_str = entry.to_string_lossy();
+                println_verbose(&format!("read nuspec from '{}'", path_str));
+                let metadata = read_nuspec_from_nupkg(&path_str)
+                    .and_then(|content| PackageMetadata::from_nuspec_text(&path_str, &content).ok())
+                    .unwrap_or_else(|| PackageMetadata {
+                        id: p.id,
+                        version: p.version,
+                        ..Default::default()
+                    });
+                res.push(metadata);
+            }
+            _ => (),
         }
-    };
-    let mut nuspec_content = String::new();
-    if nuspec_file.read_to_string(&mut nuspec_content).is_err() {
-        return get_package_from_nupkg_filename(nupkg_path);
     }
-
-    let p =  get_package_from_nuspec_text(nuspec_content.as_bytes());
-    Some(p)
- }
+    Ok(res)
+}
 
 fn get_package_from_nupkg_filename(filename: &str) -> Option<Package> {
-    let semver_regex = regex::Regex::new(r#"^(.+?)\.(((\d+\.?)+)(-.+)?)\.nupkg$"#).unwrap();
-    match semver_regex.captures(filename) {
-        Some(captures) => Some(Package {
-            id: captures
-                .get(1)
-                .map_or(String::from(""), |m| String::from(m.as_str())),
-            version: captures
-                .get(2)
-                .map_or(String::from(""), |m| String::from(m.as_str())),
-            pinned: false,
-            dependencies: None,
-        }),
-        None => {
-            println!("ERROR: failed to get package from filename '{}'", filename);
-            None
-        }
-    }
+    let semver_regex = regex::Regex::new(r"^(.+?)\.(((\d+\.?)+)(-.+)?)\.nupkg$").unwrap();
+    semver_regex.captures(filename).map(|captures| Package {
+        id: captures
+            .get(1)
+            .map_or(String::from(""), |m| String::from(m.as_str())),
+        version: captures
+            .get(2)
+            .map_or(String::from(""), |m| String::from(m.as_str())),
+        pinned: false,
+        dependencies: None,
+    })
 }
 
-pub fn get_nupkgs_from_path(
-    pkgs: &Vec<Package>,
+/// all packages (any version) in a folder feed accepted by `filter`
+pub(crate) fn find_nupkgs_in_path(
     feed: &Feed,
     prerelease: bool,
-) -> Result<Vec<Package>, Box<dyn std::error::Error>> {
+    filter: impl Fn(&Package) -> bool,
+) -> Result<Vec<Package>, RocoError> {
     let mut feed_dir = PathBuf::from(&feed.url);
     feed_dir.push("**/*.nupkg");
 
-    let prerelease_regex = regex::Regex::new(r#"^(\d+\.?){1,4}\-.+"#).unwrap();
+    let prerelease_regex = regex::Regex::new(r"^(\d+\.?){1,4}\-.+").unwrap();
 
     let mut packages: Vec<Package> = Vec::new();
     for entry in glob::glob(&feed_dir.to_string_lossy())? {
-        match get_package_from_nupkg(entry?.file_name().unwrap().to_str().unwrap()) {
-            Some(p) => {
-                // only need to check if a version has a prerelease part,
-                // no need to validate / parse the whole thing at this point!
-                let version_is_prerelease = prerelease_regex.is_match(&p.version);
-                if !prerelease && version_is_prerelease {
-                    continue;
-                }
-                if pkgs
-                    .iter()
-                    .any(|s| s.id.to_lowercase() == p.id.to_lowercase())
-                {
-                    packages.push(p);
-                }
+        if let Some(p) = get_package_from_nupkg(&entry?.to_string_lossy()) {
+            // only need to check if a version has a prerelease part,
+            // no need to validate / parse the whole thing at this point!
+            let version_is_prerelease = prerelease_regex.is_match(&p.version);
+            if !prerelease && version_is_prerelease {
+                continue;
+            }
+            if filter(&p) {
+                packages.push(p);
             }
-            None => {}
         }
     }
 
     Ok(packages)
 }
 
+pub fn get_nupkgs_from_path(
+    pkgs: &[Package],
+    feed: &Feed,
+    prerelease: bool,
+) -> Result<Vec<Package>, RocoError> {
+    find_nupkgs_in_path(feed, prerelease, |p| {
+        pkgs.iter()
+            .any(|s| s.id.to_lowercase() == p.id.to_lowercase())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::roco::testutil::{feed, nuspec_text, TestDir};
     //NOTE: ChocolateyInstall, RocolateyTestRoot env-vars needs to be set in via Cargo [env]
 
+    fn home() -> ChocoHome {
+        ChocoHome::from_env().unwrap()
+    }
+
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn get_package_from_nupkg_test() {
         let pkg = get_package_from_nupkg("googlechrome.80.0.3987.149.nupkg").unwrap();
         assert_eq!(pkg.id, "googlechrome");
@@ -402,18 +864,317 @@ mod tests {
         // TODO: look up if package ids are case-sensitive or not
         // NOTE: would need to normalize across local/v2/v3 sources
         // iirc ProGet does 'support' case-sensitive package IDs
-        let mut pkg_path = PathBuf::from(
-            get_chocolatey_dir().expect("env:ChocolateyInstall must be set during tests!"),
-        );
-        pkg_path.push("lib");
+        let home = ChocoHome::from_env().expect("env:ChocolateyInstall must be set during tests!");
+        let mut pkg_path = home.lib_dir();
         pkg_path.push("Chocolatey/Chocolatey.nuspec");
 
-        let pkg = get_package_from_nuspec(&pkg_path);
+        let pkg = get_package_from_nuspec(&home, &pkg_path).unwrap();
         assert_eq!(pkg.id, "Chocolatey");
         assert_eq!(pkg.version, "2.0");
     }
 
     #[test]
+    fn get_local_packages_other_root_test() {
+        let root = TestDir::new("home");
+        root.add_package("foo", "foo", "1.0");
+        root.write(".chocolatey/foo.1.0/.pin", "");
+
+        let home = root.home();
+        let pkgs = get_local_packages(&home).unwrap();
+        assert_eq!(pkgs.len(), 1);
+        assert_eq!(pkgs[0].id, "foo");
+        assert!(pkgs[0].pinned);
+    }
+
+    #[test]
+    fn get_local_packages_skips_broken_nuspec_test() {
+        let tests_root = std::env::var("RocolateyTestRoot").unwrap();
+        let home =
+            ChocoHome::from_dir(PathBuf::from(tests_root).join("broken_choco_home")).unwrap();
+        let ids = |pkgs: Vec<Package>| pkgs.into_iter().map(|p| p.id).collect::<Vec<String>>();
+        assert_eq!(ids(get_local_packages(&home).unwrap()), ["good"]);
+        assert_eq!(ids(get_local_bad_packages(&home).unwrap()), ["bad"]);
+        assert_eq!(get_package_metadata(&home, "GOOD").unwrap().version, "1.0");
+    }
+
+    #[test]
+    fn get_package_from_malformed_nuspec_test() {
+        let res = get_package_from_nuspec_text(
+            "broken.nuspec",
+            b"<package><metadata><id>broken</id></version></metadata></package>",
+            None,
+        );
+        assert!(matches!(res, Err(RocoError::NuspecParse { .. })));
+
+        let res = get_package_from_nuspec_text(
+            "noid.nuspec",
+            b"<package><metadata><id>noid</id><dependencies><dependency version=\"1.0\" /></dependencies></metadata></package>",
+            None,
+        );
+        assert!(matches!(res, Err(RocoError::NuspecParse { .. })));
+    }
+
+    #[test]
+    fn get_dependency_tree_pkg_text_test() {
+        let pkg = |id: &str, version: &str, dependencies: Option<Vec<Package>>| Package {
+            id: id.to_string(),
+            version: version.to_string(),
+            pinned: false,
+            dependencies,
+        };
+        let app = pkg(
+            "app",
+            "1.0",
+            Some(vec![
+                pkg("lib.a", "[1.0,2.0)", None),
+                pkg("lib.b", "3.0", None),
+                pkg("lib.c", "", None),
+            ]),
+        );
+        let lib_a = pkg("lib.a", "2.1", None);
+        let lib_b = pkg("lib.b", "3.0.1", None);
+        let packages: HashMap<String, &Package> =
+            HashMap::from([("lib.a".to_string(), &lib_a), ("lib.b".to_string(), &lib_b)]);
+
+        assert_eq!(
+            get_dependency_tree_pkg_text(1, &app, &packages, &mut Vec::new()),
+            [
+                "app (1.0)",
+                " |-lib.a ([1.0,2.0))",
+                "ERROR: installed lib.a (2.1) does not satisfy [1.0,2.0)",
+                " |-lib.b (3.0)",
+                " |-lib.c ",
+                "ERROR: failed to locate lib.c among local packages",
+                "",
+            ]
+            .join("\r\n")
+        );
+
+        let a = pkg("a", "1.0", Some(vec![pkg("b", "", None)]));
+        let b = pkg(
+            "b",
+            "1.0",
+            Some(vec![pkg("a", "", None), pkg("b", "", None)]),
+        );
+        let packages: HashMap<String, &Package> =
+            HashMap::from([("a".to_string(), &a), ("b".to_string(), &b)]);
+        assert_eq!(
+            get_dependency_tree_pkg_text(1, &a, &packages, &mut Vec::new()),
+            [
+                "a (1.0)",
+                " |-b ",
+                " | |-a ",
+                "ERROR: dependency cycle a -> b -> a",
+                " | |-b ",
+                "ERROR: dependency cycle b -> b",
+                "",
+            ]
+            .join("\r\n")
+        );
+    }
+
+    #[test]
+    fn get_health_issues_test() {
+        let (count, text) = get_health_text(&home(), false).unwrap();
+        assert_eq!((count, text.as_str()), (0, "no problems found."));
+
+        let root = TestDir::new("doctor");
+        for dir in ["lib/empty", "lib-bkp/ok", "lib-bad/failed"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        root.add_package("ok", "ok", "1.0");
+        root.add_package("multi", "multi", "1.0");
+        root.write("lib/multi/other.nuspec", nuspec_text("other", "1.0"));
+        root.add_package("renamed", "OK", "1.0");
+        root.write("lib/foo.old/foo.nuspec", nuspec_text("foo.new", "1.0"));
+        root.write("lib/broken/broken.nuspec", "<package><metadata>");
+        root.add_package("pending", "pending", "1.0");
+        root.write("lib/pending/.chocolateyPending", "");
+
+        let home = root.home();
+        let issues = get_health_issues(&home).unwrap();
+        let kinds: Vec<(Severity, &str, &str)> = issues
+            .iter()
+            .map(|i| (i.severity, i.package_id.as_str(), i.problem.kind()))
+            .collect();
+        let sep = std::path::MAIN_SEPARATOR;
+        assert_eq!(
+            kinds,
+            vec![
+                (Severity::Error, "broken", "invalid-nuspec"),
+                (Severity::Error, "empty", "missing-nuspec"),
+                (Severity::Error, "failed", "failed-install"),
+                (Severity::Error, "OK", "duplicate-id"),
+                (Severity::Error, "pending", "pending-install"),
+                (Severity::Warning, "foo.new", "id-mismatch"),
+                (Severity::Warning, "multi", "multiple-nuspecs"),
+                (Severity::Warning, "OK", "id-mismatch"),
+                (Severity::Warning, "ok", "backup-leftover"),
+            ]
+        );
+        assert_eq!(
+            issues[3].problem,
+            HealthProblem::DuplicateId {
+                other: root.join("lib").join("ok")
+            }
+        );
+        assert_eq!(issues[6].problem, HealthProblem::MultipleNuspecs(2));
+
+        let (count, text) = get_health_text(&home, true).unwrap();
+        assert_eq!(count, 5);
+        assert!(text.split("\r\n").any(|l| l
+            == format!(
+                "WARNING|backup-leftover|ok|lib-bkp{}ok|choco upgrade ok --force",
+                sep
+            )));
+        let (_, text) = get_health_text(&home, false).unwrap();
+        assert!(text.contains(&format!(
+            "ERROR: empty (lib{}empty): no nuspec found\r\n  -> choco install empty --force",
+            sep
+        )));
+        assert!(text.ends_with("\r\n5 errors, 4 warnings found."));
+    }
+
+    #[test]
+    fn get_package_info_text_test() {
+        let metadata = get_package_metadata(&home(), "VLC").unwrap();
+        assert_eq!(metadata.id, "vlc");
+        assert_eq!(metadata.authors.as_deref(), Some("VideoLAN"));
+        assert!(!metadata.require_license_acceptance);
+
+        let text = get_package_info_text(&home(), "vlc", false).unwrap();
+        assert!(text.starts_with("vlc 3.0.18\r\n Title: VLC media player\r\n"));
+        assert!(text.contains("\r\n Software License: https://www.videolan.org/legal.html\r\n"));
+        assert!(text.ends_with("\r\n\r\n1 packages installed."));
+        let text = get_package_info_text(&home(), "vlc", true).unwrap();
+        assert!(text.contains("\r\nlicenseUrl|https://www.videolan.org/legal.html\r\n"));
+        assert!(text
+            .split("\r\n")
+            .any(|l| l == "tags|vlc media player admin foss cross-platform"));
+
+        assert!(matches!(
+            get_package_metadata(&home(), "does.not.exist"),
+            Err(RocoError::PackageNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn dependents_index_test() {
+        let pkg = |id: &str, dependencies: &[&str]| Package {
+            id: id.to_string(),
+            version: "1.0".to_string(),
+            pinned: false,
+            dependencies: Some(
+                dependencies
+                    .iter()
+                    .map(|d| Package {
+                        id: d.to_string(),
+                        version: String::new(),
+                        pinned: false,
+                        dependencies: None,
+                    })
+                    .collect(),
+            ),
+        };
+        let packages = vec![
+            pkg("app", &["Lib.A", "tool"]),
+            pkg("lib.a", &["base"]),
+            pkg("tool", &["base", "base"]),
+            pkg("base", &["app"]), // cycle
+        ];
+        let index = DependentsIndex::new(&packages);
+        let ids = |pkgs: Vec<&Package>| {
+            let mut ids: Vec<String> = pkgs.iter().map(|p| p.id.clone()).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(index.direct("BASE")), vec!["lib.a", "tool"]);
+        assert_eq!(ids(index.direct("lib.a")), vec!["app"]);
+        assert!(index.direct("unknown").is_empty());
+        assert_eq!(ids(index.transitive("lib.a")), vec!["app", "base", "tool"]);
+    }
+
+    #[test]
+    fn get_dependents_text_test() {
+        let text = get_dependents_text(&home(), "JRE8", false, true).unwrap();
+        assert_eq!(text, "adobeair|0.0.42\r\nfirefox|84.0.1");
+        let text = get_dependents_text(&home(), "morg.pkg.id2", true, false).unwrap();
+        assert_eq!(
+            text,
+            "adobeair 0.0.42\r\nfirefox 84.0.1\r\njre8 8.0.56\r\n3 packages depend on morg.pkg.id2."
+        );
+    }
+
+    #[test]
+    fn get_sources_text_test() {
+        // matches the output of 'choco source list' for test/fake_choco_home
+        assert_eq!(
+            get_sources_text(&home(), false).unwrap(),
+            [
+                "chocolatey - https://chocolatey.org/api/v2 | Priority 101|Bypass Proxy - False|Self-Service - False|Admin Only - False.",
+                "nuget.org - https://api.nuget.org/v3/index.json | Priority 101|Bypass Proxy - False|Self-Service - False|Admin Only - False.",
+                "badurl [Disabled] - https://github.com/rocolatey/feed/doesntexist (Authenticated)| Priority 42|Bypass Proxy - True|Self-Service - False|Admin Only - False.",
+                "local-dev [Disabled] - c:/local-pkgs | Priority 150|Bypass Proxy - False|Self-Service - True|Admin Only - True.",
+            ]
+            .join("\r\n")
+        );
+        // matches 'choco source list -r'
+        assert_eq!(
+            get_sources_text(&home(), true).unwrap(),
+            [
+                "chocolatey|https://chocolatey.org/api/v2|False|||101|False|False|False",
+                "nuget.org|https://api.nuget.org/v3/index.json|False|||101|False|False|False",
+                "badurl|https://github.com/rocolatey/feed/doesntexist|True|\"roco|user\"||42|True|False|False",
+                "local-dev|c:/local-pkgs|True|||150|False|True|True",
+            ]
+            .join("\r\n")
+        );
+    }
+
+    #[test]
+    fn get_features_text_test() {
+        let features = get_features_text(&home(), false).unwrap();
+        let features: Vec<&str> = features.split("\r\n").collect();
+        assert_eq!(features.len(), 46);
+        assert_eq!(
+            features[0],
+            "[x] checksumFiles - Checksum files when pulled in from internet (based on package)."
+        );
+        assert_eq!(
+            features[2],
+            "[ ] allowGlobalConfirmation - Prompt for confirmation in scripts or bypass."
+        );
+
+        let features = get_features_text(&home(), true).unwrap();
+        assert!(features.starts_with(
+            "checksumFiles|Enabled|Checksum files when pulled in from internet (based on package).\r\nautoUninstaller|Enabled|"
+        ));
+        assert!(features
+            .contains("\r\nallowGlobalConfirmation|Disabled|Prompt for confirmation in scripts or bypass.\r\n"));
+    }
+
+    #[test]
+    fn get_config_text_test() {
+        let config = get_config_text(&home(), true).unwrap();
+        assert!(config.contains(
+            "\r\ncommandExecutionTimeoutSeconds|18000|Default timeout for command execution. '0' for infinite (starting in 0.10.4).\r\n"
+        ));
+        let config = get_config_text(&home(), false).unwrap();
+        assert!(config.starts_with("cacheLocation =  | Cache location if not TEMP folder."));
+
+        assert_eq!(
+            get_config_value_text(&home(), "commandexecutiontimeoutseconds").unwrap(),
+            "18000"
+        );
+        assert_eq!(get_config_value_text(&home(), "cacheLocation").unwrap(), "");
+        assert!(matches!(
+            get_config_value_text(&home(), "doesNotExist"),
+            Err(RocoError::ConfigKeyNotFound(_))
+        ));
+    }
+
+    #[test]
+    #[allow(clippy::useless_conversion)]
     fn get_nupkgs_from_path_test() {
         let tests_root = std::env::var("RocolateyTestRoot").unwrap();
         let mut tests_repo = PathBuf::from(tests_root);
@@ -431,6 +1192,7 @@ mod tests {
             certificate: None,
             self_service: false,
             priority: 0,
+            request_timeout_seconds: None,
             feed_type: crate::roco::FeedType::LocalFileSystem,
             service_index: None,
         };
@@ -453,4 +1215,60 @@ mod tests {
         let pkgs = get_nupkgs_from_path(&pkgs, &tests_feed, true).unwrap();
         assert!(pkgs.len() == 2);
     }
+
+    #[test]
+    fn get_folder_feed_metadata_test() {
+        use std::io::Write;
+
+        let root = TestDir::new("feed");
+        let mut zip = zip::ZipWriter::new(File::create(root.join("git.2.40.0.nupkg")).unwrap());
+        zip.start_file("git.nuspec", Default::default()).unwrap();
+        zip.write_all(
+            br#"<?xml version="1.0"?>
+<package xmlns="http://schemas.microsoft.com/packaging/2015/06/nuspec.xsd">
+  <metadata>
+    <id>git</id>
+    <version>2.40.0</version>
+    <title>Git</title>
+    <authors>The Git Development Community</authors>
+  </metadata>
+</package>"#,
+        )
+        .unwrap();
+        zip.finish().unwrap();
+        // not a zip, falls back to the filename
+        root.write("Git.2.41.0-rc1.nupkg", b"not a zip");
+        root.write("gitextensions.4.0.nupkg", b"not a zip");
+
+        let feed = feed(
+            "folder",
+            &root.to_string_lossy(),
+            0,
+            crate::roco::FeedType::LocalFileSystem,
+        );
+        let mut res = get_folder_feed_metadata(&feed, "GIT").unwrap();
+        res.sort_by(|a, b| a.version.cmp(&b.version));
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].title.as_deref(), Some("Git"));
+        assert_eq!(
+            res[0].authors.as_deref(),
+            Some("The Git Development Community")
+        );
+        assert_eq!(
+            (res[1].id.as_str(), res[1].version.as_str()),
+            ("Git", "2.41.0-rc1")
+        );
+        assert_eq!(res[1].title, None);
+
+        // a nupkg is read from its path, its file name only matters if that fails
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::copy(
+            root.join("git.2.40.0.nupkg"),
+            root.join("sub/git-latest.nupkg"),
+        )
+        .unwrap();
+        let pkgs = find_nupkgs_in_path(&feed, false, |p| p.id == "git").unwrap();
+        assert_eq!(pkgs.len(), 2);
+        assert!(pkgs.iter().all(|p| p.version == "2.40.0"));
+    }
 }
@@ -1,22 +1,36 @@
-use quick_xml::events::Event;
-use quick_xml::Reader;
 use std::collections::HashMap;
-use std::path::PathBuf;
 
+pub mod config;
+pub mod config_writer;
+pub mod credentials;
+pub mod deps;
+pub mod details;
+pub mod diff;
+pub mod drift;
+pub mod error;
+pub mod export;
+pub mod graph;
+pub mod home;
 pub mod local;
 pub mod nuget2;
 pub mod nuget3;
+pub mod nuspec;
+pub mod package_files;
+pub mod pin;
 pub mod remote;
-pub mod semver;
+pub mod search;
+#[cfg(test)]
+mod testutil;
+pub mod version;
+pub mod versions;
 use crate::println_verbose;
-
-#[derive(Debug)]
-pub enum NuspecTag {
-    Null,
-    Id,
-    Version,
-    Dependency,
-}
+pub use config::ChocolateyConfig;
+use credentials::CredentialTarget;
+pub use credentials::{encrypt_choco_config_string, CredentialDecryptor};
+pub use error::RocoError;
+pub use home::ChocoHome;
+pub use nuspec::PackageMetadata;
+pub use version::{NuGetVersion, VersionRange};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FeedType {
@@ -45,6 +59,10 @@ impl Package {
     pub fn pinned(&self) -> bool {
         self.pinned
     }
+    /// for dependencies `version` holds the allowed version range
+    pub fn version_range(&self) -> Result<version::VersionRange, RocoError> {
+        self.version.parse()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -59,6 +77,7 @@ pub struct Feed {
     pub self_service: bool,
     pub admin_only: bool,
     pub priority: i64,
+    pub request_timeout_seconds: Option<u64>,
     pub feed_type: FeedType,
     pub service_index: Option<nuget3::NuGetV3Index>,
 }
@@ -73,6 +92,8 @@ pub struct Credential {
 pub struct ProxySettings {
     pub url: String,
     pub credential: Option<Credential>,
+    pub bypass_list: Vec<String>,
+    pub bypass_on_local: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -89,255 +110,114 @@ fn xml_attribs_to_map(
     attrs: &mut quick_xml::events::attributes::Attributes,
 ) -> HashMap<String, String> {
     attrs
+        .flatten()
         .map(|a| {
-            let a = a.unwrap();
             (
-                String::from_utf8(Vec::from(a.key.as_ref())).unwrap(),
-                String::from_utf8(Vec::from(a.value)).unwrap(),
+                String::from_utf8_lossy(a.key.as_ref()).to_string(),
+                String::from_utf8_lossy(&a.value).to_string(),
             )
         })
         .collect::<HashMap<String, String>>()
 }
 
-fn get_feed_from_source_attribs(
-    attrs: &mut quick_xml::events::attributes::Attributes,
-) -> Option<Feed> {
-    let attrib_map = xml_attribs_to_map(attrs);
-
-    let disabled = match attrib_map.get("disabled") {
-        Some(attrib_value) => attrib_value == "true",
-        None => false,
-    };
-
-    let name = attrib_map.get("id").unwrap();
-    let url = attrib_map.get("value").unwrap();
-    let user = attrib_map.get("user");
-    let password = attrib_map.get("password");
-
+fn get_feed_from_source_config(
+    source: &config::SourceConfig,
+    cfg: &ChocolateyConfig,
+    proxy: &Option<ProxySettings>,
+    decryptor: &dyn CredentialDecryptor,
+) -> Result<Feed, RocoError> {
     println_verbose(&format!(
         "feed '{}' -> '{}' | disabled: {}",
-        name, url, disabled
+        source.id, source.value, source.disabled
     ));
 
     // NOTE: don't need to decrypt when feed is disabled anyway
-    let cred = match !disabled && user.is_some() && password.is_some() {
-        true => Some(Credential {
-            user: user.unwrap().clone(),
-            pass: decrypt_choco_config_string(password.unwrap()),
+    let cred = match (source.disabled, &source.user, &source.password) {
+        (false, Some(user), Some(password)) => Some(Credential {
+            user: user.clone(),
+            pass: decryptor.decrypt(CredentialTarget::Source(&source.id), password)?,
         }),
-        false => None,
+        _ => None,
     };
 
-    Some(Feed {
-        name: name.clone(),
-        url: url.clone(),
+    Ok(Feed {
+        name: source.id.clone(),
+        url: source.value.clone(),
         credential: cred,
-        proxy: None,
-        disabled: disabled,
+        proxy: match source.bypass_proxy {
+            true => None,
+            false => proxy.clone(),
+        },
+        disabled: source.disabled,
         feed_type: FeedType::Unknown,
         service_index: None,
-        certificate: match attrib_map.get("certificate") {
-            Some(c) => Some(c.clone()),
-            None => None,
-        },
-        bypass_proxy: match attrib_map.get("bypass_proxy") {
-            Some(c) => c == "true",
-            None => false,
-        },
-        self_service: match attrib_map.get("self_service") {
-            Some(c) => c == "true",
-            None => false,
-        },
-        admin_only: match attrib_map.get("admin_only") {
-            Some(c) => c == "true",
-            None => false,
-        },
-        priority: match attrib_map.get("priority") {
-            Some(c) => c.parse::<i64>().unwrap_or(0),
-            None => 0,
-        },
+        certificate: source.certificate.clone(),
+        bypass_proxy: source.bypass_proxy,
+        self_service: source.self_service,
+        admin_only: source.admin_only,
+        priority: source.priority,
+        request_timeout_seconds: cfg.web_request_timeout_seconds().filter(|t| *t > 0),
     })
 }
 
-fn get_config_settings_from_attribs(
-    config_settings: &mut HashMap<String, String>,
-    attrs: &mut quick_xml::events::attributes::Attributes,
-) {
-    let attrib_map = xml_attribs_to_map(attrs);
-    let val = attrib_map.get("value");
-    if val.is_some() {
-        config_settings.insert(attrib_map.get("key").unwrap().clone(), val.unwrap().clone());
-    }
+fn get_proxy_settings(
+    cfg: &ChocolateyConfig,
+    decryptor: &dyn CredentialDecryptor,
+) -> Result<Option<ProxySettings>, RocoError> {
+    let proxy_url = match cfg.proxy() {
+        Some(proxy_url) => proxy_url,
+        None => return Ok(None),
+    };
+    let credential = match cfg.proxy_user() {
+        Some(proxy_user) => Some(Credential {
+            user: proxy_user.to_string(),
+            pass: match cfg.proxy_password() {
+                Some(proxy_pass) => decryptor.decrypt(CredentialTarget::Proxy, proxy_pass)?,
+                None => String::new(),
+            },
+        }),
+        None => None,
+    };
+    Ok(Some(ProxySettings {
+        url: proxy_url.to_string(),
+        credential,
+        bypass_list: cfg
+            .proxy_bypass_list()
+            .iter()
+            .map(|e| e.to_string())
+            .collect(),
+        bypass_on_local: cfg.proxy_bypass_on_local(),
+    }))
 }
 
-fn get_chocolatey_dir() -> Result<String, std::env::VarError> {
-    let key = "ChocolateyInstall";
-    match std::env::var(key) {
-        Ok(val) => Ok(String::from(val)),
-        Err(e) => {
-            eprintln!("failed to get choco dir 'ChocolateyInstall': {}", e);
-            ::std::process::exit(1)
-        }
-    }
+fn get_choco_sources(home: &ChocoHome) -> Result<Vec<Feed>, RocoError> {
+    get_choco_sources_with(home, credentials::default_decryptor()?.as_ref())
 }
 
-fn get_choco_sources() -> Result<Vec<Feed>, std::io::Error> {
-    let mut sources = Vec::new();
-    let choco_dir = get_chocolatey_dir().expect("failed to get choco dir");
-    let mut cfg_dir = PathBuf::from(choco_dir);
-    cfg_dir.push("config/chocolatey.config");
-
-    println_verbose(&format!("parse '{}'", cfg_dir.to_str().unwrap()));
-    let mut config_settings: HashMap<String, String> = HashMap::new();
-
-    for entry in glob::glob(&cfg_dir.to_string_lossy()).expect("Failed to read glob pattern") {
-        match entry {
-            Ok(path) => {
-                let mut reader = Reader::from_file(path).expect("failed to init xml reader");
-                reader.trim_text(true);
-                let mut buf = Vec::new();
-                loop {
-                    match reader.read_event_into(&mut buf) {
-                        Ok(Event::Empty(ref e)) => match e.name().as_ref() {
-                            b"source" => {
-                                sources.push(get_feed_from_source_attribs(&mut e.attributes()));
-                            }
-                            b"add" => {
-                                get_config_settings_from_attribs(
-                                    &mut config_settings,
-                                    &mut e.attributes(),
-                                );
-                            }
-                            _ => {}
-                        },
-                        Ok(Event::Start(ref e)) => match e.name().as_ref() {
-                            b"source" => {
-                                sources.push(get_feed_from_source_attribs(&mut e.attributes()));
-                            }
-                            b"add" => {
-                                get_config_settings_from_attribs(
-                                    &mut config_settings,
-                                    &mut e.attributes(),
-                                );
-                            }
-                            _ => {}
-                        },
-                        Ok(Event::Eof) => break,
-                        _ => (),
-                    }
-                    buf.clear();
-                }
-            }
-            Err(e) => println!("{:?}", e),
-        }
-    }
-    // println!("{:#?}", config_settings);
-    let proxy_config = match config_settings.get("proxy") {
-        Some(proxy_url) => match proxy_url.is_empty() {
-            true => None,
-            false => Some(ProxySettings {
-                url: proxy_url.clone(),
-                credential: match config_settings.get("proxyUser") {
-                    Some(proxy_user) => Some(Credential {
-                        user: proxy_user.clone(),
-                        pass: match config_settings.get("proxyPassword") {
-                            Some(proxy_pass) => decrypt_choco_config_string(proxy_pass),
-                            None => String::new(),
-                        },
-                    }),
-                    None => None,
-                },
-            }),
-        },
-        None => None,
+/// the configured source with the given id (case-insensitive),
+/// only its password (and the proxy's) gets decrypted
+fn get_choco_source(home: &ChocoHome, id: &str) -> Result<Option<Feed>, RocoError> {
+    let cfg = home.config()?;
+    let source = match cfg.sources.iter().find(|s| s.id.eq_ignore_ascii_case(id)) {
+        Some(source) => source,
+        None => return Ok(None),
     };
-
-    let mut sources_with_proxy = Vec::new();
-    for s in sources {
-        match s {
-            Some(mut feed) => {
-                feed.proxy = proxy_config.clone();
-                sources_with_proxy.push(feed);
-            }
-            None => {}
-        }
-    }
-    Ok(sources_with_proxy)
+    let decryptor = credentials::default_decryptor()?;
+    let proxy_config = get_proxy_settings(&cfg, decryptor.as_ref())?;
+    get_feed_from_source_config(source, &cfg, &proxy_config, decryptor.as_ref()).map(Some)
 }
 
-fn decrypt_choco_config_string(encrypted: &str) -> String {
-    /*
-    fn decrypt_choco_config_string(encrypted: &str) -> String {
-        println_verbose(&format!("decypher '{}'", encrypted));
-
-        // Convert the encrypted base64 string to bytes
-        let encrypted_bytes = base64::decode(encrypted).expect("Failed to decode base64 string");
-
-        // Create a DATA_BLOB for the encrypted data
-        let mut encrypted_blob = DATA_BLOB {
-            cbData: encrypted_bytes.len() as u32,
-            pbData: encrypted_bytes.as_ptr() as *mut u8,
-        };
-
-        // Create an empty DATA_BLOB for the decrypted data
-        let mut decrypted_blob = DATA_BLOB {
-            cbData: 0,
-            pbData: null_mut(),
-        };
-
-        // Convert the optional entropy string to bytes
-        let entropy = CString::new("Chocolatey").expect("Failed to create CString");
-        let mut entropy_blob = DATA_BLOB {
-            cbData: entropy.as_bytes().len() as u32,
-            pbData: entropy.as_ptr() as *mut u8,
-        };
-
-        // Call CryptUnprotectData to decrypt the data
-        let result = unsafe {
-            CryptUnprotectData(
-                &mut encrypted_blob,
-                null_mut(),
-                &mut entropy_blob,
-                null_mut(),
-                null_mut(),
-                0,
-                &mut decrypted_blob,
-            )
-        };
-
-        if result == ERROR_SUCCESS {
-            // Convert the decrypted data to a string
-            let decrypted_bytes = unsafe {
-                std::slice::from_raw_parts(decrypted_blob.pbData, decrypted_blob.cbData as usize)
-            };
-            let decrypted_string = String::from_utf8_lossy(decrypted_bytes).to_string();
-
-            // Free the decrypted data
-            unsafe {
-                winapi::um::winbase::LocalFree(decrypted_blob.pbData as *mut _);
-            }
-
-            decrypted_string
-        } else {
-            panic!("Failed to decrypt data");
-        }
-    }
-    */
-    println_verbose(&format!("decypher '{}'", encrypted));
-    let pwsh = format!(
-        "Add-Type -AssemblyName System.Security;([System.Text.UTF8Encoding]::UTF8.GetString([System.Security.Cryptography.ProtectedData]::Unprotect(([System.Convert]::FromBase64String('{}')),([System.Text.UTF8Encoding]::UTF8.GetBytes('Chocolatey')),[System.Security.Cryptography.DataProtectionScope]::LocalMachine)))",
-        encrypted
-    );
-    let chdec = std::process::Command::new("powershell.exe")
-        .arg("-NoProfile")
-        .arg("-ExecutionPolicy")
-        .arg("Bypass")
-        .arg(pwsh)
-        .output()
-        .expect("failed to run decypher text");
-    let decrypted = String::from_utf8_lossy(&chdec.stdout);
-    let res = decrypted.trim(); // remove newlines
-    res.to_string()
+/// configured sources, passwords are decrypted using the given decryptor
+pub fn get_choco_sources_with(
+    home: &ChocoHome,
+    decryptor: &dyn CredentialDecryptor,
+) -> Result<Vec<Feed>, RocoError> {
+    let cfg = home.config()?;
+    let proxy_config = get_proxy_settings(&cfg, decryptor)?;
+    cfg.sources
+        .iter()
+        .map(|s| get_feed_from_source_config(s, &cfg, &proxy_config, decryptor))
+        .collect()
 }
 
 #[cfg(test)]
@@ -346,15 +226,10 @@ mod tests {
     use super::*;
     //NOTE: ChocolateyInstall, RocolateyTestRoot env-vars needs to be set in via Cargo [env]
 
-    #[test]
-    fn get_chocolatey_dir_test() {
-        // env:ChocolateyInstall will be set by cargo [env]
-        assert!(get_chocolatey_dir().is_ok());
-    }
-
     #[tokio::test]
+    #[allow(clippy::bool_assert_comparison, clippy::redundant_iter_cloned)]
     async fn get_choco_sources_test() {
-        let sources = get_choco_sources();
+        let sources = get_choco_sources(&ChocoHome::from_env().unwrap());
         assert!(sources.is_ok());
         let sources = sources.unwrap();
         sources.iter().cloned().for_each(|s| {
@@ -375,4 +250,40 @@ mod tests {
         assert_eq!(choco_source.feed_type, FeedType::Unknown);
         // TODO: verify choco_source.evaluate_feed_type works correctly
     }
+
+    #[test]
+    fn get_feed_from_source_config_test() {
+        let cfg = config::get_config_from_text(
+            "inline",
+            br#"<chocolatey>
+  <config>
+    <add key="proxy" value="http://proxy:8080" />
+    <add key="proxyUser" value="puser" />
+    <add key="proxyPassword" value="ENC-PROXY" />
+  </config>
+  <sources>
+    <source id="internal" value="https://repo/nuget" user="ci" password="ENC-SOURCE" priority="1" />
+    <source id="off" value="https://off/nuget" disabled="true" user="ci" password="ENC-UNKNOWN" />
+  </sources>
+</chocolatey>"#,
+        )
+        .unwrap();
+        let decryptor = credentials::StaticDecryptor::new([
+            ("ENC-PROXY", "proxy-secret"),
+            ("ENC-SOURCE", "source-secret"),
+        ]);
+
+        let proxy = get_proxy_settings(&cfg, &decryptor).unwrap();
+        assert_eq!(
+            proxy.as_ref().unwrap().credential.as_ref().unwrap().pass,
+            "proxy-secret"
+        );
+        let feed = get_feed_from_source_config(&cfg.sources[0], &cfg, &proxy, &decryptor).unwrap();
+        let cred = feed.credential.unwrap();
+        assert_eq!(cred.user, "ci");
+        assert_eq!(cred.pass, "source-secret");
+        // disabled feeds are never decrypted
+        let feed = get_feed_from_source_config(&cfg.sources[1], &cfg, &proxy, &decryptor).unwrap();
+        assert!(feed.credential.is_none());
+    }
 }
@@ -2,8 +2,10 @@ use quick_xml::events::Event;
 use quick_xml::Reader;
 
 use crate::println_verbose;
+use crate::roco::nuspec::DependencyGroup;
 use crate::roco::remote::{build_reqwest, invoke_package_bulk_request};
-use crate::roco::{Feed, Package};
+use crate::roco::search::SearchOptions;
+use crate::roco::{xml_attribs_to_map, Feed, NuGetVersion, Package, PackageMetadata, RocoError};
 
 // https://joelverhagen.github.io/NuGetUndocs/
 // http://docs.oasis-open.org/odata/odata/v4.0/errata03/os/complete/part1-protocol/odata-v4.0-errata03-os-part1-protocol-complete.html
@@ -29,7 +31,7 @@ async fn receive_package_delta(
     batch_size: u32,
     batch_offset: u32,
     prerelease: bool,
-) -> (u32, String) {
+) -> Result<(u32, String), RocoError> {
     let base_uri = format!("{}/Packages()", feed.url);
     let latest_filter = match prerelease {
         true => "$filter=IsAbsoluteLatestVersion",
@@ -43,24 +45,31 @@ async fn receive_package_delta(
         ),
     };
 
-    let client = build_reqwest(&feed);
-    let resp = client.get(&rs).send().await;
-    let query_res = resp.unwrap().text().await.unwrap();
+    let client = build_reqwest(feed)?;
+    let query_res = client
+        .get(&rs)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| RocoError::from_reqwest(&feed.name, e))?
+        .text()
+        .await
+        .map_err(|e| RocoError::from_reqwest(&feed.name, e))?;
     let c = query_res.matches("</entry>").count();
-    (c as u32, query_res)
+    Ok((c as u32, query_res))
 }
 
 pub(crate) async fn get_remote_packages(
     pkgs: &[Package],
     feed: &Feed,
     prerelease: bool,
-) -> Result<Vec<Package>, Box<dyn std::error::Error>> {
-
+) -> Result<Vec<Package>, RocoError> {
     let latest_filter = match prerelease {
         true => "IsAbsoluteLatestVersion",
         false => "IsLatestVersion",
     };
-    let query_string_base: String = format!("{}/Packages?$filter={} and (", feed.url, latest_filter);
+    let query_string_base: String =
+        format!("{}/Packages?$filter={} and (", feed.url, latest_filter);
     let total_pkgs = pkgs.len();
 
     // https://chocolatey.org/api/v2/Packages?$filter=IsLatestVersion and (Id eq 'Chocolatey' or Id eq 'Boxstarter' or Id eq 'vscode' or Id eq 'notepadplusplus')
@@ -68,9 +77,9 @@ pub(crate) async fn get_remote_packages(
     // NOTE: some feeds may have pagination (such as choco community repo)
     // determine number of packages returned by single request and use it as batch size for this repo from now on
     let (max_batch_size, _) = if total_pkgs == 1 {
-        (1 as u32, "".to_string())
+        (1_u32, "".to_string())
     } else {
-        receive_package_delta(feed, 0, 0, prerelease).await
+        receive_package_delta(feed, 0, 0, prerelease).await?
     };
 
     let query_str_delim = " or ".to_owned();
@@ -84,13 +93,288 @@ pub(crate) async fn get_remote_packages(
         |p| format!("(tolower(Id) eq '{}')", p.id.to_lowercase()),
         &query_str_delim,
         &query_str_end,
-        |pkgs, batch_str| -> () {
+        |_, pkgs, batch_str| {
             pkgs.extend(get_packages_from_odata(batch_str));
+            Ok(())
         },
     )
     .await
 }
 
+fn odata_url(feed: &Feed, function: &str, params: &[(&str, String)]) -> Result<String, RocoError> {
+    reqwest::Url::parse_with_params(&format!("{}/{}", feed.url, function), params)
+        .map(|u| u.to_string())
+        .map_err(|e| RocoError::feed_response(&feed.name, e))
+}
+
+/// quoted OData string literal
+fn odata_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+async fn get_odata(feed: &Feed, url: &str) -> Result<String, RocoError> {
+    println_verbose(&format!(" -> GET: {}", url));
+    build_reqwest(feed)?
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| RocoError::from_reqwest(&feed.name, e))?
+        .text()
+        .await
+        .map_err(|e| RocoError::from_reqwest(&feed.name, e))
+}
+
+/// follows the `next` links of a paged OData response until `limit` packages were received
+async fn get_odata_packages(
+    feed: &Feed,
+    url: String,
+    limit: Option<usize>,
+) -> Result<Vec<Package>, RocoError> {
+    let mut packages = Vec::new();
+    let mut next = Some(url);
+    while let Some(url) = next {
+        let odata_xml = get_odata(feed, &url).await?;
+        let page = get_packages_from_odata(&odata_xml);
+        if page.is_empty() {
+            break;
+        }
+        packages.extend(page);
+        if let Some(limit) = limit.filter(|l| packages.len() >= *l) {
+            packages.truncate(limit);
+            break;
+        }
+        next = get_next_link_from_odata(&odata_xml);
+    }
+    Ok(packages)
+}
+
+fn is_prerelease(version: &str) -> bool {
+    match version.parse::<NuGetVersion>() {
+        Ok(v) => v.is_prerelease(),
+        Err(_) => version.contains('-'),
+    }
+}
+
+/// all versions of a package (`FindPackagesById()`, following pagination)
+pub(crate) async fn find_packages_by_id(
+    feed: &Feed,
+    id: &str,
+    prerelease: bool,
+) -> Result<Vec<Package>, RocoError> {
+    let url = odata_url(feed, "FindPackagesById()", &[("id", odata_string(id))])?;
+    let mut packages = get_odata_packages(feed, url, None).await?;
+    packages.retain(|p| prerelease || !is_prerelease(&p.version));
+    Ok(packages)
+}
+
+pub(crate) async fn search_packages(
+    feed: &Feed,
+    options: &SearchOptions,
+) -> Result<Vec<Package>, RocoError> {
+    if options.exact {
+        return find_packages_by_id(feed, &options.term, options.prerelease).await;
+    }
+    let mut params = vec![
+        ("searchTerm", odata_string(&options.term)),
+        ("targetFramework", "''".to_string()),
+        ("includePrerelease", options.prerelease.to_string()),
+    ];
+    if !options.all_versions {
+        let latest_filter = match options.prerelease {
+            true => "IsAbsoluteLatestVersion",
+            false => "IsLatestVersion",
+        };
+        params.push(("$filter", latest_filter.to_string()));
+    }
+    params.push(("$skip", "0".to_string()));
+    params.push(("$top", options.limit().to_string()));
+    let url = odata_url(feed, "Search()", &params)?;
+    get_odata_packages(feed, url, Some(options.limit())).await
+}
+
+/// metadata of a specific version or the latest one
+/// (`Packages(Id='x',Version='y')` or `Packages()?$filter=tolower(Id) eq 'x' and IsLatestVersion`)
+pub(crate) async fn get_package_metadata(
+    feed: &Feed,
+    id: &str,
+    version: Option<&str>,
+    prerelease: bool,
+) -> Result<Vec<PackageMetadata>, RocoError> {
+    let url = match version {
+        Some(version) => format!(
+            "{}/Packages(Id={},Version={})",
+            feed.url,
+            odata_string(id),
+            odata_string(version)
+        ),
+        None => {
+            let latest_filter = match prerelease {
+                true => "IsAbsoluteLatestVersion",
+                false => "IsLatestVersion",
+            };
+            let filter = format!(
+                "tolower(Id) eq {} and {}",
+                odata_string(&id.to_lowercase()),
+                latest_filter
+            );
+            odata_url(feed, "Packages()", &[("$filter", filter)])?
+        }
+    };
+    match get_odata(feed, &url).await {
+        Ok(odata_xml) => get_metadata_from_odata(&feed.name, &odata_xml),
+        // the version does not exist
+        Err(RocoError::HttpStatus { status: 404, .. }) => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// 'id:range:targetFramework|...' (the range and target framework may be empty)
+fn get_dependency_groups_from_odata(dependencies: &str) -> Vec<DependencyGroup> {
+    let mut groups: Vec<DependencyGroup> = Vec::new();
+    for dependency in dependencies.split('|').filter(|d| !d.is_empty()) {
+        let mut parts = dependency.splitn(3, ':');
+        let id = parts.next().unwrap_or_default();
+        let range = parts.next().unwrap_or_default();
+        let target_framework = parts
+            .next()
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string());
+        let group = match groups
+            .iter_mut()
+            .position(|g| g.target_framework == target_framework)
+        {
+            Some(idx) => &mut groups[idx],
+            None => {
+                groups.push(DependencyGroup {
+                    target_framework,
+                    dependencies: Vec::new(),
+                });
+                groups.last_mut().unwrap()
+            }
+        };
+        // a target framework without dependencies is listed as '::tfm'
+        if !id.is_empty() {
+            group.dependencies.push(Package {
+                id: id.to_string(),
+                version: range.to_string(),
+                pinned: false,
+                dependencies: None,
+            });
+        }
+    }
+    groups
+}
+
+fn set_odata_property(metadata: &mut PackageMetadata, name: &str, value: String) {
+    match name {
+        "Published" => metadata.published = Some(value),
+        "DownloadCount" => metadata.download_count = value.parse().ok(),
+        "VersionDownloadCount" => metadata.version_download_count = value.parse().ok(),
+        "PackageHash" => metadata.package_hash = Some(value).filter(|h| !h.is_empty()),
+        "PackageHashAlgorithm" => {
+            metadata.package_hash_algorithm = Some(value).filter(|a| !a.is_empty())
+        }
+        "Listed" => metadata.listed = Some(value.eq_ignore_ascii_case("true")),
+        "Dependencies" => {
+            metadata.dependency_groups = get_dependency_groups_from_odata(&value);
+            metadata.merge_dependency_groups();
+        }
+        // remaining properties are named like the nuspec elements (ProjectUrl -> projectUrl)
+        _ => {
+            let mut chars = name.chars();
+            if let Some(first) = chars.next() {
+                metadata.set(
+                    &format!("{}{}", first.to_lowercase(), chars.as_str()),
+                    value,
+                );
+            }
+        }
+    }
+}
+
+/// package metadata of each `<entry>` (`entry/m:properties/d:*`)
+pub(crate) fn get_metadata_from_odata(
+    feed_name: &str,
+    odata_xml: &str,
+) -> Result<Vec<PackageMetadata>, RocoError> {
+    let mut packages = Vec::new();
+    let mut reader = Reader::from_str(odata_xml);
+    reader.trim_text(true);
+
+    let mut metadata: Option<PackageMetadata> = None;
+    let mut title = String::new();
+    let mut in_properties = false;
+    // current element below entry/m:properties or entry/title
+    let mut property: Option<String> = None;
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                match (name.as_str(), metadata.is_some(), in_properties) {
+                    ("entry", _, _) => metadata = Some(PackageMetadata::default()),
+                    ("properties", true, _) => in_properties = true,
+                    (_, true, true) | ("title", true, false) => property = Some(name),
+                    _ => (),
+                }
+                text.clear();
+            }
+            Ok(Event::Text(e)) if property.is_some() => match e.unescape() {
+                Ok(t) => text.push_str(&t),
+                Err(_) => text.push_str(&String::from_utf8_lossy(&e)),
+            },
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"entry" => {
+                    if let Some(mut m) = metadata.take() {
+                        if m.id.is_empty() {
+                            m.id = std::mem::take(&mut title);
+                        }
+                        // nuget.org marks unlisted packages with this publish date
+                        if m.listed.is_none() && m.published.is_some() {
+                            m.listed = m.published.as_ref().map(|p| !p.starts_with("1900-01-01"));
+                        }
+                        packages.push(m);
+                    }
+                }
+                b"properties" => in_properties = false,
+                _ => match (property.take(), metadata.as_mut()) {
+                    (Some(name), Some(m)) if in_properties => {
+                        set_odata_property(m, &name, std::mem::take(&mut text))
+                    }
+                    (Some(_), Some(_)) => title = std::mem::take(&mut text),
+                    _ => (),
+                },
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(RocoError::feed_response(feed_name, e)),
+            _ => (),
+        }
+    }
+    Ok(packages)
+}
+
+/// url of the next page (`<link rel="next" href="..." />`)
+pub(crate) fn get_next_link_from_odata(odata_xml: &str) -> Option<String> {
+    let mut reader = Reader::from_str(odata_xml);
+    reader.trim_text(true);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if e.local_name().as_ref() == b"link" =>
+            {
+                let attrib_map = xml_attribs_to_map(&mut e.attributes());
+                if attrib_map.get("rel").map(|r| r.as_str()) == Some("next") {
+                    return attrib_map.get("href").map(|h| h.replace("&amp;", "&"));
+                }
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => (),
+        }
+    }
+}
+
 pub(crate) fn get_packages_from_odata(odata_xml: &str) -> Vec<Package> {
     let mut packages = Vec::new();
     let mut pkg_name = String::new();
@@ -116,26 +400,24 @@ pub(crate) fn get_packages_from_odata(odata_xml: &str) -> Vec<Package> {
         match reader.read_event_into(&mut buf) {
             Ok(Event::Start(ref e)) => match e.name().as_ref() {
                 b"entry" => state = ODataParserState::InEntry,
-                b"title" => match state {
-                    ODataParserState::InEntry => {
+                b"title" => {
+                    if let ODataParserState::InEntry = state {
                         state = ODataParserState::InEntryId;
                     }
-                    _ => {}
-                },
-                b"d:Version" => match state {
-                    ODataParserState::InEntry => {
+                }
+                b"d:Version" => {
+                    if let ODataParserState::InEntry = state {
                         state = ODataParserState::InEntryVersion;
                     }
-                    _ => {}
-                },
+                }
                 _ => {}
             },
             Ok(Event::Text(e)) => match state {
                 ODataParserState::InEntryId => {
-                    pkg_name = String::from_utf8(e.to_vec()).unwrap();
+                    pkg_name = String::from_utf8_lossy(&e).to_string();
                 }
                 ODataParserState::InEntryVersion => {
-                    pkg_version = String::from_utf8(e.to_vec()).unwrap()
+                    pkg_version = String::from_utf8_lossy(&e).to_string()
                 }
                 _ => (),
             },
@@ -149,22 +431,20 @@ pub(crate) fn get_packages_from_odata(odata_xml: &str) -> Vec<Package> {
                         id: pkg_name.to_string(),
                         version: pkg_version.to_string(),
                         pinned: false,
-                        dependencies: None
+                        dependencies: None,
                     });
                     state = ODataParserState::LookingForEntry;
                 }
-                b"title" => match state {
-                    ODataParserState::InEntryId => {
+                b"title" => {
+                    if let ODataParserState::InEntryId = state {
                         state = ODataParserState::InEntry;
                     }
-                    _ => {}
-                },
-                b"d:Version" => match state {
-                    ODataParserState::InEntryVersion => {
+                }
+                b"d:Version" => {
+                    if let ODataParserState::InEntryVersion = state {
                         state = ODataParserState::InEntry;
                     }
-                    _ => {}
-                },
+                }
                 _ => {}
             },
             Ok(Event::Eof) => break,
@@ -175,3 +455,100 @@ pub(crate) fn get_packages_from_odata(odata_xml: &str) -> Vec<Package> {
 
     packages
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ODATA_PAGE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xml:base="https://example.org/api/v2/" xmlns="http://www.w3.org/2005/Atom" xmlns:d="http://schemas.microsoft.com/ado/2007/08/dataservices" xmlns:m="http://schemas.microsoft.com/ado/2007/08/dataservices/metadata">
+  <title type="text">Packages</title>
+  <link rel="self" title="Packages" href="Packages" />
+  <entry>
+    <title type="text">git</title>
+    <link rel="edit" href="Packages(Id='git',Version='2.40.0')" />
+    <m:properties><d:Version>2.40.0</d:Version></m:properties>
+  </entry>
+  <entry>
+    <title type="text">git</title>
+    <m:properties><d:Version>2.41.0-rc1</d:Version></m:properties>
+  </entry>
+  <link rel="next" href="https://example.org/api/v2/FindPackagesById()?id='git'&amp;$skip=2" />
+</feed>"#;
+
+    #[test]
+    fn odata_paging_test() {
+        let packages = get_packages_from_odata(ODATA_PAGE);
+        assert_eq!(
+            packages
+                .iter()
+                .map(|p| format!("{}|{}", p.id, p.version))
+                .collect::<Vec<String>>(),
+            ["git|2.40.0", "git|2.41.0-rc1"]
+        );
+        assert_eq!(
+            get_next_link_from_odata(ODATA_PAGE).as_deref(),
+            Some("https://example.org/api/v2/FindPackagesById()?id='git'&$skip=2")
+        );
+        assert_eq!(get_next_link_from_odata("<feed></feed>"), None);
+        assert!(is_prerelease("2.41.0-rc1"));
+        assert!(!is_prerelease("2.40.0"));
+        assert_eq!(odata_string("o'neil"), "'o''neil'");
+    }
+
+    #[test]
+    fn get_metadata_from_odata_test() {
+        let odata_xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<entry xml:base="https://example.org/api/v2/" xmlns="http://www.w3.org/2005/Atom" xmlns:d="http://schemas.microsoft.com/ado/2007/08/dataservices" xmlns:m="http://schemas.microsoft.com/ado/2007/08/dataservices/metadata">
+  <id>https://example.org/api/v2/Packages(Id='git',Version='2.40.0')</id>
+  <title type="text">git</title>
+  <summary type="text">distributed version control</summary>
+  <m:properties>
+    <d:Version>2.40.0</d:Version>
+    <d:Title>Git</d:Title>
+    <d:Authors>Git contributors</d:Authors>
+    <d:ProjectUrl>https://git-scm.com/</d:ProjectUrl>
+    <d:Tags> git vcs </d:Tags>
+    <d:Dependencies>chocolatey-core.extension:1.3.3:|git.install:[2.40.0]:</d:Dependencies>
+    <d:Published m:type="Edm.DateTime">2023-03-14T17:12:34.187</d:Published>
+    <d:DownloadCount m:type="Edm.Int32">12345</d:DownloadCount>
+    <d:VersionDownloadCount m:type="Edm.Int32">42</d:VersionDownloadCount>
+    <d:PackageHash>abc==</d:PackageHash>
+    <d:PackageHashAlgorithm>SHA512</d:PackageHashAlgorithm>
+    <d:IconUrl m:null="true" />
+    <d:ReleaseNotes>fixes &amp; more</d:ReleaseNotes>
+  </m:properties>
+</entry>"#;
+        let packages = get_metadata_from_odata("feed", odata_xml).unwrap();
+        assert_eq!(packages.len(), 1);
+        let m = &packages[0];
+        assert_eq!((m.id.as_str(), m.version.as_str()), ("git", "2.40.0"));
+        assert_eq!(m.title.as_deref(), Some("Git"));
+        assert_eq!(m.project_url.as_deref(), Some("https://git-scm.com/"));
+        assert_eq!(m.tags, ["git", "vcs"]);
+        assert_eq!(m.release_notes.as_deref(), Some("fixes & more"));
+        assert_eq!(m.icon_url, None);
+        assert_eq!(m.download_count, Some(12345));
+        assert_eq!(m.version_download_count, Some(42));
+        assert_eq!(m.package_hash.as_deref(), Some("abc=="));
+        assert_eq!(m.listed, Some(true));
+        assert_eq!(m.dependency_groups.len(), 1);
+        assert_eq!(
+            m.dependencies
+                .iter()
+                .map(|d| format!("{} {}", d.id, d.version))
+                .collect::<Vec<String>>(),
+            ["chocolatey-core.extension 1.3.3", "git.install [2.40.0]"]
+        );
+
+        let unlisted = odata_xml.replace("2023-03-14T17:12:34.187", "1900-01-01T00:00:00");
+        let packages = get_metadata_from_odata("feed", &unlisted).unwrap();
+        assert_eq!(packages[0].listed, Some(false));
+
+        let groups = get_dependency_groups_from_odata("a:1.0:net45|::net6.0|b::net45");
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].target_framework.as_deref(), Some("net45"));
+        assert_eq!(groups[0].dependencies.len(), 2);
+        assert!(groups[1].dependencies.is_empty());
+    }
+}
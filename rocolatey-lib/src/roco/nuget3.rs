@@ -1,12 +1,15 @@
 use crate::{
     println_verbose,
-    roco::{Feed, Package},
+    roco::{
+        nuspec::DependencyGroup, search::SearchOptions, Feed, NuGetVersion, Package,
+        PackageMetadata, RocoError,
+    },
 };
 
 use serde::Deserialize;
 use serde_json::{self};
 
-use super::remote::invoke_package_bulk_request;
+use super::remote::{build_reqwest, invoke_package_bulk_request};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct NuGetV3Index {
@@ -30,17 +33,17 @@ pub struct QueryResult {
 pub struct QueryResultPackage {
     id: String,
     version: String,
+    versions: Option<Vec<QueryResultVersion>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QueryResultVersion {
+    version: String,
 }
 
 fn get_resource<'f>(feed: &'f Feed, resource_type: &str) -> Option<Vec<&'f NuGetResource>> {
-    if feed.service_index.is_none() {
-        return None;
-    }
-    let idx = feed.service_index.as_ref().unwrap();
-    if idx.resources.is_none() {
-        return None;
-    }
-    let res = idx.resources.as_ref().unwrap();
+    let idx = feed.service_index.as_ref()?;
+    let res = idx.resources.as_ref()?;
     Some(
         res.iter()
             .filter(|e| e.resource_type == resource_type)
@@ -48,28 +51,28 @@ fn get_resource<'f>(feed: &'f Feed, resource_type: &str) -> Option<Vec<&'f NuGet
     )
 }
 
+fn get_search_query_service(feed: &Feed) -> Result<&str, RocoError> {
+    // TODO: handle multiple service URLs (secondary/ fallback?)
+    get_resource(feed, "SearchQueryService")
+        .as_ref()
+        .and_then(|s| s.first())
+        .map(|s| s.id.as_str())
+        .ok_or_else(|| RocoError::feed_response(&feed.name, "SearchQueryService missing"))
+}
+
 // https://learn.microsoft.com/en-us/nuget/api/overview
 
 pub(crate) async fn get_remote_packages(
     pkgs: &[Package],
     feed: &Feed,
     prerelease: bool,
-) -> Result<Vec<Package>, Box<dyn std::error::Error>> {
+) -> Result<Vec<Package>, RocoError> {
     // r"SearchQueryService/3.5.0"
     // GET {@id}?q={QUERY}&prerelease={PRERELEASE}
     // https://azuresearch-usnc.nuget.org/query?q=packageid:chocolatey&prerelease=true
     // https://azuresearch-usnc.nuget.org/query?q=packageid:chocolatey%20packageid:chocolatey.lib&prerelease=true
 
-    let search_query_service: Option<Vec<&NuGetResource>> =
-        get_resource(feed, "SearchQueryService");
-
-    if search_query_service.is_none() {
-        Err(r"SearchQueryService missing")?
-    }
-
-    // TODO: handle multiple service URLs (secondary/ fallback?)
-    let service = search_query_service.as_ref().unwrap().first();
-    let service = &service.unwrap().id;
+    let service = get_search_query_service(feed)?;
 
     println_verbose(&format!("query NuGet v3 '{}' => {}", feed.name, service));
 
@@ -90,33 +93,437 @@ pub(crate) async fn get_remote_packages(
         |p| format!("packageid:{}", p.id),
         &query_str_delim,
         &query_str_end,
-        |pkgs, batch_str| -> () {
-            extract_packages(pkgs, batch_str);
-        },
+        extract_packages,
     )
     .await
 }
 
-fn extract_packages(pkgs_res: &mut Vec<Package>, resp: &String) {
-    let query_result: QueryResult = serde_json::from_str(resp).unwrap();
-    match query_result.data {
-        Some(pkgs) => {
-            pkgs.iter().for_each(|p| {
-                pkgs_res.push(Package {
-                    id: p.id.clone(),
-                    version: p.version.clone(),
-                    pinned: false,
-                    dependencies: None
+fn extract_packages(
+    feed_name: &str,
+    pkgs_res: &mut Vec<Package>,
+    resp: &str,
+) -> Result<(), RocoError> {
+    let query_result: QueryResult =
+        serde_json::from_str(resp).map_err(|e| RocoError::feed_response(feed_name, e))?;
+    if let Some(pkgs) = query_result.data {
+        pkgs.iter().for_each(|p| {
+            pkgs_res.push(Package {
+                id: p.id.clone(),
+                version: p.version.clone(),
+                pinned: false,
+                dependencies: None,
+            })
+        });
+    }
+    Ok(())
+}
+
+// https://learn.microsoft.com/en-us/nuget/api/search-query-service-resource
+pub(crate) async fn search_packages(
+    feed: &Feed,
+    options: &SearchOptions,
+) -> Result<Vec<Package>, RocoError> {
+    let service = get_search_query_service(feed)?;
+    let query = match options.exact {
+        true => format!("packageid:{}", options.term),
+        false => options.term.clone(),
+    };
+    let url = reqwest::Url::parse_with_params(
+        service,
+        &[
+            ("q", query),
+            ("skip", "0".to_string()),
+            ("take", options.limit().to_string()),
+            ("prerelease", options.prerelease.to_string()),
+            ("semVerLevel", "2.0.0".to_string()),
+        ],
+    )
+    .map_err(|e| RocoError::feed_response(&feed.name, e))?;
+    println_verbose(&format!(" -> GET: {}", url));
+    let resp = build_reqwest(feed)?
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| RocoError::from_reqwest(&feed.name, e))?
+        .text()
+        .await
+        .map_err(|e| RocoError::from_reqwest(&feed.name, e))?;
+    let mut packages = get_search_results(&feed.name, &resp, options.all_versions)?;
+    if options.exact {
+        packages.retain(|p| p.id.eq_ignore_ascii_case(&options.term));
+    }
+    Ok(packages)
+}
+
+/// the latest version of each search result, or all of its versions
+fn get_search_results(
+    feed_name: &str,
+    resp: &str,
+    all_versions: bool,
+) -> Result<Vec<Package>, RocoError> {
+    let query_result: QueryResult =
+        serde_json::from_str(resp).map_err(|e| RocoError::feed_response(feed_name, e))?;
+    let package = |id: &str, version: &str| Package {
+        id: id.to_string(),
+        version: version.to_string(),
+        pinned: false,
+        dependencies: None,
+    };
+    Ok(query_result
+        .data
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|p| match (&p.versions, all_versions) {
+            (Some(versions), true) => versions
+                .iter()
+                .map(|v| package(&p.id, &v.version))
+                .collect::<Vec<Package>>(),
+            _ => vec![package(&p.id, &p.version)],
+        })
+        .collect())
+}
+
+fn get_registrations_base_url(feed: &Feed) -> Result<&str, RocoError> {
+    // prefer the uncompressed hives, the gzipped ones (3.4.0/3.6.0) are served with
+    // 'Content-Encoding: gzip' regardless of what the client accepts
+    [
+        "RegistrationsBaseUrl",
+        "RegistrationsBaseUrl/3.0.0-rc",
+        "RegistrationsBaseUrl/3.0.0-beta",
+        "RegistrationsBaseUrl/3.6.0",
+    ]
+    .iter()
+    .filter_map(|t| get_resource(feed, t).and_then(|r| r.first().copied()))
+    .map(|r| r.id.as_str())
+    .next()
+    .ok_or_else(|| RocoError::feed_response(&feed.name, "RegistrationsBaseUrl missing"))
+}
+
+async fn get_json(feed: &Feed, url: &str) -> Result<serde_json::Value, RocoError> {
+    println_verbose(&format!(" -> GET: {}", url));
+    let resp = build_reqwest(feed)?
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| RocoError::from_reqwest(&feed.name, e))?
+        .text()
+        .await
+        .map_err(|e| RocoError::from_reqwest(&feed.name, e))?;
+    serde_json::from_str(&resp).map_err(|e| RocoError::feed_response(&feed.name, e))
+}
+
+// https://learn.microsoft.com/en-us/nuget/api/registration-base-url-resource
+/// metadata of all versions of a package (registration leaves)
+pub(crate) async fn get_package_metadata(
+    feed: &Feed,
+    id: &str,
+) -> Result<Vec<PackageMetadata>, RocoError> {
+    let base_url = get_registrations_base_url(feed)?;
+    let index_url = format!(
+        "{}/{}/index.json",
+        base_url.trim_end_matches('/'),
+        id.to_lowercase()
+    );
+    let index = match get_json(feed, &index_url).await {
+        Ok(index) => index,
+        Err(RocoError::HttpStatus { status: 404, .. }) => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+    let mut packages = Vec::new();
+    for page in index["items"].as_array().into_iter().flatten() {
+        // large registrations don't inline their pages
+        let page = match page.get("items") {
+            Some(_) => page.clone(),
+            None => match page["@id"].as_str() {
+                Some(page_url) => get_json(feed, page_url).await?,
+                None => continue,
+            },
+        };
+        packages.extend(get_metadata_from_registration_page(&page));
+    }
+    if let Ok(counts) = get_download_counts(feed, id).await {
+        for m in packages.iter_mut() {
+            m.download_count = counts.0;
+            m.version_download_count = counts.1.get(&m.version.to_lowercase()).copied();
+        }
+    }
+    Ok(packages)
+}
+
+// https://learn.microsoft.com/en-us/nuget/api/package-base-address-resource
+/// all versions of a package (flat container `{PackageBaseAddress}/{id}/index.json`)
+pub(crate) async fn get_package_versions(
+    feed: &Feed,
+    id: &str,
+    prerelease: bool,
+) -> Result<Vec<Package>, RocoError> {
+    let base_url = get_resource(feed, "PackageBaseAddress/3.0.0")
+        .and_then(|r| r.first().copied())
+        .map(|r| r.id.as_str())
+        .ok_or_else(|| RocoError::feed_response(&feed.name, "PackageBaseAddress missing"))?;
+    let url = format!(
+        "{}/{}/index.json",
+        base_url.trim_end_matches('/'),
+        id.to_lowercase()
+    );
+    match get_json(feed, &url).await {
+        Ok(index) => Ok(get_versions_from_flat_container(id, &index, prerelease)),
+        Err(RocoError::HttpStatus { status: 404, .. }) => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// the flat container only knows lowercase ids, the requested id is used instead
+fn get_versions_from_flat_container(
+    id: &str,
+    index: &serde_json::Value,
+    prerelease: bool,
+) -> Vec<Package> {
+    index["versions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+        .filter(|v| prerelease || v.parse::<NuGetVersion>().is_ok_and(|v| !v.is_prerelease()))
+        .map(|v| Package {
+            id: id.to_string(),
+            version: v.to_string(),
+            pinned: false,
+            dependencies: None,
+        })
+        .collect()
+}
+
+/// download counts (total, per lowercase version) are only provided by the search service
+async fn get_download_counts(
+    feed: &Feed,
+    id: &str,
+) -> Result<(Option<u64>, std::collections::HashMap<String, u64>), RocoError> {
+    let service = get_search_query_service(feed)?;
+    let url = reqwest::Url::parse_with_params(
+        service,
+        &[
+            ("q", format!("packageid:{}", id)),
+            ("prerelease", "true".to_string()),
+            ("semVerLevel", "2.0.0".to_string()),
+        ],
+    )
+    .map_err(|e| RocoError::feed_response(&feed.name, e))?;
+    let resp = get_json(feed, url.as_str()).await?;
+    Ok(get_download_counts_from_search(&resp, id))
+}
+
+fn get_download_counts_from_search(
+    resp: &serde_json::Value,
+    id: &str,
+) -> (Option<u64>, std::collections::HashMap<String, u64>) {
+    let package = resp["data"].as_array().and_then(|d| {
+        d.iter()
+            .find(|p| p["id"].as_str().is_some_and(|i| i.eq_ignore_ascii_case(id)))
+    });
+    match package {
+        Some(p) => (
+            p["totalDownloads"].as_u64(),
+            p["versions"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|v| {
+                    Some((
+                        v["version"].as_str()?.to_lowercase(),
+                        v["downloads"].as_u64()?,
+                    ))
                 })
-            });
+                .collect(),
+        ),
+        None => (None, Default::default()),
+    }
+}
+
+/// string or array of strings (e.g. 'authors', 'tags')
+fn json_strings(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::String(s) => vec![s.clone()],
+        serde_json::Value::Array(a) => a
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|v| v.to_string())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn get_metadata_from_catalog_entry(entry: &serde_json::Value) -> PackageMetadata {
+    let mut metadata = PackageMetadata::default();
+    // properties are named like the nuspec elements
+    for element in [
+        "id",
+        "version",
+        "title",
+        "summary",
+        "description",
+        "projectUrl",
+        "licenseUrl",
+        "iconUrl",
+        "releaseNotes",
+        "copyright",
+    ]
+    .iter()
+    {
+        if let Some(value) = entry[*element].as_str() {
+            metadata.set(element, value.to_string());
         }
-        None => {}
-    };
+    }
+    let authors = json_strings(&entry["authors"]).join(", ");
+    metadata.set("authors", authors);
+    metadata.tags = json_strings(&entry["tags"])
+        .iter()
+        .flat_map(|t| t.split_whitespace())
+        .map(|t| t.to_string())
+        .collect();
+    metadata.require_license_acceptance = entry["requireLicenseAcceptance"].as_bool() == Some(true);
+    if let Some(expression) = entry["licenseExpression"]
+        .as_str()
+        .filter(|e| !e.is_empty())
+    {
+        metadata.license = Some(expression.to_string());
+        metadata.license_type = Some("expression".to_string());
+    }
+    metadata.published = entry["published"].as_str().map(|p| p.to_string());
+    metadata.listed = entry["listed"].as_bool();
+    metadata.package_hash = entry["packageHash"].as_str().map(|h| h.to_string());
+    metadata.package_hash_algorithm = entry["packageHashAlgorithm"]
+        .as_str()
+        .map(|a| a.to_string());
+    metadata.dependency_groups = entry["dependencyGroups"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|g| DependencyGroup {
+            target_framework: g["targetFramework"]
+                .as_str()
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_string()),
+            dependencies: g["dependencies"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|d| {
+                    Some(Package {
+                        id: d["id"].as_str()?.to_string(),
+                        version: d["range"].as_str().unwrap_or_default().to_string(),
+                        pinned: false,
+                        dependencies: None,
+                    })
+                })
+                .collect(),
+        })
+        .collect();
+    metadata.merge_dependency_groups();
+    metadata
+}
+
+fn get_metadata_from_registration_page(page: &serde_json::Value) -> Vec<PackageMetadata> {
+    page["items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|leaf| get_metadata_from_catalog_entry(&leaf["catalogEntry"]))
+        .filter(|m| !m.id.is_empty())
+        .collect()
 }
 
 pub(crate) fn read_service_index(index_json: serde_json::Value) -> Option<NuGetV3Index> {
-    match serde_json::from_value(index_json) {
-        Ok(val) => Some(val),
-        Err(_) => None,
+    serde_json::from_value(index_json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_search_results_test() {
+        let resp = r#"{"totalHits": 2, "data": [
+            {"id": "git", "version": "2.41.0", "versions": [{"version": "2.40.0", "downloads": 1}, {"version": "2.41.0", "downloads": 2}]},
+            {"id": "git.portable", "version": "2.41.0"}
+        ]}"#;
+        let ids = |packages: Vec<Package>| {
+            packages
+                .iter()
+                .map(|p| format!("{}|{}", p.id, p.version))
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(
+            ids(get_search_results("feed", resp, false).unwrap()),
+            ["git|2.41.0", "git.portable|2.41.0"]
+        );
+        assert_eq!(
+            ids(get_search_results("feed", resp, true).unwrap()),
+            ["git|2.40.0", "git|2.41.0", "git.portable|2.41.0"]
+        );
+        assert!(get_search_results("feed", "{}", true).unwrap().is_empty());
+        assert!(matches!(
+            get_search_results("feed", "<html>", false),
+            Err(RocoError::FeedResponse { .. })
+        ));
+    }
+
+    #[test]
+    fn get_metadata_from_registration_test() {
+        let page: serde_json::Value = serde_json::from_str(
+            r#"{"@id": "https://example.org/reg/git/index.json#page/2.40.0/2.41.0", "items": [
+                {"catalogEntry": {
+                    "id": "Git", "version": "2.40.0", "title": "Git",
+                    "authors": ["a", "b"], "tags": ["git", "vcs"],
+                    "description": "distributed version control",
+                    "licenseExpression": "GPL-2.0-only", "listed": true,
+                    "published": "2023-03-14T17:12:34.187+00:00",
+                    "dependencyGroups": [
+                        {"targetFramework": ".NETFramework4.5", "dependencies": [{"id": "git.install", "range": "[2.40.0, )"}]},
+                        {"targetFramework": "net6.0"}
+                    ]
+                }},
+                {"catalogEntry": {"id": "Git", "version": "2.41.0-rc1", "authors": "someone", "tags": "a b", "listed": false}}
+            ]}"#,
+        )
+        .unwrap();
+        let packages = get_metadata_from_registration_page(&page);
+        assert_eq!(packages.len(), 2);
+        let m = &packages[0];
+        assert_eq!((m.id.as_str(), m.version.as_str()), ("Git", "2.40.0"));
+        assert_eq!(m.authors.as_deref(), Some("a, b"));
+        assert_eq!(m.tags, ["git", "vcs"]);
+        assert_eq!(m.license.as_deref(), Some("GPL-2.0-only"));
+        assert_eq!(m.listed, Some(true));
+        assert_eq!(m.dependency_groups.len(), 2);
+        assert_eq!(m.dependencies[0].version, "[2.40.0, )");
+        assert_eq!(packages[1].authors.as_deref(), Some("someone"));
+        assert_eq!(packages[1].tags, ["a", "b"]);
+        assert_eq!(packages[1].listed, Some(false));
+
+        let search: serde_json::Value = serde_json::from_str(
+            r#"{"data": [{"id": "git", "totalDownloads": 100, "versions": [{"version": "2.40.0", "downloads": 60}]}]}"#,
+        )
+        .unwrap();
+        let (total, versions) = get_download_counts_from_search(&search, "Git");
+        assert_eq!(total, Some(100));
+        assert_eq!(versions.get("2.40.0"), Some(&60));
+    }
+
+    #[test]
+    fn get_versions_from_flat_container_test() {
+        let index: serde_json::Value =
+            serde_json::from_str(r#"{"versions": ["2.39.0", "2.40.0", "2.41.0-rc1"]}"#).unwrap();
+        let versions = |prerelease| {
+            get_versions_from_flat_container("Git", &index, prerelease)
+                .iter()
+                .map(|p| format!("{}|{}", p.id, p.version))
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(versions(false), ["Git|2.39.0", "Git|2.40.0"]);
+        assert_eq!(versions(true).len(), 3);
+        assert!(get_versions_from_flat_container("git", &serde_json::Value::Null, true).is_empty());
     }
 }
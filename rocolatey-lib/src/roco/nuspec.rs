@@ -0,0 +1,529 @@
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::NsReader;
+
+use crate::roco::{xml_attribs_to_map, Package, RocoError};
+
+/// all nuspec schema versions live below this namespace
+/// (e.g. http://schemas.microsoft.com/packaging/2015/06/nuspec.xsd)
+const NUSPEC_NAMESPACE_PREFIX: &[u8] = b"http://schemas.microsoft.com/packaging/";
+
+/// dependencies of a `<group>` (or of `<dependencies>` itself, without target framework)
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGroup {
+    pub target_framework: Option<String>,
+    pub dependencies: Vec<Package>,
+}
+
+/// the `<metadata>` of a nuspec
+#[derive(Debug, Clone, Default)]
+pub struct PackageMetadata {
+    pub id: String,
+    pub version: String,
+    pub title: Option<String>,
+    pub authors: Option<String>,
+    pub owners: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub project_url: Option<String>,
+    pub project_source_url: Option<String>,
+    pub package_source_url: Option<String>,
+    pub docs_url: Option<String>,
+    pub bug_tracker_url: Option<String>,
+    pub license_url: Option<String>,
+    /// `<license>` element (license expression or file), see `license_type`
+    pub license: Option<String>,
+    /// type attribute of the `<license>` element ('expression' or 'file')
+    pub license_type: Option<String>,
+    pub tags: Vec<String>,
+    pub release_notes: Option<String>,
+    pub require_license_acceptance: bool,
+    pub icon_url: Option<String>,
+    pub copyright: Option<String>,
+    /// package id + version range (may be empty) of each dependency of any target framework
+    /// (first declaration wins if a package is listed in several groups)
+    pub dependencies: Vec<Package>,
+    pub dependency_groups: Vec<DependencyGroup>,
+    /// provided by remote feeds only (if at all)
+    pub published: Option<String>,
+    /// downloads of all versions
+    pub download_count: Option<u64>,
+    pub version_download_count: Option<u64>,
+    pub package_hash: Option<String>,
+    pub package_hash_algorithm: Option<String>,
+    pub listed: Option<bool>,
+}
+
+fn xml_text(e: &BytesText) -> String {
+    match e.unescape() {
+        Ok(text) => text.to_string(),
+        Err(_) => String::from_utf8_lossy(e).to_string(),
+    }
+}
+
+/// elements without namespace or in one of the nuspec namespaces
+fn is_nuspec_namespace(ns: &ResolveResult) -> bool {
+    match ns {
+        ResolveResult::Unbound => true,
+        ResolveResult::Bound(Namespace(ns)) => ns.starts_with(NUSPEC_NAMESPACE_PREFIX),
+        ResolveResult::Unknown(_) => false,
+    }
+}
+
+/// element path below the document root, elements of foreign namespaces are kept as ""
+/// so nothing inside of them is matched
+fn is_path(path: &[String], expected: &[&str]) -> bool {
+    path.len() == expected.len() && path.iter().zip(expected).all(|(a, b)| a == b)
+}
+
+fn dependency_from_element(origin: &str, e: &BytesStart) -> Result<Package, RocoError> {
+    let attrib_map = xml_attribs_to_map(&mut e.attributes());
+    let id = attrib_map
+        .get("id")
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| RocoError::nuspec_parse(origin, "dependency without 'id' attribute"))?;
+    Ok(Package {
+        id: id.to_string(),
+        version: attrib_map.get("version").cloned().unwrap_or_default(),
+        pinned: false,
+        dependencies: None,
+    })
+}
+
+impl PackageMetadata {
+    pub fn from_nuspec_text(origin: &str, content: &[u8]) -> Result<PackageMetadata, RocoError> {
+        const METADATA: [&str; 2] = ["package", "metadata"];
+        const DEPENDENCIES: [&str; 3] = ["package", "metadata", "dependencies"];
+        const GROUP: [&str; 4] = ["package", "metadata", "dependencies", "group"];
+
+        let mut metadata = PackageMetadata::default();
+        let mut ungrouped = DependencyGroup::default();
+        let mut group: Option<DependencyGroup> = None;
+
+        let mut reader = NsReader::from_reader(content);
+        reader.trim_text(true);
+        let mut buf = Vec::new();
+        let mut path: Vec<String> = Vec::new();
+        let mut text = String::new();
+
+        loop {
+            let (ns, event) = reader
+                .read_resolved_event_into(&mut buf)
+                .map_err(|e| RocoError::nuspec_parse(origin, e))?;
+            match event {
+                Event::Start(ref e) | Event::Empty(ref e) => {
+                    let name = match is_nuspec_namespace(&ns) {
+                        true => String::from_utf8_lossy(e.local_name().as_ref()).to_string(),
+                        false => String::new(),
+                    };
+                    let parent = path.as_slice();
+                    if is_path(parent, &METADATA) && name == "license" {
+                        metadata.license_type =
+                            xml_attribs_to_map(&mut e.attributes()).get("type").cloned();
+                    } else if is_path(parent, &DEPENDENCIES) && name == "group" {
+                        group = Some(DependencyGroup {
+                            target_framework: xml_attribs_to_map(&mut e.attributes())
+                                .get("targetFramework")
+                                .cloned()
+                                .filter(|t| !t.is_empty()),
+                            dependencies: Vec::new(),
+                        });
+                    } else if is_path(parent, &DEPENDENCIES) && name == "dependency" {
+                        ungrouped
+                            .dependencies
+                            .push(dependency_from_element(origin, e)?);
+                    } else if is_path(parent, &GROUP) && name == "dependency" {
+                        if let Some(group) = group.as_mut() {
+                            group.dependencies.push(dependency_from_element(origin, e)?);
+                        }
+                    }
+                    text.clear();
+                    if matches!(event, Event::Start(_)) {
+                        path.push(name);
+                    } else if is_path(parent, &DEPENDENCIES) && name == "group" {
+                        // <group /> without dependencies
+                        metadata.dependency_groups.extend(group.take());
+                    }
+                }
+                Event::Text(e) if path.len() == 3 => text.push_str(&xml_text(&e)),
+                Event::CData(e) if path.len() == 3 => text.push_str(&String::from_utf8_lossy(&e)),
+                Event::End(_) => {
+                    if path.len() == 3 && is_path(&path[..2], &METADATA) {
+                        metadata.set(&path[2], std::mem::take(&mut text));
+                    } else if is_path(&path, &GROUP) {
+                        metadata.dependency_groups.extend(group.take());
+                    }
+                    path.pop();
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+            buf.clear();
+        }
+
+        if metadata.id.trim().is_empty() {
+            return Err(RocoError::nuspec_parse(
+                origin,
+                "missing or empty <id> in package/metadata",
+            ));
+        }
+
+        if !ungrouped.dependencies.is_empty() {
+            metadata.dependency_groups.insert(0, ungrouped);
+        }
+        metadata.merge_dependency_groups();
+
+        Ok(metadata)
+    }
+
+    /// fills `dependencies` from `dependency_groups`
+    pub(crate) fn merge_dependency_groups(&mut self) {
+        self.dependencies.clear();
+        for d in self
+            .dependency_groups
+            .iter()
+            .flat_map(|g| g.dependencies.iter())
+        {
+            if !self
+                .dependencies
+                .iter()
+                .any(|e| e.id.eq_ignore_ascii_case(&d.id))
+            {
+                self.dependencies.push(d.clone());
+            }
+        }
+    }
+
+    /// sets a field by its nuspec element name (e.g. 'projectUrl')
+    pub(crate) fn set(&mut self, element: &str, value: String) {
+        let field = match element {
+            "id" => {
+                self.id = value;
+                return;
+            }
+            "version" => {
+                self.version = value;
+                return;
+            }
+            "tags" => {
+                self.tags = value.split_whitespace().map(|t| t.to_string()).collect();
+                return;
+            }
+            "requireLicenseAcceptance" => {
+                self.require_license_acceptance = value.trim().eq_ignore_ascii_case("true");
+                return;
+            }
+            "title" => &mut self.title,
+            "authors" => &mut self.authors,
+            "owners" => &mut self.owners,
+            "summary" => &mut self.summary,
+            "description" => &mut self.description,
+            "projectUrl" => &mut self.project_url,
+            "projectSourceUrl" => &mut self.project_source_url,
+            "packageSourceUrl" => &mut self.package_source_url,
+            "docsUrl" => &mut self.docs_url,
+            "bugTrackerUrl" => &mut self.bug_tracker_url,
+            "licenseUrl" => &mut self.license_url,
+            "license" => &mut self.license,
+            "releaseNotes" => &mut self.release_notes,
+            "iconUrl" => &mut self.icon_url,
+            "copyright" => &mut self.copyright,
+            _ => return,
+        };
+        if !value.is_empty() {
+            *field = Some(value);
+        }
+    }
+
+    /// the package (id, version, dependencies) described by this metadata
+    pub fn to_package(&self, pinned: bool) -> Package {
+        Package {
+            id: self.id.clone(),
+            version: self.version.clone(),
+            pinned,
+            dependencies: match self.dependencies.is_empty() {
+                true => None,
+                false => Some(self.dependencies.clone()),
+            },
+        }
+    }
+
+    /// 'key|value' lines for limitoutput, otherwise formatted like `choco info`
+    pub fn to_text(&self, limitoutput: bool) -> String {
+        let tags = self.tags.join(" ");
+        let dependencies = self
+            .dependencies
+            .iter()
+            .map(|d| match d.version.is_empty() {
+                true => d.id.clone(),
+                false => format!("{} {}", d.id, d.version),
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+        let require_license_acceptance = self.require_license_acceptance.to_string();
+        let download_count = self.download_count.map(|c| c.to_string());
+        let version_download_count = self.version_download_count.map(|c| c.to_string());
+        let package_hash = match limitoutput {
+            true => self.package_hash.clone(),
+            false => self
+                .package_hash
+                .as_ref()
+                .map(|h| match &self.package_hash_algorithm {
+                    Some(algorithm) => format!("'{}' ({})", h, algorithm),
+                    None => format!("'{}'", h),
+                }),
+        };
+        let listed = self.listed.map(|l| l.to_string());
+        // choco shows the license url if there is no license expression/file
+        let license = match limitoutput {
+            true => self.license.as_ref(),
+            false => self.license.as_ref().or(self.license_url.as_ref()),
+        };
+        // key (limitoutput), label (None: limitoutput only), value
+        let fields: [(&str, Option<&str>, Option<&String>); 25] = [
+            ("id", None, Some(&self.id)),
+            ("version", None, Some(&self.version)),
+            ("title", Some("Title"), self.title.as_ref()),
+            ("published", Some("Published"), self.published.as_ref()),
+            ("listed", Some("Listed"), listed.as_ref()),
+            (
+                "downloadCount",
+                Some("Number of Downloads"),
+                download_count.as_ref(),
+            ),
+            (
+                "versionDownloadCount",
+                Some("Downloads for this version"),
+                version_download_count.as_ref(),
+            ),
+            (
+                "packageHash",
+                Some("Package Checksum"),
+                package_hash.as_ref(),
+            ),
+            (
+                "packageHashAlgorithm",
+                None,
+                self.package_hash_algorithm.as_ref(),
+            ),
+            ("authors", Some("Software Author(s)"), self.authors.as_ref()),
+            ("owners", Some("Maintainer(s)"), self.owners.as_ref()),
+            ("tags", Some("Tags"), Some(&tags).filter(|t| !t.is_empty())),
+            (
+                "projectUrl",
+                Some("Software Site"),
+                self.project_url.as_ref(),
+            ),
+            ("license", Some("Software License"), license),
+            ("licenseUrl", None, self.license_url.as_ref()),
+            (
+                "requireLicenseAcceptance",
+                Some("Require License Acceptance"),
+                Some(&require_license_acceptance).filter(|_| self.require_license_acceptance),
+            ),
+            (
+                "projectSourceUrl",
+                Some("Software Source"),
+                self.project_source_url.as_ref(),
+            ),
+            (
+                "packageSourceUrl",
+                Some("Chocolatey Package Source"),
+                self.package_source_url.as_ref(),
+            ),
+            ("docsUrl", Some("Documentation"), self.docs_url.as_ref()),
+            (
+                "bugTrackerUrl",
+                Some("Issues"),
+                self.bug_tracker_url.as_ref(),
+            ),
+            ("iconUrl", Some("Icon"), self.icon_url.as_ref()),
+            ("copyright", Some("Copyright"), self.copyright.as_ref()),
+            (
+                "dependencies",
+                Some("Dependencies"),
+                Some(&dependencies).filter(|d| !d.is_empty()),
+            ),
+            ("summary", Some("Summary"), self.summary.as_ref()),
+            (
+                "description",
+                Some("Description"),
+                self.description.as_ref(),
+            ),
+        ];
+        let release_notes = (
+            "releaseNotes",
+            Some("Release Notes"),
+            self.release_notes.as_ref(),
+        );
+
+        let mut lines = Vec::new();
+        if !limitoutput {
+            lines.push(format!("{} {}", self.id, self.version));
+        }
+        for (key, label, value) in fields.iter().chain([release_notes].iter()) {
+            let value = match value {
+                Some(value) => value,
+                None => continue,
+            };
+            match (limitoutput, label) {
+                (true, _) => {
+                    // values are single line in limitoutput mode
+                    let value: Vec<&str> = value.lines().map(|l| l.trim()).collect();
+                    lines.push(format!("{}|{}", key, value.join(" ")));
+                }
+                (false, Some(label)) => {
+                    lines.push(format!(" {}: {}", label, value.replace('\n', "\r\n  ")))
+                }
+                (false, None) => {}
+            }
+        }
+        lines.join("\r\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NUSPEC: &str = concat!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+        "<package xmlns=\"http://schemas.microsoft.com/packaging/2015/06/nuspec.xsd\">\n",
+        "  <metadata>\n",
+        "    <id>demo</id>\n",
+        "    <version>1.2.3</version>\n",
+        "    <title>Demo &amp; Co</title>\n",
+        "    <authors>someone</authors>\n",
+        "    <owners>someone else</owners>\n",
+        "    <projectUrl>https://example.org/demo</projectUrl>\n",
+        "    <license type=\"expression\">MIT</license>\n",
+        "    <requireLicenseAcceptance>true</requireLicenseAcceptance>\n",
+        "    <tags>demo  test admin</tags>\n",
+        "    <summary>a demo package</summary>\n",
+        "    <description><![CDATA[line 1\n",
+        "line <2>]]></description>\n",
+        "    <releaseNotes></releaseNotes>\n",
+        "    <dependencies>\n",
+        "      <dependency id=\"lib\" version=\"[1.0,2.0)\" />\n",
+        "      <dependency id=\"other\" />\n",
+        "    </dependencies>\n",
+        "  </metadata>\n",
+        "</package>\n",
+    );
+
+    #[test]
+    fn package_metadata_test() {
+        let m = PackageMetadata::from_nuspec_text("demo.nuspec", NUSPEC.as_bytes()).unwrap();
+        assert_eq!(m.id, "demo");
+        assert_eq!(m.version, "1.2.3");
+        assert_eq!(m.title.as_deref(), Some("Demo & Co"));
+        assert_eq!(m.owners.as_deref(), Some("someone else"));
+        assert_eq!(m.license.as_deref(), Some("MIT"));
+        assert_eq!(m.license_type.as_deref(), Some("expression"));
+        assert_eq!(m.license_url, None);
+        assert!(m.require_license_acceptance);
+        assert_eq!(m.tags, vec!["demo", "test", "admin"]);
+        assert_eq!(m.description.as_deref(), Some("line 1\nline <2>"));
+        assert_eq!(m.release_notes, None);
+        assert_eq!(m.dependencies.len(), 2);
+
+        assert_eq!(m.dependency_groups.len(), 1);
+        assert_eq!(m.dependency_groups[0].target_framework, None);
+
+        let p = m.to_package(true);
+        assert_eq!(p.dependencies.unwrap()[0].version, "[1.0,2.0)");
+
+        assert_eq!(
+            m.to_text(true),
+            [
+                "id|demo",
+                "version|1.2.3",
+                "title|Demo & Co",
+                "authors|someone",
+                "owners|someone else",
+                "tags|demo test admin",
+                "projectUrl|https://example.org/demo",
+                "license|MIT",
+                "requireLicenseAcceptance|true",
+                "dependencies|lib [1.0,2.0), other",
+                "summary|a demo package",
+                "description|line 1 line <2>",
+            ]
+            .join("\r\n")
+        );
+        assert!(m
+            .to_text(false)
+            .starts_with("demo 1.2.3\r\n Title: Demo & Co\r\n Software Author(s): someone\r\n"));
+        assert!(m
+            .to_text(false)
+            .ends_with(" Description: line 1\r\n  line <2>"));
+    }
+
+    #[test]
+    fn namespaced_nuspec_test() {
+        let nuspec = concat!(
+            "<?xml version=\"1.0\"?>\n",
+            "<ns:package xmlns:ns=\"http://schemas.microsoft.com/packaging/2013/05/nuspec.xsd\"",
+            " xmlns:x=\"urn:other\">\n",
+            "  <ns:metadata>\n",
+            "    <ns:id>grouped</ns:id>\n",
+            "    <ns:version>2.0</ns:version>\n",
+            "    <x:version>9.9</x:version>\n",
+            "    <ns:repository type=\"git\"><ns:version>8.8</ns:version></ns:repository>\n",
+            "    <ns:dependencies>\n",
+            "      <ns:group targetFramework=\"net45\">\n",
+            "        <ns:dependency id=\"lib\" version=\"1.0\"></ns:dependency>\n",
+            "        <ns:dependency id=\"other\" />\n",
+            "      </ns:group>\n",
+            "      <ns:group targetFramework=\"netstandard2.0\">\n",
+            "        <ns:dependency id=\"LIB\" version=\"2.0\" />\n",
+            "      </ns:group>\n",
+            "      <ns:group />\n",
+            "    </ns:dependencies>\n",
+            "  </ns:metadata>\n",
+            "  <ns:files><ns:file src=\"tools\\**\" /></ns:files>\n",
+            "  <ns:version>7.7</ns:version>\n",
+            "</ns:package>\n",
+        );
+        let m = PackageMetadata::from_nuspec_text("grouped.nuspec", nuspec.as_bytes()).unwrap();
+        assert_eq!(m.id, "grouped");
+        assert_eq!(m.version, "2.0");
+        let groups: Vec<(Option<&str>, usize)> = m
+            .dependency_groups
+            .iter()
+            .map(|g| (g.target_framework.as_deref(), g.dependencies.len()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![(Some("net45"), 2), (Some("netstandard2.0"), 1), (None, 0)]
+        );
+        let dependencies: Vec<(&str, &str)> = m
+            .dependencies
+            .iter()
+            .map(|d| (d.id.as_str(), d.version.as_str()))
+            .collect();
+        assert_eq!(dependencies, vec![("lib", "1.0"), ("other", "")]);
+    }
+
+    #[test]
+    fn invalid_nuspec_test() {
+        let parse = |metadata: &str| {
+            let nuspec = format!("<package><metadata>{}</metadata></package>", metadata);
+            PackageMetadata::from_nuspec_text("invalid.nuspec", nuspec.as_bytes())
+        };
+        assert!(parse("<id>ok</id><version>1.0</version>").is_ok());
+        for metadata in [
+            "<version>1.0</version>",
+            "<id></id><version>1.0</version>",
+            "<id> </id><version>1.0</version>",
+            "<x><id>nested</id></x><version>1.0</version>",
+            "<id>dep</id><dependencies><dependency version=\"1.0\" /></dependencies>",
+        ] {
+            assert!(
+                matches!(parse(metadata), Err(RocoError::NuspecParse { .. })),
+                "{}",
+                metadata
+            );
+        }
+    }
+}
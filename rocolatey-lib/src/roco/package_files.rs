@@ -0,0 +1,418 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use md5::Md5;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use sha2::{Digest, Sha256};
+
+use crate::println_verbose;
+use crate::roco::local::get_local_packages;
+use crate::roco::{xml_attribs_to_map, ChocoHome, Package, RocoError};
+
+// verification of installed files against the package information files choco writes on
+// install ($ChocolateyInstall/.chocolatey/<id>.<version>/.files)
+
+/// a file as recorded in `.files`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageFile {
+    /// absolute path at the time of installation (e.g. 'C:\ProgramData\chocolatey\lib\...')
+    pub path: String,
+    /// MD5 (choco default) or SHA256, hex encoded
+    pub checksum: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileProblem {
+    /// recorded, but does not exist anymore
+    Missing,
+    /// checksum differs from the recorded one
+    Modified { expected: String, actual: String },
+    /// exists in the package directory, but was not recorded
+    Unexpected,
+    /// the file (or the package's `.files`) could not be read
+    Unreadable { message: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileIssue {
+    pub package_id: String,
+    pub package_version: String,
+    pub path: PathBuf,
+    pub problem: FileProblem,
+}
+
+/// result of verifying the installed files of a single package
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackageVerification {
+    Verified(Vec<FileIssue>),
+    /// no `.files` recorded for this package (e.g. installed by an old choco version)
+    NoFileInfo,
+}
+
+pub fn parse_package_files_text(
+    origin: &str,
+    content: &[u8],
+) -> Result<Vec<PackageFile>, RocoError> {
+    let mut files = Vec::new();
+    let mut reader = Reader::from_reader(content);
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if e.local_name().as_ref() == b"file" =>
+            {
+                let attrib_map = xml_attribs_to_map(&mut e.attributes());
+                let path = attrib_map
+                    .get("path")
+                    .filter(|p| !p.is_empty())
+                    .ok_or_else(|| {
+                        RocoError::package_info_parse(origin, "file without 'path' attribute")
+                    })?;
+                files.push(PackageFile {
+                    path: path.to_string(),
+                    checksum: attrib_map.get("checksum").cloned().unwrap_or_default(),
+                });
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(RocoError::package_info_parse(origin, e)),
+            _ => (),
+        }
+        buf.clear();
+    }
+    Ok(files)
+}
+
+pub fn get_package_files(
+    home: &ChocoHome,
+    id: &str,
+    version: &str,
+) -> Result<Option<Vec<PackageFile>>, RocoError> {
+    let path = home.package_files_file(id, version);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let path_str = path.to_string_lossy();
+    println_verbose(&format!("parse package files '{}'", path_str));
+    parse_package_files_text(&path_str, &std::fs::read(&path)?).map(Some)
+}
+
+/// lib/<id> (the directory name may differ in case from the package id)
+pub(crate) fn package_dir(home: &ChocoHome, id: &str) -> Option<PathBuf> {
+    std::fs::read_dir(home.lib_dir())
+        .ok()?
+        .flatten()
+        .find(|e| e.file_name().to_string_lossy().eq_ignore_ascii_case(id))
+        .map(|e| e.path())
+}
+
+/// maps a recorded path into the inspected installation
+/// (the files were recorded with the root of the machine that installed them)
+pub(crate) fn resolve_recorded_path(recorded: &str, id: &str, package_dir: &Path) -> PathBuf {
+    let segments: Vec<&str> = recorded.split(['\\', '/']).collect();
+    let lib = segments
+        .windows(2)
+        .position(|w| w[0].eq_ignore_ascii_case("lib") && w[1].eq_ignore_ascii_case(id));
+    match lib {
+        Some(i) => segments[i + 2..]
+            .iter()
+            .fold(package_dir.to_path_buf(), |p, s| p.join(s)),
+        None => PathBuf::from(recorded),
+    }
+}
+
+/// hex encoded checksum of the file, using the algorithm matching the length of `expected`
+/// (None if the checksum type is not known)
+fn file_checksum(path: &Path, expected: &str) -> Result<Option<String>, RocoError> {
+    let mut file = std::fs::File::open(path)?;
+    Ok(match expected.len() {
+        32 => {
+            let mut hasher = Md5::new();
+            std::io::copy(&mut file, &mut hasher)?;
+            Some(format!("{:X}", hasher.finalize()))
+        }
+        64 => {
+            let mut hasher = Sha256::new();
+            std::io::copy(&mut file, &mut hasher)?;
+            Some(format!("{:X}", hasher.finalize()))
+        }
+        _ => None,
+    })
+}
+
+/// unreadable files and package information are reported as issues,
+/// so they don't stop the verification of the other files/packages
+pub fn verify_package(home: &ChocoHome, pkg: &Package) -> Result<PackageVerification, RocoError> {
+    let issue = |path: PathBuf, problem: FileProblem| FileIssue {
+        package_id: pkg.id.clone(),
+        package_version: pkg.version.clone(),
+        path,
+        problem,
+    };
+    let files = match get_package_files(home, &pkg.id, &pkg.version) {
+        Ok(Some(files)) => files,
+        Ok(None) => return Ok(PackageVerification::NoFileInfo),
+        Err(e) => {
+            return Ok(PackageVerification::Verified(vec![issue(
+                home.package_files_file(&pkg.id, &pkg.version),
+                FileProblem::Unreadable {
+                    message: e.to_string(),
+                },
+            )]))
+        }
+    };
+    let package_dir = package_dir(home, &pkg.id).unwrap_or_else(|| home.lib_dir().join(&pkg.id));
+
+    let mut issues = Vec::new();
+    let mut recorded = HashSet::new();
+    for f in files.iter() {
+        let path = resolve_recorded_path(&f.path, &pkg.id, &package_dir);
+        recorded.insert(path.to_string_lossy().to_lowercase());
+        if !path.is_file() {
+            issues.push(issue(path, FileProblem::Missing));
+            continue;
+        }
+        match file_checksum(&path, &f.checksum) {
+            Ok(Some(actual)) if !actual.eq_ignore_ascii_case(&f.checksum) => {
+                issues.push(issue(
+                    path,
+                    FileProblem::Modified {
+                        expected: f.checksum.to_uppercase(),
+                        actual,
+                    },
+                ));
+            }
+            Ok(_) => (),
+            Err(e) => issues.push(issue(
+                path,
+                FileProblem::Unreadable {
+                    message: e.to_string(),
+                },
+            )),
+        }
+    }
+    for entry in walkdir::WalkDir::new(&package_dir)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+    {
+        if !recorded.contains(&entry.path().to_string_lossy().to_lowercase()) {
+            issues.push(issue(entry.into_path(), FileProblem::Unexpected));
+        }
+    }
+    issues.sort_by_key(|i| i.path.to_string_lossy().to_lowercase());
+    Ok(PackageVerification::Verified(issues))
+}
+
+fn get_file_issue_text(home: &ChocoHome, issue: &FileIssue, limitoutput: bool) -> String {
+    let path = issue
+        .path
+        .strip_prefix(home.lib_dir())
+        .or_else(|_| issue.path.strip_prefix(home.root()))
+        .unwrap_or(&issue.path)
+        .to_string_lossy();
+    let (kind, detail) = match &issue.problem {
+        FileProblem::Missing => ("missing", String::new()),
+        FileProblem::Modified { expected, actual } => {
+            ("modified", format!("expected {}, got {}", expected, actual))
+        }
+        FileProblem::Unexpected => ("unexpected", String::new()),
+        FileProblem::Unreadable { message } => ("unreadable", message.clone()),
+    };
+    match limitoutput {
+        true => format!(
+            "{}|{}|{}|{}",
+            issue.package_id, issue.package_version, kind, path
+        ),
+        false => match detail.is_empty() {
+            true => format!(
+                "{} {}: {} {}",
+                issue.package_id, issue.package_version, kind, path
+            ),
+            false => format!(
+                "{} {}: {} {} ({})",
+                issue.package_id, issue.package_version, kind, path, detail
+            ),
+        },
+    }
+}
+
+/// verifies all local packages or the package with the given id,
+/// returns the number of issues found + the report
+pub fn get_verify_text(
+    home: &ChocoHome,
+    pkg: Option<&str>,
+    limitoutput: bool,
+) -> Result<(usize, String), RocoError> {
+    let mut packages = get_local_packages(home)?;
+    if let Some(id) = pkg {
+        packages.retain(|p| p.id.eq_ignore_ascii_case(id));
+        if packages.is_empty() {
+            return Err(RocoError::PackageNotFound(id.to_string()));
+        }
+    }
+    packages.sort_by_key(|p| p.id.to_lowercase());
+
+    let mut lines = Vec::new();
+    let mut issue_count = 0;
+    let mut affected = 0;
+    let mut skipped = Vec::new();
+    for p in packages.iter() {
+        match verify_package(home, p)? {
+            PackageVerification::Verified(issues) => {
+                issue_count += issues.len();
+                affected += usize::from(!issues.is_empty());
+                lines.extend(
+                    issues
+                        .iter()
+                        .map(|i| get_file_issue_text(home, i, limitoutput)),
+                );
+            }
+            PackageVerification::NoFileInfo => skipped.push(p.id.as_str()),
+        }
+    }
+    if !limitoutput {
+        let verified = packages.len() - skipped.len();
+        lines.push(match issue_count {
+            0 => format!("all files of {} packages verified.", verified),
+            n => format!(
+                "{} file problems found in {} of {} packages verified.",
+                n, affected, verified
+            ),
+        });
+        if !skipped.is_empty() {
+            lines.push(format!(
+                "no package information (.files) for {} packages: {}",
+                skipped.len(),
+                skipped.join(", ")
+            ));
+        }
+    }
+    Ok((issue_count, lines.join("\r\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::TestDir;
+
+    #[test]
+    fn parse_package_files_test() {
+        let files = parse_package_files_text(
+            ".files",
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+                "<fileSnapshot xmlns:xsd=\"http://www.w3.org/2001/XMLSchema\"",
+                " xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">\n",
+                "  <files>\n",
+                "    <file path=\"C:\\ProgramData\\chocolatey\\lib\\foo\\foo.nuspec\"",
+                " checksum=\"D41D8CD98F00B204E9800998ECF8427E\" />\n",
+                "    <file path=\"C:\\ProgramData\\chocolatey\\lib\\foo\\tools\\a.ps1\" />\n",
+                "  </files>\n",
+                "</fileSnapshot>\n",
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].checksum, "D41D8CD98F00B204E9800998ECF8427E");
+        assert_eq!(
+            files[1].path,
+            "C:\\ProgramData\\chocolatey\\lib\\foo\\tools\\a.ps1"
+        );
+        assert_eq!(files[1].checksum, "");
+
+        assert!(matches!(
+            parse_package_files_text(".files", b"<fileSnapshot><files><file /></files>"),
+            Err(RocoError::PackageInfoParse { .. })
+        ));
+    }
+
+    #[test]
+    fn resolve_recorded_path_test() {
+        let dir = Path::new("/choco/lib/Foo");
+        assert_eq!(
+            resolve_recorded_path(
+                "C:\\ProgramData\\chocolatey\\lib\\foo\\tools\\a.ps1",
+                "FOO",
+                dir
+            ),
+            dir.join("tools").join("a.ps1")
+        );
+        assert_eq!(
+            resolve_recorded_path("D:\\other\\a.ps1", "foo", dir),
+            PathBuf::from("D:\\other\\a.ps1")
+        );
+    }
+
+    #[test]
+    fn get_verify_text_test() {
+        let root = TestDir::new("verify");
+        root.add_package("foo", "foo", "1.0");
+        root.add_package("bar", "bar", "2.0");
+        root.add_package("qux", "qux", "3.0");
+        root.write(
+            ".chocolatey/qux.3.0/.files",
+            "<fileSnapshot><files><file /></files>",
+        );
+        root.write("lib/foo/tools/ok.txt", "");
+        root.write("lib/foo/tools/changed.txt", "changed");
+        root.write("lib/foo/tools/new.txt", "");
+        // checksums of an empty file (MD5 + SHA256)
+        let md5_empty = "D41D8CD98F00B204E9800998ECF8427E";
+        let sha256_empty = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let files = [
+            ("foo.nuspec", ""),
+            ("tools\\ok.txt", md5_empty),
+            ("tools\\changed.txt", sha256_empty),
+            ("tools\\gone.txt", md5_empty),
+        ]
+        .iter()
+        .map(|(path, checksum)| {
+            format!(
+                "<file path=\"C:\\ProgramData\\chocolatey\\lib\\foo\\{}\" checksum=\"{}\" />",
+                path, checksum
+            )
+        })
+        .collect::<String>();
+        root.write(
+            ".chocolatey/foo.1.0/.files",
+            format!("<fileSnapshot><files>{}</files></fileSnapshot>", files),
+        );
+
+        let home = root.home();
+        let (count, text) = get_verify_text(&home, None, true).unwrap();
+        assert_eq!(count, 4);
+        let sep = std::path::MAIN_SEPARATOR;
+        assert_eq!(
+            text,
+            [
+                format!("foo|1.0|modified|foo{}tools{}changed.txt", sep, sep),
+                format!("foo|1.0|missing|foo{}tools{}gone.txt", sep, sep),
+                format!("foo|1.0|unexpected|foo{}tools{}new.txt", sep, sep),
+                // a broken .files doesn't stop the verification of the other packages
+                format!("qux|3.0|unreadable|.chocolatey{}qux.3.0{}.files", sep, sep),
+            ]
+            .join("\r\n")
+        );
+        let (_, text) = get_verify_text(&home, Some("FOO"), false).unwrap();
+        assert!(text.ends_with("\r\n3 file problems found in 1 of 1 packages verified."));
+        assert!(text.contains(&format!("(expected {}, got ", sha256_empty.to_uppercase())));
+        let (count, text) = get_verify_text(&home, Some("bar"), false).unwrap();
+        assert_eq!(count, 0);
+        assert_eq!(
+            text,
+            "all files of 0 packages verified.\r\nno package information (.files) for 1 packages: bar"
+        );
+        assert!(matches!(
+            get_verify_text(&home, Some("baz"), true),
+            Err(RocoError::PackageNotFound(_))
+        ));
+        let (_, text) = get_verify_text(&home, Some("qux"), false).unwrap();
+        assert!(text.starts_with(&format!(
+            "qux 3.0: unreadable .chocolatey{}qux.3.0{}.files (failed to parse package information",
+            sep, sep
+        )));
+    }
+}
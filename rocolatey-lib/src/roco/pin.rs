@@ -0,0 +1,143 @@
+use std::cmp::Ordering;
+
+use crate::roco::local::get_local_packages;
+use crate::roco::{version, ChocoHome, Package, RocoError};
+use crate::{println_verbose, version_comparison};
+
+// pins are empty marker files choco keeps in the package information directory
+// ($ChocolateyInstall/.chocolatey/<id>.<version>/.pin)
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PinEdit {
+    Add,
+    Remove,
+}
+
+/// the installed package with the given id (case-insensitive) and (optionally) version
+pub fn get_installed_package(
+    home: &ChocoHome,
+    id: &str,
+    version: Option<&str>,
+) -> Result<Package, RocoError> {
+    get_local_packages(home)?
+        .into_iter()
+        .find(|p| {
+            p.id.eq_ignore_ascii_case(id)
+                && version.is_none_or(|v| {
+                    version::compare(&p.version, v, version_comparison()) == Ordering::Equal
+                })
+        })
+        .ok_or_else(|| match version {
+            Some(v) => RocoError::PackageNotFound(format!("{} {}", id, v)),
+            None => RocoError::PackageNotFound(id.to_string()),
+        })
+}
+
+/// pin state of an installed package
+pub fn is_package_pinned(home: &ChocoHome, id: &str) -> Result<bool, RocoError> {
+    let pkg = get_installed_package(home, id, None)?;
+    Ok(home.is_pinned(&pkg.id, &pkg.version))
+}
+
+pub fn get_pinned_packages(home: &ChocoHome) -> Result<Vec<Package>, RocoError> {
+    let mut packages = get_local_packages(home)?;
+    packages.retain(|p| p.pinned);
+    Ok(packages)
+}
+
+/// adds or removes the pin of an installed package,
+/// returns the package + whether anything was changed
+pub fn edit_pin(
+    home: &ChocoHome,
+    id: &str,
+    version: Option<&str>,
+    edit: PinEdit,
+) -> Result<(Package, bool), RocoError> {
+    let pkg = get_installed_package(home, id, version)?;
+    let pin_file = home.pin_file(&pkg.id, &pkg.version);
+    let changed = match (edit, pin_file.exists()) {
+        (PinEdit::Add, false) => {
+            println_verbose(&format!("create '{}'", pin_file.to_string_lossy()));
+            std::fs::create_dir_all(home.package_info_dir(&pkg.id, &pkg.version))?;
+            std::fs::write(&pin_file, "")?;
+            true
+        }
+        (PinEdit::Remove, true) => {
+            println_verbose(&format!("remove '{}'", pin_file.to_string_lossy()));
+            std::fs::remove_file(&pin_file)?;
+            true
+        }
+        _ => false,
+    };
+    Ok((pkg, changed))
+}
+
+/// same output as `choco pin list` (`id|version`, regardless of limitoutput)
+pub fn get_pins_text(home: &ChocoHome) -> Result<String, RocoError> {
+    Ok(get_pinned_packages(home)?
+        .iter()
+        .map(|p| format!("{}|{}", p.id, p.version))
+        .collect::<Vec<String>>()
+        .join("\r\n"))
+}
+
+/// same messages as `choco pin add|remove`
+pub fn edit_pin_text(
+    home: &ChocoHome,
+    id: &str,
+    version: Option<&str>,
+    edit: PinEdit,
+) -> Result<String, RocoError> {
+    Ok(match edit_pin(home, id, version, edit)? {
+        (_, false) => "Nothing to change. Pin already set or removed.".to_string(),
+        (p, true) => match edit {
+            PinEdit::Add => format!("Successfully added a pin for {} v{}.", p.id, p.version),
+            PinEdit::Remove => format!("Successfully removed a pin for {} v{}.", p.id, p.version),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::TestDir;
+
+    #[test]
+    fn edit_pin_test() {
+        let root = TestDir::new("pin");
+        root.add_package("foo", "foo", "1.0");
+        root.add_package("Bar", "Bar", "2.0.0");
+        let home = root.home();
+        assert_eq!(get_pins_text(&home).unwrap(), "");
+        assert!(!is_package_pinned(&home, "bar").unwrap());
+
+        assert_eq!(
+            edit_pin_text(&home, "bar", Some("2.0"), PinEdit::Add).unwrap(),
+            "Successfully added a pin for Bar v2.0.0."
+        );
+        assert!(root.join(".chocolatey/Bar.2.0.0/.pin").is_file());
+        assert!(is_package_pinned(&home, "BAR").unwrap());
+        assert_eq!(
+            edit_pin_text(&home, "bar", None, PinEdit::Add).unwrap(),
+            "Nothing to change. Pin already set or removed."
+        );
+        edit_pin(&home, "foo", None, PinEdit::Add).unwrap();
+        assert_eq!(get_pins_text(&home).unwrap(), "Bar|2.0.0\r\nfoo|1.0");
+
+        assert_eq!(
+            edit_pin_text(&home, "foo", None, PinEdit::Remove).unwrap(),
+            "Successfully removed a pin for foo v1.0."
+        );
+        assert!(!root.join(".chocolatey/foo.1.0/.pin").exists());
+        assert_eq!(get_pins_text(&home).unwrap(), "Bar|2.0.0");
+
+        assert!(matches!(
+            edit_pin(&home, "foo", Some("1.1"), PinEdit::Add),
+            Err(RocoError::PackageNotFound(_))
+        ));
+        assert!(matches!(
+            is_package_pinned(&home, "baz"),
+            Err(RocoError::PackageNotFound(_))
+        ));
+    }
+}
@@ -1,12 +1,15 @@
 use std::collections::HashMap;
-use tokio;
+use std::future::Future;
 
-use crate::roco::{get_choco_sources, Feed, FeedType, OutdatedInfo, Package};
-use crate::roco::{local, nuget2, nuget3, semver};
-use crate::{is_ssl_required, println_verbose};
+use crate::roco::{
+    get_choco_source, get_choco_sources, ChocoHome, Feed, FeedType, NuGetVersion, OutdatedInfo,
+    Package, PackageMetadata, RocoError,
+};
+use crate::roco::{local, nuget2, nuget3, version};
+use crate::{is_ssl_required, println_verbose, version_comparison};
 
 impl Feed {
-    pub async fn evaluate_feed_type(&mut self) -> Result<FeedType, Box<dyn std::error::Error>> {
+    pub async fn evaluate_feed_type(&mut self) -> Result<FeedType, RocoError> {
         if self.feed_type != FeedType::Unknown {
             // already evaluated
             return Ok(self.feed_type);
@@ -24,20 +27,18 @@ impl Feed {
         let service_index = match regex::Regex::new(r"\.json$").unwrap().is_match(&self.url) {
             true => {
                 //looks like a v3 feed url
-                let request = build_reqwest(self);
-                let resp = request.get(&self.url).send().await;
-                let resp = match resp.is_ok() {
-                    true => resp.unwrap(),
-                    false => return Err(resp.err().unwrap().to_string())?,
-                };
+                let request = build_reqwest(self)?;
+                let resp = request
+                    .get(&self.url)
+                    .send()
+                    .await
+                    .map_err(|e| RocoError::from_reqwest(&self.name, e))?;
                 if resp.status().is_success() {
-                    let content = resp.text().await.unwrap();
-                    let v: Result<serde_json::Value, _> = serde_json::from_str(&content);
-                    if v.is_ok() {
-                        Some(v.unwrap())
-                    } else {
-                        None
-                    }
+                    let content = resp
+                        .text()
+                        .await
+                        .map_err(|e| RocoError::from_reqwest(&self.name, e))?;
+                    serde_json::from_str::<serde_json::Value>(&content).ok()
                 } else {
                     None
                 }
@@ -45,10 +46,10 @@ impl Feed {
             false => None,
         };
 
-        if service_index.is_some() {
+        if let Some(service_index) = service_index {
             println_verbose(&format!("feed {} looks like NuGet V3", self.name));
             self.feed_type = FeedType::NuGetV3;
-            self.service_index = nuget3::read_service_index(service_index.unwrap());
+            self.service_index = nuget3::read_service_index(service_index);
         } else {
             println_verbose(&format!("feed {} is most likely NuGet V2", self.name));
             self.feed_type = FeedType::NuGetV2;
@@ -59,26 +60,36 @@ impl Feed {
 
 // https://rust-lang-nursery.github.io/rust-cookbook/web/clients/download.html
 
-pub(crate) fn build_reqwest(feed: &Feed) -> reqwest::Client {
+pub(crate) fn build_reqwest(feed: &Feed) -> Result<reqwest::Client, RocoError> {
     let mut builder: reqwest::ClientBuilder = reqwest::Client::builder();
-    if feed.proxy.is_some() {
-        let proxy_settings = feed.proxy.as_ref().unwrap();
-        let mut proxy = reqwest::Proxy::all(&proxy_settings.url).unwrap();
-        if proxy_settings.credential.is_some() {
-            let credential = proxy_settings.credential.as_ref().unwrap();
+    if let Some(proxy_settings) = &feed.proxy {
+        let mut proxy = reqwest::Proxy::all(&proxy_settings.url).map_err(|e| {
+            RocoError::config_parse("chocolatey.config", format!("invalid proxy: {}", e))
+        })?;
+        if let Some(credential) = &proxy_settings.credential {
             proxy = proxy.basic_auth(&credential.user, &credential.pass);
         }
+        let mut bypass_list = proxy_settings.bypass_list.clone();
+        if proxy_settings.bypass_on_local {
+            bypass_list.extend(["localhost", "127.0.0.1", "::1"].map(String::from));
+        }
+        proxy = proxy.no_proxy(reqwest::NoProxy::from_string(&bypass_list.join(",")));
         builder = builder.proxy(proxy);
     }
+    if let Some(timeout) = feed.request_timeout_seconds {
+        builder = builder.timeout(std::time::Duration::from_secs(timeout));
+    }
     let mut headers = reqwest::header::HeaderMap::new();
 
-    if feed.credential.is_some() {
-        let cred = feed.credential.as_ref().unwrap();
+    if let Some(cred) = &feed.credential {
         let cred = http_auth_basic::Credentials::new(&cred.user, &cred.pass);
-        headers.insert(
-            reqwest::header::AUTHORIZATION,
-            cred.as_http_header().parse().unwrap(),
-        );
+        let header_value = cred.as_http_header().parse().map_err(|_| {
+            RocoError::config_parse(
+                "chocolatey.config",
+                format!("invalid credentials for source '{}'", feed.name),
+            )
+        })?;
+        headers.insert(reqwest::header::AUTHORIZATION, header_value);
     }
     static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
     builder
@@ -86,126 +97,72 @@ pub(crate) fn build_reqwest(feed: &Feed) -> reqwest::Client {
         .default_headers(headers)
         .danger_accept_invalid_certs(!is_ssl_required())
         .build()
-        .unwrap()
+        .map_err(|e| RocoError::from_reqwest(&feed.name, e))
 }
 
 async fn get_latest_remote_packages_on_feed(
-    pkgs: &Vec<Package>,
+    pkgs: &[Package],
     feed: &Feed,
     prerelease: bool,
-) -> Result<Vec<Package>, Box<dyn std::error::Error>> {
-    let res = match &feed.feed_type {
-        FeedType::LocalFileSystem => {
-            let nupkg_files = local::get_nupkgs_from_path(pkgs, feed, prerelease);
-            match nupkg_files.is_ok() {
-                true => Ok(nupkg_files.unwrap()),
-                false => Err(format!(
-                    "failed to read package info from file system '{}'",
-                    feed.url
-                ))?,
-            }
-        }
-        FeedType::NuGetV2 => {
-            let packages = nuget2::get_remote_packages(pkgs, feed, prerelease).await;
-            match packages.is_ok() {
-                true => Ok(packages.unwrap()),
-                false => Err(format!(
-                    "failed to receive packages from NuGet v2 feed '{}'",
-                    feed.url
-                ))?,
-            }
-        }
-        FeedType::NuGetV3 => {
-            let packages = nuget3::get_remote_packages(pkgs, feed, prerelease).await;
-            match packages.is_ok() {
-                true => Ok(packages.unwrap()),
-                false => Err(format!(
-                    "failed to receive packages from NuGet v3 feed '{}'",
-                    feed.url
-                ))?,
-            }
-        }
-        FeedType::Unknown => Err(format!(
-            "cannot communicate with unknown feed type, please check feed '{}'",
-            feed.name
-        ))?,
-    };
-    res
+) -> Result<Vec<Package>, RocoError> {
+    match &feed.feed_type {
+        FeedType::LocalFileSystem => local::get_nupkgs_from_path(pkgs, feed, prerelease),
+        FeedType::NuGetV2 => nuget2::get_remote_packages(pkgs, feed, prerelease).await,
+        FeedType::NuGetV3 => nuget3::get_remote_packages(pkgs, feed, prerelease).await,
+        FeedType::Unknown => Err(RocoError::UnknownFeedType(feed.name.clone())),
+    }
 }
 
 async fn get_latest_remote_packages(
-    pkgs: &Vec<Package>,
+    pkgs: &[Package],
     limit_output: bool,
-    feeds: &Vec<Feed>,
+    feeds: &[Feed],
     prerelease: bool,
-) -> Result<HashMap<String, Package>, Box<dyn std::error::Error>> {
+) -> HashMap<String, Package> {
     let mut remote_pkgs: HashMap<String, Package> = HashMap::new();
+    if pkgs.is_empty() || feeds.is_empty() {
+        return remote_pkgs;
+    }
 
     let num_threads = num_cpus::get();
     let num_parts = std::cmp::max(2, std::cmp::min(num_threads, num_threads / feeds.len()));
-    let chunk_size = (pkgs.len() + num_parts - 1) / num_parts;
+    let chunk_size = pkgs.len().div_ceil(num_parts);
 
     let mut tasks = vec![];
-    let feeds = feeds.clone();
 
     for f in feeds {
         for chunk in pkgs.chunks(chunk_size) {
             let pkgs = chunk.to_vec();
             let feed = f.clone();
             tasks.push(tokio::spawn(async move {
-                let pkgs = get_latest_remote_packages_on_feed(&pkgs, &feed, prerelease)
+                get_latest_remote_packages_on_feed(&pkgs, &feed, prerelease)
                     .await
                     .unwrap_or_else(|e| {
                         if !limit_output {
                             eprintln!("failed to fetch packages: {}", e)
                         }
                         vec![]
-                    });
-                pkgs
+                    })
             }));
         }
     }
 
     for t in tasks {
-        let pkgs = t.await.unwrap();
+        let pkgs = t.await.unwrap_or_default();
         for p in pkgs {
             let lowercase_id = p.id.to_lowercase();
-            if remote_pkgs.contains_key(&lowercase_id) {
-                let remote_version = &remote_pkgs.get(&lowercase_id).unwrap().version;
-                if !semver::is_newer(&p.version, remote_version) {
+            if let Some(remote_pkg) = remote_pkgs.get(&lowercase_id) {
+                if !version::is_newer(&p.version, &remote_pkg.version, version_comparison()) {
                     continue;
                 }
             }
             remote_pkgs.insert(lowercase_id, p);
         }
     }
-    Ok(remote_pkgs)
+    remote_pkgs
 }
 
-pub async fn get_outdated_packages(
-    pkg: &str,
-    limit_output: bool,
-    prerelease: bool,
-    ignore_pinned: bool,
-    ignore_unfound: bool,
-) -> (i32, Vec<OutdatedInfo>) {
-    // foreach local package, compare remote version number
-    let mut local_packages = local::get_local_packages().expect("failed to get local package list");
-    if "all" != pkg {
-        local_packages = local_packages
-            .into_iter()
-            .filter(|p| p.id() == pkg)
-            .collect();
-        if local_packages.len() == 0 {
-            panic!("package '{}' not present in local packages.", pkg);
-        }
-    }
-    let remote_feeds = get_choco_sources().expect("failed to get choco feeds");
-    let remote_feeds: Vec<Feed> = remote_feeds
-        .into_iter()
-        .filter(|f| f.disabled == false)
-        .collect();
-
+fn log_ssl_mode() {
     println_verbose(&format!(
         "ssl checks are {}",
         if is_ssl_required() {
@@ -214,15 +171,24 @@ pub async fn get_outdated_packages(
             "disabled"
         }
     ));
+}
+
+/// enabled sources (in config order), their feed types are evaluated concurrently
+pub(crate) async fn get_enabled_feeds(home: &ChocoHome) -> Result<Vec<Feed>, RocoError> {
+    let mut remote_feeds = get_choco_sources(home)?;
+    remote_feeds.retain(|f| !f.disabled);
+
+    log_ssl_mode();
 
     // call feed.evaluate_feed_type() on each feed in remote_feeds (await!)
     let tasks: Vec<_> = remote_feeds
         .into_iter()
         .map(|mut feed| {
-            tokio::spawn(async {
-                // TODO: implement error handling
-                // -> feed may not be reachable / get it out of the way asap.
-                _ = feed.evaluate_feed_type().await;
+            tokio::spawn(async move {
+                // an unreachable feed stays 'Unknown' and gets reported when querying packages
+                if let Err(e) = feed.evaluate_feed_type().await {
+                    println_verbose(&format!("failed to evaluate feed type: {}", e));
+                }
                 feed
             })
         })
@@ -230,14 +196,164 @@ pub async fn get_outdated_packages(
     // await the tasks for resolve's to complete and give back our items
     let mut feeds = vec![];
     for task in tasks {
-        feeds.push(task.await.unwrap());
+        if let Ok(feed) = task.await {
+            feeds.push(feed);
+        }
+    }
+    Ok(feeds)
+}
+
+/// runs `query` on each feed concurrently, returns the results (by feed name, in the
+/// order of `feeds`) next to the errors of the feeds that failed;
+/// fails only if none of the feeds answered
+pub(crate) async fn query_feeds<T, F, Fut>(
+    feeds: Vec<Feed>,
+    query: F,
+) -> Result<(Vec<(String, T)>, Vec<RocoError>), RocoError>
+where
+    T: Send + 'static,
+    F: Fn(Feed) -> Fut,
+    Fut: Future<Output = Result<T, RocoError>> + Send + 'static,
+{
+    let tasks: Vec<_> = feeds
+        .into_iter()
+        .map(|feed| (feed.name.clone(), tokio::spawn(query(feed))))
+        .collect();
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for (name, task) in tasks {
+        match task.await {
+            Ok(Ok(res)) => results.push((name, res)),
+            Ok(Err(e)) => errors.push(e),
+            Err(e) => errors.push(RocoError::feed_response(&name, e)),
+        }
+    }
+    if results.is_empty() && !errors.is_empty() {
+        return Err(RocoError::AllFeedsFailed(errors));
+    }
+    Ok((results, errors))
+}
+
+/// a configured source (by id, case-insensitive, disabled ones included)
+/// or an ad-hoc source given by url or folder path
+fn find_source_feed(home: &ChocoHome, source: &str) -> Result<Feed, RocoError> {
+    if let Some(feed) = get_choco_source(home, source)? {
+        return Ok(feed);
+    }
+    let is_url = regex::Regex::new(r"^https?://.+").unwrap().is_match(source);
+    if !is_url && !std::path::Path::new(source).is_dir() {
+        return Err(RocoError::SourceNotFound(source.to_string()));
+    }
+    Ok(Feed {
+        name: source.to_string(),
+        url: source.to_string(),
+        credential: None,
+        proxy: None,
+        disabled: false,
+        certificate: None,
+        bypass_proxy: false,
+        self_service: false,
+        admin_only: false,
+        priority: 0,
+        request_timeout_seconds: None,
+        feed_type: FeedType::Unknown,
+        service_index: None,
+    })
+}
+
+/// the requested version, or the latest listed one
+fn select_metadata(
+    candidates: Vec<PackageMetadata>,
+    version: Option<&str>,
+    prerelease: bool,
+) -> Option<PackageMetadata> {
+    let parse = |v: &str| v.parse::<NuGetVersion>().ok();
+    match version {
+        Some(version) => {
+            candidates
+                .into_iter()
+                .find(|m| match (parse(&m.version), parse(version)) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => m.version.eq_ignore_ascii_case(version),
+                })
+        }
+        None => candidates
+            .into_iter()
+            .filter(|m| m.listed != Some(false))
+            .filter(|m| prerelease || parse(&m.version).is_some_and(|v| !v.is_prerelease()))
+            .reduce(|latest, m| {
+                match version::is_newer(&m.version, &latest.version, version_comparison()) {
+                    true => m,
+                    false => latest,
+                }
+            }),
+    }
+}
+
+/// metadata of a package on the given source, the latest version if none is given
+pub async fn get_remote_package_metadata(
+    home: &ChocoHome,
+    source: &str,
+    id: &str,
+    version: Option<&str>,
+    prerelease: bool,
+) -> Result<PackageMetadata, RocoError> {
+    let mut feed = find_source_feed(home, source)?;
+    log_ssl_mode();
+    let candidates = match feed.evaluate_feed_type().await? {
+        FeedType::LocalFileSystem => local::get_folder_feed_metadata(&feed, id)?,
+        FeedType::NuGetV2 => nuget2::get_package_metadata(&feed, id, version, prerelease).await?,
+        FeedType::NuGetV3 => nuget3::get_package_metadata(&feed, id).await?,
+        FeedType::Unknown => return Err(RocoError::UnknownFeedType(feed.name.clone())),
+    };
+    select_metadata(candidates, version, prerelease).ok_or_else(|| {
+        RocoError::RemotePackageNotFound {
+            source: feed.name.clone(),
+            id: match version {
+                Some(version) => format!("{} {}", id, version),
+                None => id.to_string(),
+            },
+        }
+    })
+}
+
+pub async fn get_remote_package_info_text(
+    home: &ChocoHome,
+    source: &str,
+    id: &str,
+    version: Option<&str>,
+    prerelease: bool,
+    limitoutput: bool,
+) -> Result<String, RocoError> {
+    let metadata = get_remote_package_metadata(home, source, id, version, prerelease).await?;
+    let mut res = metadata.to_text(limitoutput);
+    if !limitoutput {
+        res.push_str(&format!("\r\n Source: {}", source));
+        res.push_str("\r\n\r\n1 packages found.");
+    }
+    Ok(res)
+}
+
+pub async fn get_outdated_packages(
+    home: &ChocoHome,
+    pkg: &str,
+    limit_output: bool,
+    prerelease: bool,
+    ignore_pinned: bool,
+    ignore_unfound: bool,
+) -> Result<(i32, Vec<OutdatedInfo>), RocoError> {
+    // foreach local package, compare remote version number
+    let mut local_packages = local::get_local_packages(home)?;
+    if "all" != pkg {
+        local_packages.retain(|p| p.id() == pkg);
+        if local_packages.is_empty() {
+            return Err(RocoError::PackageNotFound(pkg.to_string()));
+        }
     }
-    let remote_feeds = feeds;
+    let remote_feeds = get_enabled_feeds(home).await?;
 
     let latest_packages =
-        get_latest_remote_packages(&local_packages, limit_output, &remote_feeds, prerelease)
-            .await
-            .expect("failed to get remote package list");
+        get_latest_remote_packages(&local_packages, limit_output, &remote_feeds, prerelease).await;
 
     let mut oi: Vec<OutdatedInfo> = Vec::new();
     let mut warning_count = 0;
@@ -252,7 +368,7 @@ pub async fn get_outdated_packages(
                     "  check latest remote pkg {}, version={} against local version={}",
                     l.id, u.version, l.version
                 ));
-                if semver::is_newer(&u.version, &l.version) {
+                if version::is_newer(&u.version, &l.version, version_comparison()) {
                     oi.push(OutdatedInfo {
                         id: l.id,
                         local_version: l.version.clone(),
@@ -279,21 +395,29 @@ pub async fn get_outdated_packages(
         };
     }
 
-    oi.sort_by(|a, b| a.id.to_lowercase().cmp(&b.id.to_lowercase()));
+    oi.sort_by_key(|a| a.id.to_lowercase());
 
-    (warning_count, oi)
+    Ok((warning_count, oi))
 }
 
 pub async fn get_outdated_packages_text(
+    home: &ChocoHome,
     pkg: &str,
     limit_output: bool,
     list_output: bool,
     prerelease: bool,
     ignore_pinned: bool,
     ignore_unfound: bool,
-) -> String {
-    let (warning_count, oi) =
-        get_outdated_packages(pkg, limit_output, prerelease, ignore_pinned, ignore_unfound).await;
+) -> Result<String, RocoError> {
+    let (warning_count, oi) = get_outdated_packages(
+        home,
+        pkg,
+        limit_output,
+        prerelease,
+        ignore_pinned,
+        ignore_unfound,
+    )
+    .await?;
 
     let mut warnings = String::new();
     let mut res = String::new();
@@ -332,26 +456,31 @@ pub async fn get_outdated_packages_text(
         ));
         if warning_count > 0 {
             res.push_str(&format!(" {} package(s) had warnings.\n", warning_count));
-            res.push_str(&format!("Warnings:\n"));
+            res.push_str("Warnings:\n");
             res.push_str(&warnings);
         }
     }
-    res
+    Ok(res)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn invoke_package_bulk_request(
     pkgs: &[Package],
 
     feed: &Feed,
-    query_string_base: &String,
+    query_string_base: &str,
 
     max_batch_size: u32,
     pkg_query_fmt: fn(pkg: &Package) -> String,
-    query_str_delim: &String,
-    query_str_end: &String,
-
-    batch_res_processor: fn(pkgs: &mut Vec<Package>, batch_res: &String),
-) -> Result<Vec<Package>, Box<dyn std::error::Error>> {
+    query_str_delim: &str,
+    query_str_end: &str,
+
+    batch_res_processor: fn(
+        feed_name: &str,
+        pkgs: &mut Vec<Package>,
+        batch_res: &str,
+    ) -> Result<(), RocoError>,
+) -> Result<Vec<Package>, RocoError> {
     let mut pkgs_res: Vec<Package> = Vec::new();
 
     let mut max_batch_size = max_batch_size;
@@ -362,35 +491,50 @@ pub(crate) async fn invoke_package_bulk_request(
     while curr_pkg_idx < total_pkgs {
         // max_batch_size and max_url_len get reduced when communication with the repository fails
         if max_batch_size == 0 || max_url_len < 100 {
-            Err("failed to execute bulk query, communication failed.")?
+            return Err(RocoError::feed_response(
+                &feed.name,
+                "failed to execute bulk query, communication failed.",
+            ));
         }
 
-        let mut query_string = format!("{}", query_string_base);
+        let mut query_string = query_string_base.to_string();
         let mut batch_size = 0;
         let last_query_package_idx = curr_pkg_idx;
 
         loop {
-            let curr_pkg = pkgs.get(curr_pkg_idx).unwrap();
+            let curr_pkg = &pkgs[curr_pkg_idx];
 
             query_string.push_str(&pkg_query_fmt(curr_pkg));
 
             curr_pkg_idx += 1;
             batch_size += 1;
 
-            let url = reqwest::Url::parse(&query_string);
-            if (url.unwrap().as_str().len() > max_url_len)
+            let url = reqwest::Url::parse(&query_string)
+                .map_err(|e| RocoError::feed_response(&feed.name, e))?;
+            if (url.as_str().len() > max_url_len)
                 || curr_pkg_idx == pkgs.len()
                 || batch_size >= max_batch_size
             {
-                query_string.push_str(&query_str_end);
+                query_string.push_str(query_str_end);
                 break;
             }
-            query_string.push_str(&query_str_delim);
+            query_string.push_str(query_str_delim);
         }
 
         println_verbose(&format!(" -> GET: {}", query_string));
-        let client = build_reqwest(&feed);
-        let resp = client.get(&query_string).send().await?;
+        let client = build_reqwest(feed)?;
+        let resp = client
+            .get(&query_string)
+            .send()
+            .await
+            .map_err(|e| RocoError::from_reqwest(&feed.name, e))?;
+
+        if resp.status() == 401 || resp.status() == 403 {
+            return Err(RocoError::FeedAuth {
+                feed: feed.name.clone(),
+                status: resp.status().as_u16(),
+            });
+        }
 
         if !resp.status().is_success() {
             println_verbose(&format!("  HTTP STATUS {}", resp.status().as_str()));
@@ -402,7 +546,7 @@ pub(crate) async fn invoke_package_bulk_request(
 
         // if we get a client err response - try reducing url length (first)
         if resp.status().is_client_error() {
-            max_url_len = max_url_len / 2;
+            max_url_len /= 2;
             println_verbose(&format!("  reduced max url length: {}", max_url_len));
             curr_pkg_idx = last_query_package_idx;
             continue;
@@ -418,8 +562,106 @@ pub(crate) async fn invoke_package_bulk_request(
             continue;
         }
 
-        batch_res_processor(&mut pkgs_res, &resp);
+        batch_res_processor(&feed.name, &mut pkgs_res, &resp)?;
     }
 
     Ok(pkgs_res)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::{self, TestDir};
+
+    fn metadata(version: &str, listed: Option<bool>) -> PackageMetadata {
+        PackageMetadata {
+            id: "git".to_string(),
+            version: version.to_string(),
+            listed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn select_metadata_test() {
+        let candidates = || {
+            vec![
+                metadata("2.39.0", None),
+                metadata("2.41.0", Some(false)),
+                metadata("2.40.0", Some(true)),
+                metadata("2.40.1-rc1", Some(true)),
+            ]
+        };
+        let version =
+            |v: Option<&str>, pre: bool| select_metadata(candidates(), v, pre).map(|m| m.version);
+        assert_eq!(version(None, false).as_deref(), Some("2.40.0"));
+        assert_eq!(version(None, true).as_deref(), Some("2.40.1-rc1"));
+        // unlisted versions can still be requested explicitly
+        assert_eq!(version(Some("2.41"), false).as_deref(), Some("2.41.0"));
+        assert_eq!(version(Some("2.42.0"), false), None);
+    }
+
+    #[tokio::test]
+    async fn query_feeds_test() {
+        let feed =
+            |name: &str, priority: i64| testutil::feed(name, "", priority, FeedType::Unknown);
+        let query = |feed: Feed| async move {
+            match feed.name.as_str() {
+                "down" => Err(RocoError::UnknownFeedType(feed.name)),
+                _ => Ok(feed.priority),
+            }
+        };
+
+        let (results, errors) =
+            query_feeds(vec![feed("a", 1), feed("down", 0), feed("b", 2)], query)
+                .await
+                .unwrap();
+        assert_eq!(results, [("a".to_string(), 1), ("b".to_string(), 2)]);
+        assert!(matches!(errors[..], [RocoError::UnknownFeedType(_)]));
+
+        let (results, errors) = query_feeds(vec![], query).await.unwrap();
+        assert!(results.is_empty() && errors.is_empty());
+
+        match query_feeds(vec![feed("down", 0), feed("down", 1)], query).await {
+            Err(RocoError::AllFeedsFailed(errors)) => assert_eq!(errors.len(), 2),
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn find_source_feed_test() {
+        let home = ChocoHome::from_env().unwrap();
+        assert_eq!(
+            find_source_feed(&home, "CHOCOLATEY").unwrap().name,
+            "chocolatey"
+        );
+        let tests_root = std::env::var("RocolateyTestRoot").unwrap();
+        let repo = std::path::Path::new(&tests_root).join("fake_repo");
+        let feed = find_source_feed(&home, &repo.to_string_lossy()).unwrap();
+        assert_eq!(feed.feed_type, FeedType::Unknown);
+        assert!(find_source_feed(&home, "https://example.org/api/v2").is_ok());
+        assert!(matches!(
+            find_source_feed(&home, "no-such-source"),
+            Err(RocoError::SourceNotFound(_))
+        ));
+
+        // only the password of the requested source is decrypted
+        let root = TestDir::new("find-source");
+        root.write(
+            "config/chocolatey.config",
+            r#"<chocolatey><sources>
+  <source id="roco-test-locked" value="https://locked/api/v2" user="ci" password="ENC" />
+  <source id="roco-test-open" value="https://open/api/v2" />
+</sources></chocolatey>"#,
+        );
+        let home = root.home();
+        assert_eq!(
+            find_source_feed(&home, "roco-test-open").unwrap().url,
+            "https://open/api/v2"
+        );
+        assert!(matches!(
+            find_source_feed(&home, "roco-test-locked"),
+            Err(RocoError::CredentialDecrypt { .. })
+        ));
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use crate::roco::remote::{get_enabled_feeds, query_feeds};
+use crate::roco::{local, nuget2, nuget3, version};
+use crate::roco::{ChocoHome, Feed, FeedType, NuGetVersion, Package, RocoError};
+use crate::{println_verbose, version_comparison};
+
+// `roco search`: queries all enabled sources, results are merged by id
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchOptions {
+    pub term: String,
+    /// only the package with exactly this id
+    pub exact: bool,
+    pub prerelease: bool,
+    /// every version instead of the latest one
+    pub all_versions: bool,
+    /// 0-based, same as choco
+    pub page: usize,
+    pub page_size: usize,
+}
+
+impl SearchOptions {
+    pub fn new(term: &str) -> SearchOptions {
+        SearchOptions {
+            term: term.to_string(),
+            exact: false,
+            prerelease: false,
+            all_versions: false,
+            page: 0,
+            page_size: 30,
+        }
+    }
+
+    /// number of results to request from each feed to be able to fill the requested page
+    pub(crate) fn limit(&self) -> usize {
+        (self.page + 1) * self.page_size
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub id: String,
+    pub version: String,
+    /// name of the source the package was taken from
+    pub source: String,
+}
+
+fn search_folder_feed(feed: &Feed, options: &SearchOptions) -> Result<Vec<Package>, RocoError> {
+    let term = options.term.to_lowercase();
+    local::find_nupkgs_in_path(feed, options.prerelease, |p| {
+        let id = p.id.to_lowercase();
+        match options.exact {
+            true => id == term,
+            false => id.contains(&term),
+        }
+    })
+}
+
+async fn search_feed(feed: &Feed, options: &SearchOptions) -> Result<Vec<Package>, RocoError> {
+    match &feed.feed_type {
+        FeedType::LocalFileSystem => search_folder_feed(feed, options),
+        FeedType::NuGetV2 => nuget2::search_packages(feed, options).await,
+        FeedType::NuGetV3 => nuget3::search_packages(feed, options).await,
+        FeedType::Unknown => Err(RocoError::UnknownFeedType(feed.name.clone())),
+    }
+}
+
+/// same order choco consults sources in: priorities above 0 first (lower is better),
+/// sources without priority in config order
+pub(crate) fn sort_by_priority(feeds: &mut [Feed]) {
+    feeds.sort_by_key(|f| (f.priority <= 0, f.priority));
+}
+
+pub(crate) fn version_key(version: &str) -> String {
+    match version.parse::<NuGetVersion>() {
+        Ok(v) => v.to_normalized_string().to_lowercase(),
+        Err(_) => version.to_lowercase(),
+    }
+}
+
+/// merges the results of each feed (given in priority order): a package is taken from the
+/// first feed offering it (its newest version there), with `all_versions` each version is
+/// taken from the first feed offering it; sorted by id, newest version first
+pub(crate) fn merge_search_results(
+    results: Vec<(String, Vec<Package>)>,
+    all_versions: bool,
+) -> Vec<SearchResult> {
+    let mut merged: HashMap<String, SearchResult> = HashMap::new();
+    for (source, packages) in results {
+        let mut found: HashMap<String, Package> = HashMap::new();
+        for p in packages {
+            let key = match all_versions {
+                true => format!("{}|{}", p.id.to_lowercase(), version_key(&p.version)),
+                false => p.id.to_lowercase(),
+            };
+            match found.get(&key) {
+                Some(f) if !version::is_newer(&p.version, &f.version, version_comparison()) => (),
+                _ => {
+                    found.insert(key, p);
+                }
+            }
+        }
+        for (key, p) in found {
+            merged.entry(key).or_insert_with(|| SearchResult {
+                id: p.id,
+                version: p.version,
+                source: source.clone(),
+            });
+        }
+    }
+    let mut merged: Vec<SearchResult> = merged.into_values().collect();
+    merged.sort_by(|a, b| {
+        a.id.to_lowercase()
+            .cmp(&b.id.to_lowercase())
+            .then_with(|| version::compare(&b.version, &a.version, version_comparison()))
+    });
+    merged
+}
+
+fn get_page(results: Vec<SearchResult>, options: &SearchOptions) -> Vec<SearchResult> {
+    results
+        .into_iter()
+        .skip(options.page * options.page_size)
+        .take(options.page_size)
+        .collect()
+}
+
+/// the requested page of results, next to the errors of the sources that could not be
+/// searched (fails if none of them could)
+pub async fn search_packages(
+    home: &ChocoHome,
+    options: &SearchOptions,
+) -> Result<(Vec<SearchResult>, Vec<RocoError>), RocoError> {
+    let mut feeds = get_enabled_feeds(home).await?;
+    sort_by_priority(&mut feeds);
+
+    let (results, errors) = query_feeds(feeds, |feed| {
+        let options = options.clone();
+        async move {
+            println_verbose(&format!(
+                "search '{}' on feed '{}'",
+                options.term, feed.name
+            ));
+            search_feed(&feed, &options).await
+        }
+    })
+    .await?;
+    let results = get_page(merge_search_results(results, options.all_versions), options);
+    Ok((results, errors))
+}
+
+/// `id version [source]`, limitoutput: `id|version` (same as `choco search -r`),
+/// next to the errors of the sources that could not be searched
+pub async fn search_packages_text(
+    home: &ChocoHome,
+    options: &SearchOptions,
+    limit_output: bool,
+) -> Result<(String, Vec<RocoError>), RocoError> {
+    let (results, errors) = search_packages(home, options).await?;
+    let mut lines: Vec<String> = results
+        .iter()
+        .map(|r| match limit_output {
+            true => format!("{}|{}", r.id, r.version),
+            false => format!("{} {} [{}]", r.id, r.version, r.source),
+        })
+        .collect();
+    if !limit_output {
+        lines.push(format!("{} packages found.", results.len()));
+    }
+    Ok((lines.join("\r\n"), errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::{feed, pkg};
+
+    fn texts(results: &[SearchResult]) -> Vec<String> {
+        results
+            .iter()
+            .map(|r| format!("{}|{}|{}", r.id, r.version, r.source))
+            .collect()
+    }
+
+    #[test]
+    fn sort_by_priority_test() {
+        let mut feeds = vec![
+            feed("a", "", 0, FeedType::LocalFileSystem),
+            feed("b", "", 10, FeedType::LocalFileSystem),
+            feed("c", "", 0, FeedType::LocalFileSystem),
+            feed("d", "", 1, FeedType::LocalFileSystem),
+        ];
+        sort_by_priority(&mut feeds);
+        let names: Vec<&str> = feeds.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["d", "b", "a", "c"]);
+    }
+
+    #[test]
+    fn merge_search_results_test() {
+        let results = || {
+            vec![
+                (
+                    "internal".to_string(),
+                    vec![
+                        pkg("git", "2.39.0", &[]),
+                        pkg("git", "2.38.0", &[]),
+                        pkg("vlc", "3.0.18", &[]),
+                    ],
+                ),
+                (
+                    "community".to_string(),
+                    vec![
+                        pkg("Git", "2.41.0", &[]),
+                        pkg("git", "2.38.0.0", &[]),
+                        pkg("7zip", "23.1", &[]),
+                    ],
+                ),
+            ]
+        };
+        assert_eq!(
+            texts(&merge_search_results(results(), false)),
+            [
+                "7zip|23.1|community",
+                "git|2.39.0|internal",
+                "vlc|3.0.18|internal"
+            ]
+        );
+        assert_eq!(
+            texts(&merge_search_results(results(), true)),
+            [
+                "7zip|23.1|community",
+                "Git|2.41.0|community",
+                "git|2.39.0|internal",
+                "git|2.38.0|internal",
+                "vlc|3.0.18|internal"
+            ]
+        );
+
+        let mut options = SearchOptions::new("git");
+        options.page_size = 2;
+        options.page = 1;
+        assert_eq!(options.limit(), 4);
+        assert_eq!(
+            texts(&get_page(merge_search_results(results(), true), &options)),
+            ["git|2.39.0|internal", "git|2.38.0|internal"]
+        );
+    }
+
+    #[test]
+    fn search_folder_feed_test() {
+        let tests_root = std::env::var("RocolateyTestRoot").unwrap();
+        let repo = std::path::Path::new(&tests_root).join("fake_repo");
+        let repo = feed(
+            "fake_repo",
+            &repo.to_string_lossy(),
+            0,
+            FeedType::LocalFileSystem,
+        );
+
+        let ids = |options: &SearchOptions| {
+            let mut res: Vec<String> = search_folder_feed(&repo, options)
+                .unwrap()
+                .iter()
+                .map(|p| format!("{}|{}", p.id, p.version))
+                .collect();
+            res.sort();
+            res
+        };
+        let mut options = SearchOptions::new("FIRE");
+        assert_eq!(ids(&options), ["Firefox|81.0.2.0"]);
+        options.prerelease = true;
+        assert_eq!(
+            ids(&options),
+            ["Firefox|81.0.2.0", "Firefox|81.0.2.0-alpha2"]
+        );
+        options.exact = true;
+        assert!(ids(&options).is_empty());
+        options.term = "googlechrome".to_string();
+        assert_eq!(ids(&options), ["googlechrome|80.0.3987.149"]);
+        assert_eq!(ids(&SearchOptions::new("")).len(), 2);
+    }
+}
@@ -0,0 +1,103 @@
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use crate::roco::{ChocoHome, Feed, FeedType, Package};
+
+// helpers shared by the unit tests
+
+/// scratch directory below the system temp dir, removed again when dropped
+/// (also if an assertion fails)
+pub(crate) struct TestDir(PathBuf);
+
+impl TestDir {
+    /// empty directory `roco-<name>-<pid>`
+    pub(crate) fn new(name: &str) -> TestDir {
+        let path = std::env::temp_dir().join(format!("roco-{}-{}", name, std::process::id()));
+        // leftovers of an aborted run
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TestDir(path)
+    }
+
+    /// writes a file (creating its parent directories), returns its path
+    pub(crate) fn write(&self, path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.0.join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    /// installs a package consisting of just a nuspec (`lib/<dir>/<dir>.nuspec`)
+    pub(crate) fn add_package(&self, dir: &str, id: &str, version: &str) -> PathBuf {
+        self.write(
+            Path::new("lib").join(dir).join(format!("{}.nuspec", dir)),
+            nuspec_text(id, version),
+        )
+    }
+
+    /// the directory as Chocolatey installation
+    pub(crate) fn home(&self) -> ChocoHome {
+        ChocoHome::from_dir(&self.0).unwrap()
+    }
+}
+
+impl Deref for TestDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// package with the given `(id, version range)` dependencies
+pub(crate) fn pkg(id: &str, version: &str, dependencies: &[(&str, &str)]) -> Package {
+    Package {
+        id: id.to_string(),
+        version: version.to_string(),
+        pinned: false,
+        dependencies: match dependencies.is_empty() {
+            true => None,
+            false => Some(
+                dependencies
+                    .iter()
+                    .map(|(id, version)| pkg(id, version, &[]))
+                    .collect(),
+            ),
+        },
+    }
+}
+
+/// enabled source without credentials or proxy
+pub(crate) fn feed(name: &str, url: &str, priority: i64, feed_type: FeedType) -> Feed {
+    Feed {
+        name: name.to_string(),
+        url: url.to_string(),
+        credential: None,
+        proxy: None,
+        disabled: false,
+        certificate: None,
+        bypass_proxy: false,
+        self_service: false,
+        admin_only: false,
+        priority,
+        request_timeout_seconds: None,
+        feed_type,
+        service_index: None,
+    }
+}
+
+/// minimal nuspec (id + version)
+pub(crate) fn nuspec_text(id: &str, version: &str) -> String {
+    format!(
+        "<package><metadata><id>{}</id><version>{}</version></metadata></package>",
+        id, version
+    )
+}
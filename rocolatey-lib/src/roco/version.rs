@@ -0,0 +1,557 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use crate::roco::RocoError;
+
+// NuGet package versions: up to 4 numeric parts, optional SemVer 2.0 prerelease labels
+// (dot-separated) and optional build metadata, e.g. '1.02.3.4-beta.2+sha.1234'
+// see https://learn.microsoft.com/en-us/nuget/concepts/package-versioning
+
+/// how prerelease labels are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VersionComparison {
+    /// same ordering as (legacy) choco: the whole prerelease part is compared as one
+    /// case-insensitive string, so '1.0-beta2' is newer than '1.0-beta14'
+    #[default]
+    ChocoCompat,
+    /// SemVer 2.0 ordering: labels are compared one by one, numeric labels numerically
+    SemVer2,
+}
+
+#[derive(Debug, Clone)]
+pub struct NuGetVersion {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    revision: u64,
+    release_labels: Vec<String>,
+    metadata: Option<String>,
+}
+
+fn is_valid_label(label: &str) -> bool {
+    !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+// numeric prerelease labels must not have leading zeros (SemVer 2.0)
+fn is_valid_release_label(label: &str) -> bool {
+    let numeric = label.chars().all(|c| c.is_ascii_digit());
+    is_valid_label(label) && !(numeric && label.len() > 1 && label.starts_with('0'))
+}
+
+fn compare_label(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        // numeric identifiers always have lower precedence
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => compare_ignore_case(a, b),
+    }
+}
+
+fn compare_ignore_case(a: &str, b: &str) -> Ordering {
+    a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase())
+}
+
+impl NuGetVersion {
+    pub fn new(major: u64, minor: u64, patch: u64, revision: u64) -> NuGetVersion {
+        NuGetVersion {
+            major,
+            minor,
+            patch,
+            revision,
+            release_labels: Vec::new(),
+            metadata: None,
+        }
+    }
+
+    pub fn major(&self) -> u64 {
+        self.major
+    }
+    pub fn minor(&self) -> u64 {
+        self.minor
+    }
+    pub fn patch(&self) -> u64 {
+        self.patch
+    }
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+    pub fn release_labels(&self) -> &[String] {
+        &self.release_labels
+    }
+    /// the prerelease part without the leading '-' (empty for stable versions)
+    pub fn release(&self) -> String {
+        self.release_labels.join(".")
+    }
+    pub fn metadata(&self) -> Option<&str> {
+        self.metadata.as_deref()
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.release_labels.is_empty()
+    }
+
+    /// normalized form as used by NuGet feeds: no leading zeros, the revision only if set,
+    /// no build metadata
+    pub fn to_normalized_string(&self) -> String {
+        let mut res = format!("{}.{}.{}", self.major, self.minor, self.patch);
+        if self.revision > 0 {
+            res.push_str(&format!(".{}", self.revision));
+        }
+        if self.is_prerelease() {
+            res.push('-');
+            res.push_str(&self.release());
+        }
+        res
+    }
+
+    /// compares precedence, build metadata is ignored in either mode
+    pub fn compare_with(&self, other: &NuGetVersion, mode: VersionComparison) -> Ordering {
+        let numeric = (self.major, self.minor, self.patch, self.revision).cmp(&(
+            other.major,
+            other.minor,
+            other.patch,
+            other.revision,
+        ));
+        if numeric != Ordering::Equal {
+            return numeric;
+        }
+        // a stable version is newer than any of its prereleases
+        match (self.is_prerelease(), other.is_prerelease()) {
+            (false, false) => return Ordering::Equal,
+            (false, true) => return Ordering::Greater,
+            (true, false) => return Ordering::Less,
+            (true, true) => {}
+        }
+        match mode {
+            VersionComparison::ChocoCompat => {
+                compare_ignore_case(&self.release(), &other.release())
+            }
+            VersionComparison::SemVer2 => {
+                for (a, b) in self.release_labels.iter().zip(other.release_labels.iter()) {
+                    let res = compare_label(a, b);
+                    if res != Ordering::Equal {
+                        return res;
+                    }
+                }
+                self.release_labels.len().cmp(&other.release_labels.len())
+            }
+        }
+    }
+
+    pub fn is_newer_than(&self, other: &NuGetVersion, mode: VersionComparison) -> bool {
+        self.compare_with(other, mode) == Ordering::Greater
+    }
+}
+
+impl FromStr for NuGetVersion {
+    type Err = RocoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RocoError::InvalidVersion(s.to_string());
+        let v = s.trim();
+
+        let (v, metadata) = match v.split_once('+') {
+            Some((v, m)) if m.split('.').all(is_valid_label) => (v, Some(m.to_string())),
+            Some(_) => return Err(invalid()),
+            None => (v, None),
+        };
+        let (v, release_labels) = match v.split_once('-') {
+            Some((v, r)) if r.split('.').all(is_valid_release_label) => {
+                (v, r.split('.').map(|l| l.to_string()).collect())
+            }
+            Some(_) => return Err(invalid()),
+            None => (v, Vec::new()),
+        };
+
+        let parts = v
+            .split('.')
+            .map(
+                |p| match !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()) {
+                    true => p.parse::<u64>().map_err(|_| invalid()),
+                    false => Err(invalid()),
+                },
+            )
+            .collect::<Result<Vec<u64>, RocoError>>()?;
+        if parts.is_empty() || parts.len() > 4 {
+            return Err(invalid());
+        }
+        let part = |i: usize| parts.get(i).copied().unwrap_or(0);
+
+        Ok(NuGetVersion {
+            major: part(0),
+            minor: part(1),
+            patch: part(2),
+            revision: part(3),
+            release_labels,
+            metadata,
+        })
+    }
+}
+
+impl fmt::Display for NuGetVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_normalized_string())?;
+        if let Some(m) = &self.metadata {
+            write!(f, "+{}", m)?;
+        }
+        Ok(())
+    }
+}
+
+// the natural ordering is SemVer 2.0 (metadata is ignored, labels are case-insensitive)
+impl Ord for NuGetVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare_with(other, VersionComparison::SemVer2)
+    }
+}
+
+impl PartialOrd for NuGetVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for NuGetVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for NuGetVersion {}
+
+impl Hash for NuGetVersion {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_normalized_string().to_ascii_lowercase().hash(state);
+    }
+}
+
+/// compares two version strings, returns false if either can't be parsed
+pub fn is_newer(a: &str, b: &str, mode: VersionComparison) -> bool {
+    match (a.parse::<NuGetVersion>(), b.parse::<NuGetVersion>()) {
+        (Ok(a), Ok(b)) => a.is_newer_than(&b, mode),
+        _ => false,
+    }
+}
+
+/// orders two version strings, unparseable versions are compared as
+/// (case-insensitive) strings
+pub fn compare(a: &str, b: &str, mode: VersionComparison) -> Ordering {
+    match (a.parse::<NuGetVersion>(), b.parse::<NuGetVersion>()) {
+        (Ok(a), Ok(b)) => a.compare_with(&b, mode),
+        _ => compare_ignore_case(a, b),
+    }
+}
+
+/// NuGet version range as used for nuspec dependencies:
+/// '1.0' (>= 1.0), '[1.0]' (exactly 1.0), '(1.0,)', '(,2.0]', '[1.0,2.0)', ...
+/// an empty range matches any version
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct VersionRange {
+    min: Option<NuGetVersion>,
+    min_inclusive: bool,
+    max: Option<NuGetVersion>,
+    max_inclusive: bool,
+}
+
+impl VersionRange {
+    /// matches any version
+    pub fn any() -> VersionRange {
+        VersionRange::default()
+    }
+
+    pub fn exact(v: NuGetVersion) -> VersionRange {
+        VersionRange {
+            min: Some(v.clone()),
+            min_inclusive: true,
+            max: Some(v),
+            max_inclusive: true,
+        }
+    }
+
+    pub fn at_least(v: NuGetVersion) -> VersionRange {
+        VersionRange {
+            min: Some(v),
+            min_inclusive: true,
+            max: None,
+            max_inclusive: false,
+        }
+    }
+
+    pub fn min(&self) -> Option<&NuGetVersion> {
+        self.min.as_ref()
+    }
+    pub fn is_min_inclusive(&self) -> bool {
+        self.min_inclusive
+    }
+    pub fn max(&self) -> Option<&NuGetVersion> {
+        self.max.as_ref()
+    }
+    pub fn is_max_inclusive(&self) -> bool {
+        self.max_inclusive
+    }
+
+    pub fn is_any(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    pub fn satisfies(&self, v: &NuGetVersion) -> bool {
+        let above_min = match &self.min {
+            Some(min) if self.min_inclusive => v >= min,
+            Some(min) => v > min,
+            None => true,
+        };
+        let below_max = match &self.max {
+            Some(max) if self.max_inclusive => v <= max,
+            Some(max) => v < max,
+            None => true,
+        };
+        above_min && below_max
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = RocoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RocoError::InvalidVersion(s.to_string());
+        let r = s.trim();
+        if r.is_empty() {
+            return Ok(VersionRange::any());
+        }
+
+        let min_inclusive = match r.chars().next() {
+            Some('[') => true,
+            Some('(') => false,
+            // a plain version is a lower bound
+            _ => return Ok(VersionRange::at_least(r.parse()?)),
+        };
+        let max_inclusive = match r.chars().last() {
+            Some(']') => true,
+            Some(')') => false,
+            _ => return Err(invalid()),
+        };
+        if r.len() < 2 {
+            return Err(invalid());
+        }
+        let inner = &r[1..r.len() - 1];
+        let bound = |b: &str| match b.trim() {
+            "" => Ok(None),
+            b => b.parse::<NuGetVersion>().map(Some),
+        };
+
+        let range = match inner.split_once(',') {
+            Some((min, max)) => VersionRange {
+                min: bound(min)?,
+                min_inclusive,
+                max: bound(max)?,
+                max_inclusive,
+            },
+            // '[1.0]' is the only valid form without a ','
+            None if min_inclusive && max_inclusive => {
+                VersionRange::exact(bound(inner)?.ok_or_else(invalid)?)
+            }
+            None => return Err(invalid()),
+        };
+        if let (Some(min), Some(max)) = (&range.min, &range.max) {
+            let valid = match range.min_inclusive && range.max_inclusive {
+                true => min <= max,
+                false => min < max,
+            };
+            if !valid {
+                return Err(invalid());
+            }
+        }
+        Ok(range)
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let opt = |v: &Option<NuGetVersion>| match v {
+            Some(v) => v.to_normalized_string(),
+            None => String::new(),
+        };
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) if self.min_inclusive && self.max_inclusive && min == max => {
+                write!(f, "[{}]", min.to_normalized_string())
+            }
+            _ => write!(
+                f,
+                "{}{}, {}{}",
+                match self.min_inclusive {
+                    true => '[',
+                    false => '(',
+                },
+                opt(&self.min),
+                opt(&self.max),
+                match self.max_inclusive {
+                    true => ']',
+                    false => ')',
+                }
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> NuGetVersion {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_normalize_test() {
+        assert_eq!(v("1").to_string(), "1.0.0");
+        assert_eq!(v("1.02.003").to_string(), "1.2.3");
+        assert_eq!(v("1.2.3.0").to_string(), "1.2.3");
+        assert_eq!(v("1.2.3.4").to_string(), "1.2.3.4");
+        assert_eq!(v(" 1.0-Beta.2 ").to_string(), "1.0.0-Beta.2");
+        assert_eq!(
+            v("1.0.0-rc.1+sha.5114f85").to_string(),
+            "1.0.0-rc.1+sha.5114f85"
+        );
+        assert_eq!(
+            v("1.0.0-rc.1+sha.5114f85").to_normalized_string(),
+            "1.0.0-rc.1"
+        );
+        assert_eq!(v("1.0.0+build").metadata(), Some("build"));
+        assert!(v("1.0.0-alpha-1").is_prerelease());
+        assert_eq!(v("1.0.0-alpha-1").release(), "alpha-1");
+
+        for s in [
+            "",
+            "a",
+            "1.",
+            ".1",
+            "1.2.3.4.5",
+            "1.0-",
+            "1.0-beta..1",
+            "1.0+",
+            "v1.0",
+            "1.0-be_ta",
+            "1.0-beta.01",
+        ] {
+            assert!(
+                matches!(s.parse::<NuGetVersion>(), Err(RocoError::InvalidVersion(_))),
+                "'{}' should be invalid",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn ordering_test() {
+        assert!(v("2.0.2") > v("1.12.0"));
+        assert!(v("1.2.3.4") > v("1.2.3"));
+        assert!(v("1.1.0") > v("1.1.0-alpha"));
+        assert!(v("1.0.0-alpha.1") > v("1.0.0-alpha"));
+        assert!(v("1.0.0-alpha.beta") > v("1.0.0-alpha.1"));
+        assert!(v("1.0.0-beta.11") > v("1.0.0-beta.2"));
+        assert!(v("1.0.0-rc.1") > v("1.0.0-beta.11"));
+        assert_eq!(v("1.1"), v("1.1.0.0"));
+        assert_eq!(v("1.0.0-BETA"), v("1.0.0-beta"));
+        assert_eq!(v("1.0.0+a"), v("1.0.0+b"));
+
+        let mut versions = [v("1.0.0"), v("1.0.0-rc.1"), v("0.9"), v("1.0.0-beta.2")];
+        versions.sort();
+        let versions: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+        assert_eq!(
+            versions,
+            vec!["0.9.0", "1.0.0-beta.2", "1.0.0-rc.1", "1.0.0"]
+        );
+    }
+
+    #[test]
+    fn choco_compat_test() {
+        let compat = VersionComparison::ChocoCompat;
+        assert!(is_newer("2.1.0", "1.0.0", compat));
+        assert!(is_newer("1.2.3.1-beta1", "1.2.3", compat));
+        assert!(!is_newer("1.2.3.1-beta1", "1.2.3.2", compat));
+        assert!(is_newer("1.11-alpha", "1.07", compat));
+        assert!(is_newer("1.11", "1.07-alpha", compat));
+        assert!(is_newer("1.1.0-beta2", "1.1.0-beta1", compat));
+        // the prerelease part is a single string for choco
+        assert!(is_newer("1.1.0-beta2", "1.1.0-beta14", compat));
+        assert!(is_newer("1.1.0-beta.2", "1.1.0-beta.14", compat));
+        assert!(!is_newer(
+            "1.1.0-beta.2",
+            "1.1.0-beta.14",
+            VersionComparison::SemVer2
+        ));
+        assert!(!is_newer("1.1.0-alpha", "1.1.0", compat));
+        assert!(!is_newer("1.1.0", "1.1", compat));
+        assert!(!is_newer("1.1", "1.1.0", compat));
+        assert!(!is_newer("not-a-version", "1.0", compat));
+        assert!(!is_newer("1.0", "not-a-version", compat));
+
+        assert_eq!(
+            compare("1.1.0-beta.2", "1.1.0-beta.14", compat),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare("1.1.0-beta.2", "1.1.0-beta.14", VersionComparison::SemVer2),
+            Ordering::Less
+        );
+        assert_eq!(compare("1.1", "1.1.0.0-BETA", compat), Ordering::Greater);
+        assert_eq!(compare("1.0-Beta", "1.0.0-beta", compat), Ordering::Equal);
+        assert_eq!(compare("Latest", "latest", compat), Ordering::Equal);
+    }
+
+    #[test]
+    fn version_range_test() {
+        let r = |s: &str| s.parse::<VersionRange>().unwrap();
+
+        assert!(r("").is_any());
+        assert!(r("").satisfies(&v("0.0.1-alpha")));
+
+        assert!(r("1.0").satisfies(&v("1.0")));
+        assert!(r("1.0").satisfies(&v("3.2")));
+        assert!(!r("1.0").satisfies(&v("0.9.9")));
+        assert!(!r("1.0").satisfies(&v("1.0.0-rc.1")));
+
+        assert!(r("[1.0]").satisfies(&v("1.0.0.0")));
+        assert!(!r("[1.0]").satisfies(&v("1.0.1")));
+
+        assert!(r("(1.0,)").satisfies(&v("1.0.1")));
+        assert!(!r("(1.0,)").satisfies(&v("1.0")));
+
+        assert!(r("(,2.0]").satisfies(&v("2.0")));
+        assert!(!r("(,2.0)").satisfies(&v("2.0")));
+        assert!(r("(,2.0)").satisfies(&v("2.0-beta")));
+
+        let range = r("[1.0, 2.0)");
+        assert!(range.satisfies(&v("1.0")));
+        assert!(range.satisfies(&v("1.9.9.9")));
+        assert!(!range.satisfies(&v("2.0")));
+        assert_eq!(range.min(), Some(&v("1.0")));
+        assert!(range.is_min_inclusive());
+        assert!(!range.is_max_inclusive());
+
+        assert_eq!(r("1.0").to_string(), "[1.0.0, )");
+        assert_eq!(r("[1.0]").to_string(), "[1.0.0]");
+        assert_eq!(r(" ( 1.0 , 2.0 ] ").to_string(), "(1.0.0, 2.0.0]");
+        assert_eq!(r("(,2.0)").to_string(), "(, 2.0.0)");
+
+        for s in [
+            "(1.0)",
+            "[1.0",
+            "1.0]",
+            "[2.0,1.0]",
+            "(1.0,1.0)",
+            "[a,b]",
+            "[",
+            "[1.0,2.0,3.0]",
+        ] {
+            assert!(
+                matches!(s.parse::<VersionRange>(), Err(RocoError::InvalidVersion(_))),
+                "'{}' should be invalid",
+                s
+            );
+        }
+    }
+}
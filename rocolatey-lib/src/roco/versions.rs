@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+
+use crate::roco::remote::{get_enabled_feeds, query_feeds};
+use crate::roco::search::{sort_by_priority, version_key};
+use crate::roco::{local, nuget2, nuget3, version};
+use crate::roco::{ChocoHome, Feed, FeedType, Package, RocoError};
+use crate::{println_verbose, version_comparison};
+
+// `roco versions`: every published version of a package on each enabled source,
+// e.g. to decide what to roll back to
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageVersion {
+    pub id: String,
+    pub version: String,
+    /// name of the source offering this version
+    pub source: String,
+}
+
+async fn get_package_versions_on_feed(
+    feed: &Feed,
+    id: &str,
+    prerelease: bool,
+) -> Result<Vec<Package>, RocoError> {
+    match &feed.feed_type {
+        FeedType::LocalFileSystem => {
+            let pkg = Package {
+                id: id.to_string(),
+                version: String::new(),
+                pinned: false,
+                dependencies: None,
+            };
+            local::get_nupkgs_from_path(&[pkg], feed, prerelease)
+        }
+        FeedType::NuGetV2 => nuget2::find_packages_by_id(feed, id, prerelease).await,
+        FeedType::NuGetV3 => nuget3::get_package_versions(feed, id, prerelease).await,
+        FeedType::Unknown => Err(RocoError::UnknownFeedType(feed.name.clone())),
+    }
+}
+
+/// versions of each feed (given in priority order), newest first per feed;
+/// a version is listed once per feed, but for every feed offering it
+pub(crate) fn collect_package_versions(
+    results: Vec<(String, Vec<Package>)>,
+) -> Vec<PackageVersion> {
+    let mut res = Vec::new();
+    for (source, mut packages) in results {
+        packages.sort_by(|a, b| version::compare(&b.version, &a.version, version_comparison()));
+        let mut seen = HashSet::new();
+        for p in packages {
+            if seen.insert(version_key(&p.version)) {
+                res.push(PackageVersion {
+                    id: p.id,
+                    version: p.version,
+                    source: source.clone(),
+                });
+            }
+        }
+    }
+    res
+}
+
+/// the versions found, next to the errors of the sources that could not be queried
+/// (fails if none of them could)
+pub async fn get_package_versions(
+    home: &ChocoHome,
+    id: &str,
+    prerelease: bool,
+) -> Result<(Vec<PackageVersion>, Vec<RocoError>), RocoError> {
+    let mut feeds = get_enabled_feeds(home).await?;
+    sort_by_priority(&mut feeds);
+
+    let (results, errors) = query_feeds(feeds, |feed| {
+        let id = id.to_string();
+        async move {
+            println_verbose(&format!(
+                "list versions of '{}' on feed '{}'",
+                id, feed.name
+            ));
+            get_package_versions_on_feed(&feed, &id, prerelease).await
+        }
+    })
+    .await?;
+    Ok((collect_package_versions(results), errors))
+}
+
+/// `id version [source]`, limitoutput: `id|version|source`,
+/// next to the errors of the sources that could not be queried
+pub async fn get_package_versions_text(
+    home: &ChocoHome,
+    id: &str,
+    prerelease: bool,
+    limit_output: bool,
+) -> Result<(String, Vec<RocoError>), RocoError> {
+    let (versions, errors) = get_package_versions(home, id, prerelease).await?;
+    let mut lines: Vec<String> = versions
+        .iter()
+        .map(|v| match limit_output {
+            true => format!("{}|{}|{}", v.id, v.version, v.source),
+            false => format!("{} {} [{}]", v.id, v.version, v.source),
+        })
+        .collect();
+    if !limit_output {
+        let sources: HashSet<&str> = versions.iter().map(|v| v.source.as_str()).collect();
+        lines.push(format!(
+            "{} versions found on {} sources.",
+            versions.len(),
+            sources.len()
+        ));
+    }
+    Ok((lines.join("\r\n"), errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roco::testutil::{feed, pkg};
+
+    #[test]
+    fn collect_package_versions_test() {
+        let versions = collect_package_versions(vec![
+            (
+                "internal".to_string(),
+                vec![
+                    pkg("git", "2.39.0", &[]),
+                    pkg("git", "2.40.0", &[]),
+                    pkg("git", "2.40.0.0", &[]),
+                ],
+            ),
+            ("empty".to_string(), vec![]),
+            (
+                "community".to_string(),
+                vec![
+                    pkg("Git", "2.9.0", &[]),
+                    pkg("Git", "2.41.0-rc1", &[]),
+                    pkg("Git", "2.40.0", &[]),
+                ],
+            ),
+        ]);
+        assert_eq!(
+            versions
+                .iter()
+                .map(|v| format!("{}|{}|{}", v.id, v.version, v.source))
+                .collect::<Vec<String>>(),
+            [
+                "git|2.40.0|internal",
+                "git|2.39.0|internal",
+                "Git|2.41.0-rc1|community",
+                "Git|2.40.0|community",
+                "Git|2.9.0|community",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn get_package_versions_on_folder_feed_test() {
+        let tests_root = std::env::var("RocolateyTestRoot").unwrap();
+        let repo = std::path::Path::new(&tests_root).join("fake_repo");
+        let feed = feed(
+            "fake_repo",
+            &repo.to_string_lossy(),
+            0,
+            FeedType::LocalFileSystem,
+        );
+        let versions = |prerelease| {
+            let feed = feed.clone();
+            async move {
+                let packages = get_package_versions_on_feed(&feed, "firefox", prerelease)
+                    .await
+                    .unwrap();
+                collect_package_versions(vec![(feed.name.clone(), packages)])
+                    .into_iter()
+                    .map(|v| v.version)
+                    .collect::<Vec<String>>()
+            }
+        };
+        assert_eq!(versions(false).await, ["81.0.2.0"]);
+        assert_eq!(versions(true).await, ["81.0.2.0", "81.0.2.0-alpha2"]);
+    }
+}
@@ -1,10 +1,12 @@
 use warp::Filter;
 extern crate clap;
 use clap::{Arg, Command};
+use warp::http::StatusCode;
 
 use rocolatey_lib::roco::{
     local::{get_local_bad_packages_text, get_local_packages_text},
     remote::get_outdated_packages_text,
+    ChocoHome, RocoError,
 };
 
 #[tokio::main]
@@ -29,8 +31,26 @@ async fn main() {
                 .value_parser(clap::value_parser!(String))
                 .default_value("127.0.0.1"),
         )
+        .arg(
+            Arg::new("choco-root")
+                .long("choco-root")
+                .value_name("DIR")
+                .help("Chocolatey installation to serve (default: env:ChocolateyInstall)"),
+        )
         .get_matches();
 
+    let home = match matches.get_one::<String>("choco-root") {
+        Some(root) => ChocoHome::from_dir(root),
+        None => ChocoHome::from_env(),
+    };
+    let home = match home {
+        Ok(home) => home,
+        Err(e) => {
+            eprintln!("ERROR: {}", e);
+            std::process::exit(2);
+        }
+    };
+
     let bind_addr: &str = matches
         .get_one::<String>("address")
         .map(String::as_str)
@@ -44,45 +64,54 @@ async fn main() {
 
     println!(" server binds on ip: {}", bind_addr);
     println!(" server binds on port: {}", bind_port);
+    println!(" chocolatey root: {}", home.root().to_string_lossy());
 
-    let warp_filter = create_warp_filter();
+    let warp_filter = create_warp_filter(home);
     let server_ip: std::net::Ipv4Addr = bind_addr.parse().unwrap();
     warp::serve(warp_filter).run((server_ip, bind_port)).await;
 }
 
-fn create_warp_filter() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
-{
+fn create_warp_filter(
+    home: ChocoHome,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     let api_base = warp::path("rocolatey");
+    let home = warp::any().map(move || home.clone());
 
     let local = api_base
         .and(warp::path("local"))
         .and(warp::path::end())
-        .map(|| req_local(false));
+        .and(home.clone())
+        .map(|home| req_local(home, false));
 
     let local_r = api_base
         .and(warp::path!("local" / "r"))
         .and(warp::path::end())
-        .map(|| req_local(true));
+        .and(home.clone())
+        .map(|home| req_local(home, true));
 
     let bad = api_base
         .and(warp::path("bad"))
         .and(warp::path::end())
-        .map(|| req_local_bad(false));
+        .and(home.clone())
+        .map(|home| req_local_bad(home, false));
 
     let bad_r = api_base
         .and(warp::path!("bad" / "r"))
         .and(warp::path::end())
-        .map(|| req_local_bad(true));
+        .and(home.clone())
+        .map(|home| req_local_bad(home, true));
 
     let outdated = api_base
         .and(warp::path("outdated"))
         .and(warp::path::end())
-        .and_then(|| req_outdated(false));
+        .and(home.clone())
+        .and_then(|home| req_outdated(home, false));
 
     let outdated_r = api_base
         .and(warp::path!("outdated" / "r"))
         .and(warp::path::end())
-        .and_then(|| req_outdated(true));
+        .and(home)
+        .and_then(|home| req_outdated(home, true));
 
     let routes = local
         .or(local_r)
@@ -105,17 +134,47 @@ fn create_warp_filter() -> impl Filter<Extract = impl warp::Reply, Error = warp:
         .with(warp::log("rocolatey_server"))
 }
 
-fn req_local(limitoutput: bool) -> String {
-    get_local_packages_text("all", limitoutput)
+fn error_status(e: &RocoError) -> StatusCode {
+    match e {
+        RocoError::PackageNotFound(_)
+        | RocoError::SourceNotFound(_)
+        | RocoError::ConfigKeyNotFound(_) => StatusCode::NOT_FOUND,
+        RocoError::InvalidVersion(_) => StatusCode::BAD_REQUEST,
+        RocoError::FeedUnreachable { .. }
+        | RocoError::FeedAuth { .. }
+        | RocoError::HttpStatus { .. }
+        | RocoError::FeedResponse { .. }
+        | RocoError::UnknownFeedType(_)
+        | RocoError::AllFeedsFailed(_) => StatusCode::BAD_GATEWAY,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn text_reply(result: Result<String, RocoError>) -> warp::reply::WithStatus<String> {
+    match result {
+        Ok(text) => warp::reply::with_status(text, StatusCode::OK),
+        Err(e) => {
+            eprintln!("request failed: {}", e);
+            warp::reply::with_status(e.to_string(), error_status(&e))
+        }
+    }
 }
 
-fn req_local_bad(limitoutput: bool) -> String {
-    get_local_bad_packages_text(limitoutput)
+fn req_local(home: ChocoHome, limitoutput: bool) -> warp::reply::WithStatus<String> {
+    text_reply(get_local_packages_text(&home, "all", limitoutput))
 }
 
-async fn req_outdated(limit_output: bool) -> Result<impl warp::Reply, warp::Rejection> {
-    let result = get_outdated_packages_text("all", limit_output, false, false, true, true).await;
-    Ok(result)
+fn req_local_bad(home: ChocoHome, limitoutput: bool) -> warp::reply::WithStatus<String> {
+    text_reply(get_local_bad_packages_text(&home, limitoutput))
+}
+
+async fn req_outdated(
+    home: ChocoHome,
+    limit_output: bool,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let result =
+        get_outdated_packages_text(&home, "all", limit_output, false, false, true, true).await;
+    Ok(text_reply(result))
 }
 
 #[cfg(test)]
@@ -125,7 +184,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_local_endpoint() {
-        let warp_filter = create_warp_filter();
+        let warp_filter = create_warp_filter(ChocoHome::from_env().unwrap());
 
         let response = request()
             .method("GET")
@@ -139,7 +198,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_bad_endpoint() {
-        let warp_filter = create_warp_filter();
+        let warp_filter = create_warp_filter(ChocoHome::from_env().unwrap());
 
         let response = request()
             .method("GET")
@@ -153,7 +212,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_outdated_endpoint() {
-        let warp_filter = create_warp_filter();
+        let warp_filter = create_warp_filter(ChocoHome::from_env().unwrap());
 
         let response = request()
             .method("GET")
@@ -165,9 +224,24 @@ mod tests {
         // assert!(std::str::from_utf8(response.body()).unwrap().contains("outdated packages"));
     }
 
+    #[tokio::test]
+    async fn test_missing_choco_root() {
+        let warp_filter = create_warp_filter(ChocoHome::new("does/not/exist"));
+
+        let response = request()
+            .method("GET")
+            .path("/rocolatey/local")
+            .reply(&warp_filter)
+            .await;
+
+        // no lib/ folder means no packages, not an error
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.body(), "\r\n0 packages installed.");
+    }
+
     #[tokio::test]
     async fn test_unmatched_endpoint() {
-        let warp_filter = create_warp_filter();
+        let warp_filter = create_warp_filter(ChocoHome::from_env().unwrap());
 
         let response = request()
             .method("GET")